@@ -1,14 +1,18 @@
 //! Mastodon API v1.  
 
 pub mod accounts;
+pub mod admin;
 pub mod apps;
+pub mod blocks;
 pub mod conversations;
 pub mod follow_requests;
 pub mod followed_tags;
 pub mod instance; // pub mod accounts;
 pub mod lists;
 pub mod notifications;
+pub mod polls;
 pub mod preferences;
 pub mod push;
+pub mod statuses;
 pub mod streaming;
 pub mod timelines;
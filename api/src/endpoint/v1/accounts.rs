@@ -3,10 +3,14 @@
 
 //pub mod statuses;
 //pub mod unfollow;
+pub mod aliases;
+pub mod block;
 pub mod follow;
 pub mod following;
 pub mod lookup;
+pub mod migration;
 pub mod relationships;
+pub mod unblock;
 pub mod unfollow;
 pub mod verify_credentials;
 // https://docs.joinmastodon.org/methods/accounts/
@@ -0,0 +1,109 @@
+//! Account aliases(alsoKnownAs). Needed to move an account to this server.
+//! GET /api/v1/accounts/aliases HTTP/1.1
+//! POST /api/v1/accounts/aliases?acct=seungjin@mas.to HTTP/1.1
+//! DELETE /api/v1/accounts/aliases?acct=seungjin@mas.to HTTP/1.1
+//!
+//! `acct` is either account uri(user@domain) or actor url.
+//! Returns: Array of actor url
+//! OAuth: User token + write:accounts
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+use url::Url;
+
+use crate::http_response::HttpResponse;
+use sparrow::mastodon::account::actor_url::ActorUrl;
+use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        Method::Post => post(req, params).await,
+        Method::Delete => delete(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, _params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    aliases(&me_account.also_known_as)
+}
+
+pub async fn post(req: Request, _params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    let actor_url = match acct(&req) {
+        Some(a) => ActorUrl::resolve(a.as_str()).await?,
+        None => return HttpResponse::invalid_request(),
+    };
+
+    // Old account should be reachable.
+    let alias_account = MAccount::fetch(actor_url).await?;
+    let alias = alias_account.actor_url.to_string();
+
+    let mut also_known_as = me_account.also_known_as.to_owned();
+    if !also_known_as.contains(&alias) {
+        also_known_as.push(alias);
+        me_account.set_aliases(also_known_as.to_owned()).await?;
+    }
+
+    aliases(&also_known_as)
+}
+
+pub async fn delete(req: Request, _params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    let alias = match acct(&req) {
+        Some(a) => ActorUrl::resolve(a.as_str()).await?.to_string(),
+        None => return HttpResponse::invalid_request(),
+    };
+
+    let also_known_as: Vec<String> = me_account
+        .also_known_as
+        .iter()
+        .filter(|x| **x != alias)
+        .map(|x| x.to_owned())
+        .collect();
+    me_account.set_aliases(also_known_as.to_owned()).await?;
+
+    aliases(&also_known_as)
+}
+
+fn acct(req: &Request) -> Option<String> {
+    let parsed_url = Url::parse(req.uri()).ok()?;
+    parsed_url
+        .query_pairs()
+        .into_owned()
+        .find(|(k, _)| k == "acct")
+        .map(|(_, v)| v)
+}
+
+fn aliases(also_known_as: &Vec<String>) -> Result<Response> {
+    let a = serde_json::to_string(also_known_as).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(a)
+        .build())
+}
@@ -84,12 +84,16 @@ pub async fn post(req: Request, params: Params) -> Result<Response> {
     .await?;
 
     if send_result == 202u16 {
-        // Let's return relationship.
-        //let me
-        //let follower =
+        // Wait for Accept or Reject.
+        MFollow::request(
+            follow_object.id,
+            from_account.uid.to_owned(),
+            to_account.uid.to_owned(),
+        )
+        .await?;
 
-        let mut r = Relationship::new(&from_account, &to_account).await?;
-        r.requested = true;
+        // Let's return relationship.
+        let r = Relationship::new(&from_account, &to_account).await?;
         let a = serde_json::to_string(&r).unwrap();
 
         tracing::debug!(a);
@@ -0,0 +1,85 @@
+//! Move account to another account.
+//! POST /api/v1/accounts/migration?acct=seungjin@mas.to HTTP/1.1
+//!
+//! `acct` is either account uri(user@domain) or actor url.
+//! The target account must have this account in its alsoKnownAs.
+//! Returns: Account
+//! OAuth: User token + write:accounts
+//! Mastodon doc: <https://docs.joinmastodon.org/user/moving/#migration>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+use url::Url;
+
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::activity::r#move::Move as MoveActivity;
+use sparrow::mastodon::account::actor_url::ActorUrl;
+use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::account::Get as _;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Post => post(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn post(req: Request, _params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    let parsed_url = Url::parse(req.uri()).unwrap();
+    let acct = parsed_url
+        .query_pairs()
+        .into_owned()
+        .find(|(k, _)| k == "acct")
+        .map(|(_, v)| v);
+    let target_url = match acct {
+        Some(a) => ActorUrl::resolve(a.as_str()).await?,
+        None => return HttpResponse::invalid_request(),
+    };
+
+    // Refetch the target for its latest alsoKnownAs.
+    target_url.actor().await?.store().await?;
+    let target_account = MAccount::get(target_url).await?;
+
+    if !target_account
+        .also_known_as
+        .contains(&me_account.actor_url.to_string())
+    {
+        tracing::error!(
+            "{} is not an alias of {}",
+            me_account.actor_url,
+            target_account.actor_url
+        );
+        return HttpResponse::unprocessable_content();
+    }
+
+    me_account.move_to(&target_account).await?;
+
+    let move_activity = MoveActivity::new(
+        me_account.actor_url.to_string(),
+        target_account.actor_url.to_string(),
+    )
+    .await;
+    sparrow::mastodon::post_activity_to_audience(
+        me_account.to_owned(),
+        move_activity,
+    )
+    .await?;
+
+    let moved_account = MAccount::get(me_account.uid).await?;
+    let a = serde_json::to_string(&moved_account).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(a)
+        .build())
+}
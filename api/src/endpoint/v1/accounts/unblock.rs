@@ -0,0 +1,68 @@
+//! Unblock account  
+//! POST /api/v1/accounts/:id/unblock HTTP/1.1
+//!
+//! Returns: Relationship
+//! OAuth: User token + write:blocks
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/accounts/#unblock>
+
+use anyhow::Result;
+use serde_json::json;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::activity::undo::Undo;
+use sparrow::mastodon::account::uid::Uid;
+use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::account::Get as _;
+use sparrow::mastodon::block::Block as MBlock;
+use sparrow::mastodon::relationship::Relationship;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Post => post(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn post(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+    let who_to_unblock = Uid(params.get("id").unwrap().to_string());
+    let who_to_unblock_account = MAccount::get(who_to_unblock).await?;
+
+    if let Some(uri) = MBlock::uri(&me_account, &who_to_unblock_account).await?
+    {
+        MBlock::undo(&me_account, &who_to_unblock_account).await?;
+
+        if !who_to_unblock_account.local() {
+            let block_object = json!({
+                "id": uri,
+                "type": "Block",
+                "actor": me_account.actor_url.to_string(),
+                "object": who_to_unblock_account.actor_url.to_string(),
+            });
+            let undo_activity =
+                Undo::new(me_account.actor_url.to_string(), block_object).await;
+            sparrow::mastodon::post_activity(
+                me_account.to_owned(),
+                undo_activity,
+            )
+            .await?;
+        }
+    }
+
+    let r = Relationship::new(&me_account, &who_to_unblock_account).await?;
+    let a = serde_json::to_string(&r).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(a)
+        .build())
+}
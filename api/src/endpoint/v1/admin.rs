@@ -0,0 +1,4 @@
+//! Mastodon admin API.  
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/admin/>
+
+pub mod reports;
@@ -0,0 +1,52 @@
+//! Admin reports API  
+//! GET /api/v1/admin/reports HTTP/1.1
+//! GET /api/v1/admin/reports/:id HTTP/1.1
+//!
+//! Returns: Array of Admin::Report, or Admin::Report
+//! OAuth: User token + admin:read:reports
+//! Permission: Manage Reports
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/admin/reports/>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::mastodon::report::Report as MReport;
+use sparrow::mastodon::user_role::UserRole;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub mod resolve;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+    if !UserRole::permitted(&me_account, UserRole::MANAGE_REPORTS).await? {
+        return HttpResponse::forbidden();
+    }
+
+    let return_body = match params.get("id") {
+        None => serde_json::to_string(&MReport::all().await?).unwrap(),
+        Some(id) => match MReport::find(id.to_string()).await? {
+            Some(report) => serde_json::to_string(&report).unwrap(),
+            None => return HttpResponse::not_found(),
+        },
+    };
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(return_body)
+        .build())
+}
@@ -0,0 +1,50 @@
+//! Mark report as resolved  
+//! POST /api/v1/admin/reports/:id/resolve HTTP/1.1
+//!
+//! Returns: Admin::Report
+//! OAuth: User token + admin:write:reports
+//! Permission: Manage Reports
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/admin/reports/#resolve>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::mastodon::report::Report as MReport;
+use sparrow::mastodon::user_role::UserRole;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Post => post(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn post(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+    if !UserRole::permitted(&me_account, UserRole::MANAGE_REPORTS).await? {
+        return HttpResponse::forbidden();
+    }
+    let id = params.get("id").unwrap().to_string();
+
+    if MReport::find(id.to_owned()).await?.is_none() {
+        return HttpResponse::not_found();
+    }
+    MReport::resolve(id.to_owned(), &me_account).await?;
+
+    let report = MReport::find(id).await?;
+    let return_body = serde_json::to_string(&report).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(return_body)
+        .build())
+}
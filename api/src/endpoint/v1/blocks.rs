@@ -0,0 +1,38 @@
+//! View your blocks.  
+//! GET /api/v1/blocks HTTP/1.1
+//!
+//! Returns: Array of Account
+//! OAuth: User token + read:blocks or follow
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/blocks/#get>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::mastodon::block::Block as MBlock;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, _params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+    let blocks = MBlock::blocks(&me_account).await?;
+    let r = serde_json::to_string(&blocks).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
@@ -1,11 +1,18 @@
-// https://docs.joinmastodon.org/methods/follow_requests/
+//! Follow requests API  
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/follow_requests/>
+
 use anyhow::Result;
-use spin_sdk::{
-    http::{IntoResponse, Method, Params, Request, Response},
-    sqlite::{QueryResult, Value},
-};
+use spin_sdk::http::{IntoResponse, Method, Params, Request, Response};
 
 use crate::http_response::HttpResponse;
+use sparrow::mastodon::account::uid::Uid as AccountUid;
+use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::account::Get as _;
+use sparrow::mastodon::ME_ACCOUNT;
+use sparrow::table::follow::Follow as TFollow;
+
+pub mod authorize;
+pub mod reject;
 
 pub async fn request(
     req: Request,
@@ -28,15 +35,18 @@ pub async fn get(req: Request, _params: Params) -> Result<Response> {
         req.header("x-real-ip").unwrap().as_str().unwrap()
     );
 
-    // TODO: implement this.
-    // Return empty array for now.
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
 
-    let tags: Vec<sparrow::mastodon::account::Account> = Vec::new();
-    let return_body = serde_json::to_string(&tags).unwrap();
+    let mut accounts: Vec<MAccount> = Vec::new();
+    for f in TFollow::requests(me_account.uid.to_string()).await? {
+        let account_uid = AccountUid(f.account_uid.unwrap_or_default());
+        accounts.push(MAccount::get(account_uid).await?);
+    }
+    let return_body = serde_json::to_string(&accounts).unwrap();
 
     Ok(Response::builder()
         .status(200)
-        .header("Content-Type", "application/activity+json")
+        .header("Content-Type", "application/json")
         .body(return_body)
         .build())
 }
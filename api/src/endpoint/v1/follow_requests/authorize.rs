@@ -0,0 +1,73 @@
+//! Accept follow request  
+//! POST /api/v1/follow_requests/:account_id/authorize HTTP/1.1
+//!
+//! Returns: Relationship
+//! OAuth: User token + write:follows or follow
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/follow_requests/#accept>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::activity::follow::Follow;
+use sparrow::mastodon::account::uid::Uid as AccountUid;
+use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::account::Get as _;
+use sparrow::mastodon::follow::Follow as MFollow;
+use sparrow::mastodon::notification::Notification as MNotification;
+use sparrow::mastodon::relationship::Relationship;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Post => post(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn post(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+    let follower_uid = AccountUid(params.get("id").unwrap().to_string());
+    let follower_account = MAccount::get(follower_uid).await?;
+
+    let follow_record =
+        match MFollow::follow_record(&follower_account, &me_account).await? {
+            Some(f) if f.pending == Some(true) => f,
+            _ => return HttpResponse::not_found(),
+        };
+    let uri = follow_record.uri.unwrap_or_default();
+
+    MFollow::new(
+        uri.to_owned(),
+        follower_account.uid.to_owned(),
+        me_account.uid.to_owned(),
+    )
+    .await?;
+    MNotification::remove(uri.to_owned(), "Follow").await?;
+    MNotification::new(
+        "follow",
+        &me_account,
+        &follower_account,
+        uri.to_owned(),
+        "Follow",
+    )
+    .await?;
+
+    let follow = Follow::received(uri, &follower_account, &me_account);
+    Follow::accept(me_account.to_owned(), follow).await?;
+
+    let r = Relationship::new(&me_account, &follower_account).await?;
+    let a = serde_json::to_string(&r).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(a)
+        .build())
+}
@@ -0,0 +1,46 @@
+//! View a poll attached to a status.  
+//! GET /api/v1/polls/:id HTTP/1.1
+//!
+//! Returns: Poll
+//! OAuth: Public if parent status is public. User token + read:statuses if parent status is private.
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/polls/#get>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::mastodon::poll::Poll as MPoll;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub mod votes;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    // Announce results of my polls which have ended.
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+    MPoll::close_expired(&me_account).await?;
+
+    let poll = match MPoll::find(params.get("id").unwrap().to_string()).await? {
+        Some(p) => p,
+        None => return HttpResponse::not_found(),
+    };
+    let r = serde_json::to_string(&poll).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
@@ -0,0 +1,158 @@
+//! Vote on a poll attached to a status.  
+//! POST /api/v1/polls/:id/votes HTTP/1.1
+//!
+//! Returns: Poll
+//! OAuth: User token + write:statuses
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/polls/#vote>
+
+use anyhow::Result;
+use serde_json::Value;
+use spin_sdk::http::{Method, Params, Request, Response};
+use std::str;
+use uuid::Uuid;
+
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::activity::create::Create as CreateActivity;
+use sparrow::activitystream::object::note::Note;
+use sparrow::activitystream::object::ObjectType;
+use sparrow::mastodon::account::uid::Uid as AccountUid;
+use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::account::Get as _;
+use sparrow::mastodon::poll::Poll as MPoll;
+use sparrow::mastodon::ME_ACCOUNT;
+use sparrow::table::poll::Get as _;
+use sparrow::table::poll::Poll as TPoll;
+use sparrow::table::status::Get as _;
+use sparrow::table::status::Status as TStatus;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Post => post(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn post(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    let id = params.get("id").unwrap().to_string();
+    let tpoll =
+        match TPoll::get(("uid".to_string(), id.to_owned())).await?.last() {
+            Some(p) => p.to_owned(),
+            None => return HttpResponse::not_found(),
+        };
+    let tstatus =
+        match TStatus::get(("uid".to_string(), tpoll.status_uid.to_owned()))
+            .await?
+            .last()
+        {
+            Some(t) if t.deleted_at.is_none() => t.to_owned(),
+            _ => return HttpResponse::not_found(),
+        };
+
+    // Expired poll or already voted.
+    if MPoll::is_expired(&tpoll)
+        || !MPoll::choices(&me_account, &tpoll).await?.is_empty()
+    {
+        return HttpResponse::unprocessable_content();
+    }
+
+    let titles: Vec<String> = serde_json::from_str(&tpoll.options)?;
+    let mut choices = parse_choices(&req);
+    choices.sort();
+    choices.dedup();
+    if choices.is_empty()
+        || choices
+            .iter()
+            .any(|x| *x < 0 || *x as usize >= titles.len())
+        || (!tpoll.multiple && choices.len() > 1)
+    {
+        return HttpResponse::unprocessable_content();
+    }
+
+    let author =
+        MAccount::get(AccountUid(tpoll.account_uid.to_owned())).await?;
+
+    if author.local() {
+        MPoll::vote(&me_account, &tpoll, choices, None).await?;
+    } else {
+        // Each choice is sent to the poll's author as a Note with its name.
+        for choice in choices {
+            let vote = Note {
+                id: format!(
+                    "{}#votes/{}",
+                    me_account.actor_url,
+                    Uuid::new_v4()
+                ),
+                note_type: ObjectType::Note,
+                name: Some(titles[choice as usize].to_owned()),
+                in_reply_to: tstatus.uri.to_owned(),
+                attributed_to: Some(me_account.actor_url.to_string()),
+                to: Some(vec![author.actor_url.to_string()]),
+                ..Default::default()
+            };
+
+            let mut create_activity = CreateActivity::new(
+                me_account.actor_url.to_string(),
+                serde_json::to_value(&vote)?,
+            )
+            .await;
+            create_activity.to = Some(vec![author.actor_url.to_string()]);
+            create_activity.cc = None;
+
+            sparrow::mastodon::queue_delivery(
+                &me_account,
+                author.to_owned(),
+                &create_activity,
+            )
+            .await?;
+
+            MPoll::vote(&me_account, &tpoll, vec![choice], Some(vote.id))
+                .await?;
+        }
+    }
+
+    let poll = match MPoll::find(id).await? {
+        Some(p) => p,
+        None => return HttpResponse::not_found(),
+    };
+    let r = serde_json::to_string(&poll).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
+
+/// `choices[]` of form-urlencoded body or `choices` of JSON body.
+fn parse_choices(req: &Request) -> Vec<i64> {
+    let body = str::from_utf8(req.body()).unwrap_or_default();
+
+    if let Ok(v) = serde_json::from_str::<Value>(body) {
+        return v
+            .get("choices")
+            .and_then(|x| x.as_array())
+            .map(|x| {
+                x.iter()
+                    .filter_map(|y| match y {
+                        Value::Number(n) => n.as_i64(),
+                        Value::String(s) => s.parse().ok(),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    url::form_urlencoded::parse(body.as_bytes())
+        .filter(|(key, _)| key == "choices[]")
+        .filter_map(|(_, val)| val.parse().ok())
+        .collect()
+}
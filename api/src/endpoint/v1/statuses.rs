@@ -1,263 +1,333 @@
-// (POST) /api/v1/statuses
-// https://docs.joinmastodon.org/methods/statuses/#create
-// Returns: Status.
-// https://docs.joinmastodon.org/entities/Status/
+//! Statuses API  
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/>
 
 use anyhow::Result;
-use maplit::hashmap;
+use chrono::Utc;
+use comrak::{markdown_to_html, Options};
 use serde_json::Value;
-use sparrow::mastodon::media::MediaAttachment;
 use spin_sdk::http::{Method, Params, Request, Response};
-use spin_sdk::redis;
-use spin_sdk::sqlite::Value as SV;
-use spin_sdk::variables;
 use std::str;
-use uuid::Uuid;
-use comrak::{markdown_to_html, Options};
-use chrono::{TimeZone, Utc};
 
-use sparrow::activitypub::apo::{CollectionPage, Create, Note, Replies, RsaSignature2017};
-use sparrow::mastodon::{account::Account, application::Application, status::Status};
-use sparrow::postbox::Envelop;
-use sparrow::utils::get_current_time_in_iso_8601;
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::activity::create::Create as CreateActivity;
+use sparrow::activitystream::activity::delete::Delete as DeleteActivity;
+use sparrow::activitystream::object::note::Note;
+use sparrow::activitystream::object::question::Question;
+use sparrow::activitystream::object::tombstone::Tombstone;
+use sparrow::activitystream::PUBLIC;
+use sparrow::mastodon::poll::Poll as MPoll;
+use sparrow::mastodon::status::Status as MStatus;
+use sparrow::mastodon::ME_ACCOUNT;
+use sparrow::table::status::Get as _;
+use sparrow::table::status::Status as TStatus;
+
+pub mod history;
+pub mod pin;
+pub mod source;
+pub mod unpin;
+
+/// Maximum number of poll options.
+const MAX_POLL_OPTIONS: usize = 4;
+/// Shortest poll duration in seconds. 5 minutes.
+const MIN_POLL_EXPIRATION: i64 = 300;
+/// Longest poll duration in seconds. 1 month.
+const MAX_POLL_EXPIRATION: i64 = 2629746;
 
 pub async fn request(req: Request, params: Params) -> Result<Response> {
     match req.method() {
+        Method::Get => get(req, params).await,
         Method::Post => post(req, params).await,
-        _ => sparrow::http_response::HttpResponse::not_found().await,
+        Method::Delete => delete(req, params).await,
+        _ => HttpResponse::not_found(),
     }
 }
 
-// https://docs.joinmastodon.org/methods/statuses/#create
-pub async fn post(req: Request, _params: Params) -> Result<Response> {
-    let userid: i64 = match sparrow::auth::check_api_auth(&req).await.unwrap() {
-        sparrow::auth::TokenAuth::InValid => {
-            return sparrow::http_response::HttpResponse::unauthorized().await;
-        }
-        sparrow::auth::TokenAuth::TokenNotProvided => {
-            return sparrow::http_response::HttpResponse::unauthorized().await;
-        }
-        sparrow::auth::TokenAuth::Valid(userid) => Some(userid).unwrap() as i64,
+/// View a single status  
+///
+/// `GET /api/v1/statuses/:id HTTP/1.1`
+/// Returns: Status
+/// Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/#get>
+pub async fn get(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let tstatus = match find(params.get("id").unwrap()).await? {
+        Some(t) => t,
+        None => return HttpResponse::not_found(),
     };
 
-    // Get my actor address
-
-    let a = sparrow::db::Connection::builder()
-        .await
-        .execute(
-            "SELECT federationId FROM user WHERE id = ?",
-            &[SV::Integer(userid)],
-        )
-        .await;
-
-    let my_actor = a
-        .rows()
-        .next()
-        .unwrap()
-        .get::<&str>("federationId")
-        .unwrap();
-
-    // a="{\"language\":\"en\",\"media_attributes\":[],\"media_ids\":[],\"status\":\"bffvbb\",\"visibility\":\"public\"}"
-    let body_str = str::from_utf8(req.body()).unwrap();
-    let body: Value = serde_json::from_str(body_str).unwrap();
-    let status = body.get::<&str>("status").unwrap().as_str().unwrap();
+    let status = MStatus::fr_tstatus(tstatus).await?;
+    let r = serde_json::to_string(&status).unwrap();
 
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
 
-    let mut options = Options::default();
-    options.extension.autolink = true;
-    let status_html = markdown_to_html(status, &options);
+/// Find not deleted status with its uid.
+pub async fn find(uid: &str) -> Result<Option<TStatus>> {
+    let tstatus = TStatus::get(("uid".to_string(), uid.to_string()))
+        .await?
+        .last()
+        .cloned();
+    Ok(tstatus.filter(|t| t.deleted_at.is_none()))
+}
 
-    let media_ids: &Value = body.get("media_ids").unwrap();
-    let mut media_ids_1 = media_ids.as_array().unwrap();
+/// Publish a status with the given parameters.  
+///
+/// `POST /api/v1/statuses HTTP/1.1`
+/// Returns: Status
+/// Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/#create>
+pub async fn post(req: Request, _params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
 
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
 
-    let mut media_attachements: Vec<MediaAttachment> = Vec::new();
+    let form = match StatusForm::parse(&req) {
+        Some(f) => f,
+        None => return HttpResponse::invalid_request(),
+    };
 
-    for media in media_ids_1 {
-        let id = media.as_str().unwrap();
-        let ma = sparrow::mastodon::media::MediaAttachment::create(id).await;
-        //let c = serde_json::to_string(&b).unwrap();
-        media_attachements.push(ma);
+    if form.status.trim().is_empty() && form.poll_options.is_empty() {
+        return HttpResponse::unprocessable_content();
     }
 
-    let uuid = Uuid::now_v7().to_string();
-    let now = get_current_time_in_iso_8601().await;
-    let url = format!("{my_actor}/{uuid}");
+    let poll_expires_in = match form.poll_options.is_empty() {
+        true => None,
+        false => match form.poll_expires_in {
+            Some(e)
+                if form.poll_options.len() >= 2
+                    && form.poll_options.len() <= MAX_POLL_OPTIONS
+                    && (MIN_POLL_EXPIRATION..=MAX_POLL_EXPIRATION)
+                        .contains(&e) =>
+            {
+                Some(e)
+            }
+            _ => return HttpResponse::unprocessable_content(),
+        },
+    };
 
-    // get my followers;
-    let b = sparrow::db::Connection::builder()
-        .await
-        .execute(
-            "SELECT federationId FROM follower WHERE userID = ?",
-            &[SV::Integer(userid)],
+    let mut options = Options::default();
+    options.extension.autolink = true;
+    let content = markdown_to_html(&form.status, &options);
+
+    let tstatus = MStatus::post(
+        &me_account,
+        content,
+        form.spoiler_text,
+        form.visibility.as_str(),
+        form.sensitive,
+        form.language,
+    )
+    .await?;
+
+    if let Some(expires_in) = poll_expires_in {
+        MPoll::new(
+            &me_account,
+            tstatus.uid.to_owned(),
+            form.poll_options,
+            Utc::now().timestamp() + expires_in,
+            form.poll_multiple,
         )
-        .await;
-
-
-    for f in b.rows() {
-        let recipient = f.get::<&str>("federationId").unwrap();
+        .await?;
+    }
 
-        // CREATE OBJECTS
+    let tstatus = match find(tstatus.uid.as_str()).await? {
+        Some(t) => t,
+        None => return HttpResponse::internal_server_error(),
+    };
 
-        let collection_page = CollectionPage {
-            kind: "CollectionPage".to_string(),
-            next: format!(
-                "{}/statuses/112001985738281244/replies?only_other_accounts=true&page=true",
-                my_actor
-            ),
-            part_of: format!("{}/statuses/112001985738281244/replies", my_actor),
-            items: vec![],
+    // Direct status is delivered to mentioned accounts only.
+    if tstatus.visibility != 3 {
+        let (object, to, cc) = match tstatus.poll_id.is_some() {
+            true => {
+                let question = Question::fr_tstatus(tstatus.to_owned()).await?;
+                let (to, cc) =
+                    (question.note.to.to_owned(), question.note.cc.to_owned());
+                (serde_json::to_value(question)?, to, cc)
+            }
+            false => {
+                let note = Note::fr_tstatus(tstatus.to_owned()).await?;
+                let (to, cc) = (note.to.to_owned(), note.cc.to_owned());
+                (serde_json::to_value(note)?, to, cc)
+            }
         };
 
-        let replies = Replies {
-            id: Uuid::now_v7().to_string(),
-            kind: "Replies".to_string(),
-            first: collection_page,
-        };
+        let mut create_activity =
+            CreateActivity::new(me_account.actor_url.to_string(), object).await;
+        create_activity.to = to;
+        create_activity.cc = cc;
 
-        let rsa_signature_2017 = RsaSignature2017 {
-            kind: "RsaSignature2017".to_string(),
-            creator: "".to_string(),
-            created: "".to_string(),
-            signature_value: "".to_string(),
-        };
+        sparrow::mastodon::post_activity_to_audience(
+            me_account.to_owned(),
+            create_activity,
+        )
+        .await?;
+    }
 
-        let note = Note {
-            id: format!("{my_actor}/statuses/{uuid}"),
-            kind: "Note".to_string(),
-            summary: None,
-            in_reply_to: None,
-            published: now.clone(),
-            url: url.clone(),
-            attributed_to: my_actor.to_string(),
-            to: vec!["https://www.w3.org/ns/activitystreams#Public".to_string()],
-            cc: vec![format!("{recipient}/followers")],
-            sensitive: false,
-            atom_uri: format!("{my_actor}/statuses/112001985738281244"),
-            in_relpay_to_atom_uri: None,
-            converation:
-                "tag:seungjin.ap.dev.seungjin.net,2024-02-27:objectId=306721066:objectType=Conversation"
-                    .to_string(),
-            content: status_html.clone(),
-            content_map: vec![hashmap! {
-              "en".to_string() => status.to_string(),
-            }],
-            attachment: media_attachements.clone(),
-            tag: vec![],
-            replies: replies,
-            //signature: rsa_signature_2017,
-        };
+    let status = MStatus::fr_tstatus(tstatus).await?;
+    let r = serde_json::to_string(&status).unwrap();
 
-        let create_object = Create {
-            context: vec!["https://www.w3.org/ns/activitystreams".to_string()],
-            id: format!("{my_actor}/statuses/{uuid}/activity"),
-            kind: "Create".to_string(),
-            actor: my_actor.to_string(),
-            published: now.clone(),
-            to: vec!["https://www.w3.org/ns/activitystreams#Public".to_string()],
-            cc: vec![format!("{recipient}/followers")],
-            object: note,
-        };
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
 
-        let b = serde_json::to_string(&create_object).unwrap();
-        tracing::debug!(b);
+/// Delete one of your own statuses.  
+///
+/// `DELETE /api/v1/statuses/:id HTTP/1.1`
+/// Returns: Status
+/// Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/#delete>
+pub async fn delete(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    let tstatus = match find(params.get("id").unwrap()).await? {
+        Some(t) if t.account_id == me_account.uid.to_string() => t,
+        _ => return HttpResponse::not_found(),
+    };
 
-        // GET all followers:
+    let status = MStatus::fr_tstatus(tstatus.to_owned()).await?;
+    TStatus::delete(tstatus.uid.to_owned()).await?;
 
-        let envelop = Envelop {
-            address: recipient.to_string(),
-            letter: &create_object,
+    // Remote servers purge it with the Tombstone.
+    if tstatus.visibility != 3 {
+        let former_type = match tstatus.poll_id.is_some() {
+            true => "Question",
+            false => "Note",
         };
-
-        let payload = serde_json::to_vec(&envelop).unwrap();
-
-        // let address = format!(
-        //    "redis://{}@{}:{}",
-        //    std::env::var("REDIS_CREDENTIAL").unwrap(),
-        //    std::env::var("REDIS_HOST").unwrap(),
-        //    std::env::var("REDIS_PORT").unwrap()
-        // );
-
-        let address = format!(
-            "redis://{}@{}:{}",
-            variables::get("redis_credential").unwrap(),
-            variables::get("redis_host").unwrap(),
-            variables::get("redis_port").unwrap(),
+        let tombstone = Tombstone::new(
+            tstatus.uri.to_owned().unwrap_or_default(),
+            Some(former_type.to_string()),
+            Some(Utc::now().to_rfc3339()),
         );
-        let conn = redis::Connection::open(&address)?;
-        let channel = variables::get("redis_channel").unwrap();
-        let _ = conn.publish(channel.as_str(), &payload);
+        let mut delete_activity = DeleteActivity::new::<Value>(
+            me_account.actor_url.to_string(),
+            serde_json::to_value(tombstone)?,
+        )
+        .await?;
+        delete_activity.to = Some(vec![PUBLIC.to_string()]);
+        delete_activity.cc =
+            Some(vec![format!("{}/followers", me_account.actor_url)]);
+
+        sparrow::mastodon::post_activity_to_audience(
+            me_account.to_owned(),
+            delete_activity,
+        )
+        .await?;
     }
-  
-    // PASS TO POSTBOX
-
-    let status = Status {
-        id: uuid.clone(),
-        created_at: now,
-        in_reply_to_id: None,
-        in_reply_to_account_id: None,
-        sensitive: false,
-        spoiler_text: None,
-        visibility: "public".to_string(),
-        language: "en".to_string(),
-        uri: format!("https://mastodon.social/users/Gargron/statuses/{uuid}"),
-        url: format!("https://mastodon.social/@Gargron/{uuid}"),
-        replies_count: 5,
-        reblogs_count: 6,
-        favourites_count: 11,
-        favourited: false,
-        reblogged: false,
-        muted: false,
-        bookmarked: false,
-        content: status_html.clone(),
-        reblog: None,
-        application: Application {
-            name: Some("web".to_string()),
-            website: None,
-        },
-        account: Account {
-            id: "1".to_string(),
-            username: "seungjin".to_string(),
-            acct: "seungjin@seungjin.ap.dev.seungjin.net".to_string(),
-            display_name: "Seungjin Kim".to_string(),
-            locked: false,
-            bot: false,
-            discoverable: true,
-            group: false,
-            created_at: Utc.with_ymd_and_hms(2015, 5, 15, 0, 0, 0).unwrap(),
-            note: "<p>FOOOFOOOFFOOO</p>".to_string(),
-            url: "https://mastodon.social/@Gargron".to_string(),
-            avatar: "https://files.mastodon.social/accounts/avatars/000/000/001/original/d96d39a0abb45b92.jpg".to_string(),
-            avatar_static: "https://files.mastodon.social/accounts/avatars/000/000/001/original/d96d39a0abb45b92.jpg".to_string(),
-            header: "https://files.mastodon.social/accounts/headers/000/000/001/original/c91b871f294ea63e.png".to_string(),
-            header_static: "https://files.mastodon.social/accounts/headers/000/000/001/original/c91b871f294ea63e.png".to_string(),
-            followers_count: 322930,
-            following_count: 459,
-            statuses_count: 61323,
-            last_status_at: Some(Utc.with_ymd_and_hms(2015, 5, 15, 0, 0, 0).unwrap()),
-            emojis: None,
-            fields: None, 
-        },
-        media_attachments: Some(media_attachements),
-        mentions: None,
-        tags: None,
-        emojis: None,
-        card: None, 
-        poll: None,
-    };
 
+    let r = serde_json::to_string(&status).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
 
+/// Form parameters of status creation. JSON or form-urlencoded body.
+#[derive(Default)]
+struct StatusForm {
+    status: String,
+    spoiler_text: String,
+    visibility: String,
+    sensitive: bool,
+    language: Option<String>,
+    poll_options: Vec<String>,
+    poll_expires_in: Option<i64>,
+    poll_multiple: bool,
+}
 
-    let json_str = serde_json::to_string(&status).unwrap();
-    
+impl StatusForm {
+    fn parse(req: &Request) -> Option<Self> {
+        let body = str::from_utf8(req.body()).ok()?;
+        let is_json = req
+            .header("Content-Type")
+            .and_then(|x| x.as_str())
+            .is_some_and(|x| x.contains("json"));
+
+        match is_json {
+            true => Self::fr_json(serde_json::from_str(body).ok()?),
+            false => Some(Self::fr_form(body)),
+        }
+    }
 
+    fn fr_json(v: Value) -> Option<Self> {
+        let string = |v: &Value, key: &str| {
+            v.get(key).and_then(|x| x.as_str()).map(String::from)
+        };
+        let boolean = |v: &Value, key: &str| match v.get(key) {
+            Some(Value::Bool(b)) => *b,
+            Some(Value::String(s)) => s == "true" || s == "1",
+            _ => false,
+        };
 
+        let poll = v.get("poll").cloned().unwrap_or_default();
+        let poll_options = poll
+            .get("options")
+            .and_then(|x| x.as_array())
+            .map(|x| {
+                x.iter()
+                    .filter_map(|y| y.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let poll_expires_in = match poll.get("expires_in") {
+            Some(Value::Number(n)) => n.as_i64(),
+            Some(Value::String(s)) => s.parse().ok(),
+            _ => None,
+        };
 
+        Some(StatusForm {
+            status: string(&v, "status").unwrap_or_default(),
+            spoiler_text: string(&v, "spoiler_text").unwrap_or_default(),
+            visibility: string(&v, "visibility")
+                .unwrap_or("public".to_string()),
+            sensitive: boolean(&v, "sensitive"),
+            language: string(&v, "language"),
+            poll_options,
+            poll_expires_in,
+            poll_multiple: boolean(&poll, "multiple"),
+        })
+    }
 
-    Ok(
-        Response::builder()
-        .status(200)
-        .header("Content-Type", "application/activity+json")
-        .build()
-    )
+    fn fr_form(body: &str) -> Self {
+        let mut form = StatusForm {
+            visibility: "public".to_string(),
+            ..Default::default()
+        };
+        for (key, val) in url::form_urlencoded::parse(body.as_bytes()) {
+            match key.as_ref() {
+                "status" => form.status = val.to_string(),
+                "spoiler_text" => form.spoiler_text = val.to_string(),
+                "visibility" => form.visibility = val.to_string(),
+                "sensitive" => form.sensitive = val == "true" || val == "1",
+                "language" => form.language = Some(val.to_string()),
+                "poll[options][]" => form.poll_options.push(val.to_string()),
+                "poll[expires_in]" => form.poll_expires_in = val.parse().ok(),
+                "poll[multiple]" => {
+                    form.poll_multiple = val == "true" || val == "1"
+                }
+                _ => (),
+            }
+        }
+        form
+    }
 }
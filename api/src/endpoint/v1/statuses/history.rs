@@ -0,0 +1,42 @@
+//! Get all known versions of a status, including the initial and current states.  
+//! GET /api/v1/statuses/:id/history HTTP/1.1
+//!
+//! Returns: Array of StatusEdit
+//! OAuth: Public for public statuses, user token + read:statuses for private statuses
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/#history>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::endpoint::v1::statuses::find;
+use crate::http_response::HttpResponse;
+use sparrow::mastodon::status_edit::StatusEdit;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let tstatus = match find(params.get("id").unwrap()).await? {
+        Some(t) => t,
+        None => return HttpResponse::not_found(),
+    };
+
+    let history = StatusEdit::history(tstatus).await?;
+    let r = serde_json::to_string(&history).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
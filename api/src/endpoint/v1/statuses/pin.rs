@@ -0,0 +1,82 @@
+//! Feature one of your own public statuses at the top of your profile.  
+//! POST /api/v1/statuses/:id/pin HTTP/1.1
+//!
+//! Returns: Status
+//! OAuth: User token + write:accounts
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/#pin>
+
+use anyhow::Result;
+use serde_json::Value;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::endpoint::v1::statuses::find;
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::activity::add::Add as AddActivity;
+use sparrow::activitystream::object::note::Note;
+use sparrow::mastodon::setting::Setting;
+use sparrow::mastodon::status::Status as MStatus;
+use sparrow::mastodon::status_pin::StatusPin as MStatusPin;
+use sparrow::mastodon::ME_ACCOUNT;
+
+/// Maximum number of pinned statuses.
+const MAX_PINS: usize = 5;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Post => post(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn post(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    let tstatus = match find(params.get("id").unwrap()).await? {
+        Some(t) => t,
+        None => return HttpResponse::not_found(),
+    };
+
+    // Only my own status, not a boost nor a direct message.
+    if tstatus.account_id != me_account.uid.to_string()
+        || tstatus.reblog_of_id.is_some()
+        || tstatus.visibility == 3
+    {
+        return HttpResponse::unprocessable_content();
+    }
+
+    if !MStatusPin::is_pinned(&me_account, &tstatus).await? {
+        if MStatusPin::pins(&me_account).await?.len() >= MAX_PINS {
+            return HttpResponse::unprocessable_content();
+        }
+
+        MStatusPin::new(&me_account, &tstatus).await?;
+
+        let note = Note::fr_tstatus(tstatus.to_owned()).await?;
+        let add_activity = AddActivity::new(
+            me_account.actor_url.to_string(),
+            Value::String(note.id),
+            format!("https://{}/collections/featured", Setting::domain().await),
+        )
+        .await;
+        sparrow::mastodon::post_activity_to_audience(
+            me_account.to_owned(),
+            add_activity,
+        )
+        .await?;
+    }
+
+    let status = MStatus::fr_tstatus(tstatus).await?;
+    let r = serde_json::to_string(&status).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
@@ -0,0 +1,41 @@
+//! Obtain the source properties for a status so that it can be edited.  
+//! GET /api/v1/statuses/:id/source HTTP/1.1
+//!
+//! Returns: StatusSource
+//! OAuth: User token + read:statuses
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/#source>
+
+use anyhow::Result;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::endpoint::v1::statuses::find;
+use crate::http_response::HttpResponse;
+use sparrow::mastodon::status_source::StatusSource;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let tstatus = match find(params.get("id").unwrap()).await? {
+        Some(t) => t,
+        None => return HttpResponse::not_found(),
+    };
+
+    let r = serde_json::to_string(&StatusSource::from(tstatus)).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
@@ -0,0 +1,67 @@
+//! Unfeature a status from the top of your profile.  
+//! POST /api/v1/statuses/:id/unpin HTTP/1.1
+//!
+//! Returns: Status
+//! OAuth: User token + write:accounts
+//! Mastodon doc: <https://docs.joinmastodon.org/methods/statuses/#unpin>
+
+use anyhow::Result;
+use serde_json::Value;
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::endpoint::v1::statuses::find;
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::activity::remove::Remove as RemoveActivity;
+use sparrow::activitystream::object::note::Note;
+use sparrow::mastodon::setting::Setting;
+use sparrow::mastodon::status::Status as MStatus;
+use sparrow::mastodon::status_pin::StatusPin as MStatusPin;
+use sparrow::mastodon::ME_ACCOUNT;
+
+pub async fn request(req: Request, params: Params) -> Result<Response> {
+    match req.method() {
+        Method::Post => post(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn post(req: Request, params: Params) -> Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+
+    let tstatus = match find(params.get("id").unwrap()).await? {
+        Some(t) => t,
+        None => return HttpResponse::not_found(),
+    };
+
+    if MStatusPin::is_pinned(&me_account, &tstatus).await? {
+        MStatusPin::undo(&me_account, &tstatus).await?;
+
+        let note = Note::fr_tstatus(tstatus.to_owned()).await?;
+        let remove_activity = RemoveActivity::new(
+            me_account.actor_url.to_string(),
+            Value::String(note.id),
+            format!("https://{}/collections/featured", Setting::domain().await),
+        )
+        .await;
+        sparrow::mastodon::post_activity_to_audience(
+            me_account.to_owned(),
+            remove_activity,
+        )
+        .await?;
+    }
+
+    let status = MStatus::fr_tstatus(tstatus).await?;
+    let r = serde_json::to_string(&status).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/json")
+        .body(r)
+        .build())
+}
@@ -5,8 +5,12 @@
 use anyhow::Result;
 use spin_sdk::http::{IntoResponse, Method, Params, Request, Response};
 
+use url::Url;
+
 use crate::http_response::HttpResponse;
-use sparrow::mastodon::status::Status;
+use sparrow::mastodon::status::Status as MStatus;
+use sparrow::mastodon::ME_ACCOUNT;
+use sparrow::table::status::Status as TStatus;
 
 pub async fn request(
     req: Request,
@@ -29,14 +33,30 @@ pub async fn get(req: Request, _params: Params) -> Result<Response> {
         req.header("x-real-ip").unwrap().as_str().unwrap()
     );
 
-    // TODO: implement this.
-    // View statuses from followed users and hashtags.
-    // Return empty array for now.
+    let url = req.uri();
+    let parsed_url = Url::parse(url).unwrap();
+    let query: Vec<(String, String)> =
+        parsed_url.query_pairs().into_owned().collect();
+
+    // Default 20 statuses. Max 40 statuses.
+    let mut limit = 20i64;
+    for (k, v) in query {
+        if k == "limit" {
+            limit = v.parse::<i64>().unwrap_or(20).clamp(1, 40);
+        }
+    }
 
-    //let home_timeline: Vec<sparrow::mastodon::status::Status> = Vec::new();
-    //let return_body = home_timeline.into();
+    // View statuses from followed users and boosts of them.
+    let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+    let tstatuses =
+        TStatus::home_timeline(me_account.uid.to_string(), limit).await?;
+
+    let mut home_timeline: Vec<MStatus> = Vec::new();
+    for tstatus in tstatuses {
+        home_timeline.push(MStatus::fr_tstatus(tstatus).await?);
+    }
 
-    let return_body = "[]".to_string();
+    let return_body = serde_json::to_string(&home_timeline).unwrap();
 
     Ok(Response::builder()
         .status(200)
@@ -77,10 +77,19 @@ async fn handle_api(req: Request) -> Result<impl IntoResponse> {
         endpoint::v1::followed_tags::request,
     );
     router.any_async("/api/v1/lists", endpoint::v1::lists::request);
+    router.any_async("/api/v1/blocks", endpoint::v1::blocks::request);
     router.any_async(
         "/api/v1/follow_requests",
         endpoint::v1::follow_requests::request,
     );
+    router.any_async(
+        "/api/v1/follow_requests/:id/authorize",
+        endpoint::v1::follow_requests::authorize::request,
+    );
+    router.any_async(
+        "/api/v1/follow_requests/:id/reject",
+        endpoint::v1::follow_requests::reject::request,
+    );
     router.any_async(
         "/api/v1/instance/peer",
         endpoint::v1::instance::peer::request,
@@ -106,6 +115,14 @@ async fn handle_api(req: Request) -> Result<impl IntoResponse> {
         "/api/v1/accounts/relationships",
         endpoint::v1::accounts::relationships::request,
     );
+    router.any_async(
+        "/api/v1/accounts/aliases",
+        endpoint::v1::accounts::aliases::request,
+    );
+    router.any_async(
+        "/api/v1/accounts/migration",
+        endpoint::v1::accounts::migration::request,
+    );
 
     // router.any_async("/api/v1/timelines/public", timelines::public::request);
 
@@ -114,6 +131,47 @@ async fn handle_api(req: Request) -> Result<impl IntoResponse> {
     // router.any_async("/api/v1/streaming/health", streaming::health::request);
     //
 
+    // Status API requests
+    router.any_async("/api/v1/statuses", endpoint::v1::statuses::request);
+    router.any_async("/api/v1/statuses/:id", endpoint::v1::statuses::request);
+    router.any_async(
+        "/api/v1/statuses/:id/history",
+        endpoint::v1::statuses::history::request,
+    );
+    router.any_async(
+        "/api/v1/statuses/:id/source",
+        endpoint::v1::statuses::source::request,
+    );
+    router.any_async(
+        "/api/v1/statuses/:id/pin",
+        endpoint::v1::statuses::pin::request,
+    );
+    router.any_async(
+        "/api/v1/statuses/:id/unpin",
+        endpoint::v1::statuses::unpin::request,
+    );
+
+    // Poll API requests
+    router.any_async("/api/v1/polls/:id", endpoint::v1::polls::request);
+    router.any_async(
+        "/api/v1/polls/:id/votes",
+        endpoint::v1::polls::votes::request,
+    );
+
+    // Admin API requests
+    router.any_async(
+        "/api/v1/admin/reports",
+        endpoint::v1::admin::reports::request,
+    );
+    router.any_async(
+        "/api/v1/admin/reports/:id",
+        endpoint::v1::admin::reports::request,
+    );
+    router.any_async(
+        "/api/v1/admin/reports/:id/resolve",
+        endpoint::v1::admin::reports::resolve::request,
+    );
+
     // Account API requests
     router.any_async("/api/v1/accounts/:id", endpoint::v1::accounts::request);
     router.any_async(
@@ -124,6 +182,14 @@ async fn handle_api(req: Request) -> Result<impl IntoResponse> {
         "/api/v1/accounts/:id/unfollow",
         endpoint::v1::accounts::unfollow::request,
     );
+    router.any_async(
+        "/api/v1/accounts/:id/block",
+        endpoint::v1::accounts::block::request,
+    );
+    router.any_async(
+        "/api/v1/accounts/:id/unblock",
+        endpoint::v1::accounts::unblock::request,
+    );
     router.any_async(
         "/api/v1/accounts/:id/following",
         endpoint::v1::accounts::following::request,
@@ -137,7 +203,6 @@ async fn handle_api(req: Request) -> Result<impl IntoResponse> {
     //     accounts::relationships::request,
     // );
 
-    // router.any_async("/api/v1/statuses", statuses::request);
     // router.any_async("/api/v1/favourites", favourites::request);
     // router.any_async("/api/v1/bookmarks", bookmarks::request);
     //
@@ -13,18 +13,15 @@ use uuid::Uuid;
 
 //use sparrow::activitypub::action::follow::Follow as FollowAction;
 use crate::http_response::HttpResponse;
-use sparrow::activitystream::activity::accept::Accept as AcceptActivity;
-use sparrow::activitystream::activity::create::Create as CreateActivity;
-use sparrow::activitystream::activity::delete::Delete as DeleteActivity;
-use sparrow::activitystream::activity::follow::Follow as FollowActivity;
-use sparrow::activitystream::activity::undo::Undo as UndoActivity;
-use sparrow::activitystream::activity::Activity;
 use sparrow::activitystream::activity::ActivityType;
 use sparrow::activitystream::object::note::Note as NoteObject;
-use sparrow::activitystream::object::ObjectType;
 use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::inbox_activity::InboxActivity;
+use sparrow::mastodon::inbox_error::InboxError;
+use sparrow::mastodon::job::Job;
+use sparrow::mastodon::recipient::Recipient;
+use sparrow::mastodon::unavailable_domain::UnavailableDomain;
 use sparrow::mastodon::ValidationResult;
-use sparrow::mastodon::ACTOR_ACCOUNT;
 use sparrow::mastodon::ME_ACCOUNT;
 use sparrow::REQUEST_UID;
 
@@ -83,16 +80,19 @@ pub async fn post(req: Request) -> Result<Response> {
     tracing::trace!("POST to INBOX");
 
     let (body, activity_type, object_type) =
-        inspect(String::from_utf8_lossy(req.body()).to_string());
+        match inspect(String::from_utf8_lossy(req.body()).to_string()) {
+            Ok(x) => x,
+            Err(e) => return Ok(e.response()),
+        };
 
     tracing::trace!(
         "{:?} {:?} by {:?}",
         activity_type,
         object_type,
-        body.get("actor").unwrap()
+        body.get("actor")
     );
 
-    let (me, _) = MAccount::default().await.unwrap();
+    let (me, _) = MAccount::default().await?;
 
     match ME_ACCOUNT.set(me.to_owned()) {
         Ok(_) => {
@@ -103,126 +103,130 @@ pub async fn post(req: Request) -> Result<Response> {
         }
     };
 
-    let validation =
-        sparrow::mastodon::validate_signature(&req, me.to_owned()).await?;
+    let validation = match sparrow::mastodon::validate_signature(
+        &req,
+        me.to_owned(),
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => match e.downcast_ref::<InboxError>() {
+            Some(inbox_error) => return Ok(inbox_error.response()),
+            None => return Err(e),
+        },
+    };
 
     let actor_account = match validation {
         ValidationResult::Valid(acct) => acct,
         ValidationResult::Invalid => {
             tracing::trace!("NOT VALID SIGNATURE");
             tracing::trace!("{:?}", String::from_utf8(req.body().to_vec()));
-            return HttpResponse::invalid_request();
+            let e = InboxError::SignatureFailure(
+                "signature does not match".to_string(),
+            );
+            return Ok(e.response());
         }
         ValidationResult::DeleteSelf => {
-            let activity =
-                serde_json::from_value::<Activity<DeleteActivity>>(body)
-                    .unwrap();
-            return match activity.execute(me, None).await {
-                Ok(_) => HttpResponse::accepted(),
-                Err(e) => {
-                    tracing::error!(
-                        "Error from Inbox's Delete request -> {e:?}",
-                    );
-                    HttpResponse::not_acceptable()
-                }
-            };
+            let actor = body
+                .get("actor")
+                .and_then(|x| x.as_str())
+                .unwrap_or_default();
+            // Nothing to delete for actors we never knew.
+            if MAccount::is_actor_exist(actor.to_string()).await?.is_none() {
+                return HttpResponse::accepted();
+            }
+            Job::enqueue_inbox(body, None).await?;
+            return HttpResponse::accepted();
         }
     };
 
     tracing::trace!("VALID SIGNATURE");
 
+    // Sender's server is alive. Deliver to it again.
+    if let Some(domain) = actor_account.account_uri.domain.as_ref() {
+        UnavailableDomain::available(domain.as_str()).await?;
+    }
+
     // Now I have two MAccount, me and actor_account.
 
-    match activity_type {
-        ActivityType::Accept => {
-            let activity =
-                serde_json::from_value::<Activity<AcceptActivity>>(body)
-                    .unwrap();
-            match activity.execute(me, Some(actor_account)).await {
-                Ok(_) => HttpResponse::accepted(),
-                Err(e) => {
-                    tracing::error!(
-                        "Error from Inbox's Accpet request -> {e:?}",
-                    );
-                    HttpResponse::not_acceptable()
-                }
-            }
-        }
-        ActivityType::Create => {
-            let activity =
-                serde_json::from_value::<Activity<CreateActivity>>(body)
-                    .unwrap();
-            let _ot = ObjectType::from_str(object_type.unwrap().as_str());
-
-            match activity.execute(me, Some(actor_account)).await {
-                Ok(_) => HttpResponse::accepted(),
-                Err(e) => {
-                    tracing::error!(
-                        "Error from Inbox's Create request -> {e:?}",
-                    );
-                    HttpResponse::not_acceptable()
-                }
-            }
-        }
-        ActivityType::Delete => {
-            let activity =
-                serde_json::from_value::<Activity<DeleteActivity>>(body)
-                    .unwrap();
-            match activity.execute(me, Some(actor_account)).await {
-                Ok(_) => HttpResponse::accepted(),
-                Err(e) => {
-                    tracing::error!(
-                        "Error from Inbox's Delete request -> {e:?}",
-                    );
-                    HttpResponse::not_acceptable()
-                }
-            }
-        }
-        ActivityType::Follow => {
-            let activity =
-                serde_json::from_value::<Activity<FollowActivity>>(body)
-                    .unwrap();
-            match activity.execute(me, Some(actor_account)).await {
-                Ok(_) => HttpResponse::accepted(),
-                Err(e) => {
-                    tracing::error!(
-                        "Error from Inbox's Follow request -> {e:?}",
-                    );
-                    HttpResponse::not_acceptable()
-                }
-            }
-        }
-        ActivityType::Undo => {
-            let activity =
-                serde_json::from_value::<Activity<UndoActivity>>(body)
-                    .unwrap();
-            match activity.execute(me, Some(actor_account)).await {
-                Ok(_) => HttpResponse::accepted(),
-                Err(e) => {
-                    tracing::error!(
-                        "Error from Inbox's Follow request -> {e:?}",
-                    );
-                    HttpResponse::not_acceptable()
-                }
-            }
-        }
-        action => {
-            tracing::warn!("action '{:?}' is UNKNOWN", action);
-            HttpResponse::not_acceptable()
-        }
+    let activity_id = body
+        .get("id")
+        .and_then(|x| x.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    // Shared inbox gets the activity once for all local accounts it addresses.
+    // Worker resolves them again and acts for each of them.
+    if matches!(activity_type, ActivityType::Create | ActivityType::Announce)
+        && Recipient::resolve(&body, &actor_account).await?.is_empty()
+    {
+        tracing::debug!("No local recipient of {}", activity_id);
+        return HttpResponse::accepted();
+    }
+
+    // Activity is claimed with its id. The id must be on the actor's host.
+    let actor = body.get("actor").and_then(|x| x.as_str());
+    if !activity_id.is_empty()
+        && !InboxActivity::same_origin(
+            activity_id.as_str(),
+            actor.unwrap_or_default(),
+        )
+    {
+        let e = InboxError::BadJson(format!(
+            "id {} is not on the actor's host",
+            activity_id
+        ));
+        return Ok(e.response());
     }
+
+    // Retried or duplicated deliveries are acknowledged without side effects.
+    if !activity_id.is_empty()
+        && !InboxActivity::claim(activity_id.as_str(), actor).await?
+    {
+        tracing::debug!("Activity {} is already processed", activity_id);
+        return HttpResponse::accepted();
+    }
+
+    // Worker executes the activity. Sender doesn't wait for remote calls.
+    let job_uid = match Job::enqueue_inbox(body, Some(&actor_account)).await {
+        Ok(uid) => uid,
+        Err(e) => {
+            // Sender retries it.
+            InboxActivity::release(activity_id.as_str()).await?;
+            return Err(e);
+        }
+    };
+    tracing::debug!("Activity {} is queued as job {}", activity_id, job_uid);
+
+    HttpResponse::accepted()
 }
 
-pub fn inspect(body: String) -> (Value, ActivityType, Option<String>) {
-    let v = serde_json::from_str::<Value>(body.as_str()).unwrap();
-    let v_type = v.get("type").unwrap().as_str().unwrap();
-    let activity_type = ActivityType::from_str(v_type).unwrap();
+/// Activity json, its type and its object's type (or id).
+pub fn inspect(
+    body: String,
+) -> Result<(Value, ActivityType, Option<String>), InboxError> {
+    let v = serde_json::from_str::<Value>(body.as_str())
+        .map_err(|e| InboxError::BadJson(e.to_string()))?;
+    if !v.is_object() {
+        return Err(InboxError::BadJson("not an object".to_string()));
+    }
 
-    let a = v.get("object").unwrap();
-    let object_type = match a.is_object() {
-        true => a.get("type").unwrap().as_str().map(String::from),
-        _ => Some(a.as_str().unwrap().to_string()),
+    let v_type = match v.get("type").and_then(|x| x.as_str()) {
+        Some(t) => t,
+        None => return Err(InboxError::BadJson("type is missing".to_string())),
+    };
+    let activity_type = ActivityType::from_str(v_type)
+        .ok()
+        .filter(Job::accepts)
+        .ok_or(InboxError::UnknownType(v_type.to_string()))?;
+
+    let object_type = match v.get("object") {
+        Some(Value::String(s)) => Some(s.to_owned()),
+        Some(o @ Value::Object(_)) => {
+            o.get("type").and_then(|x| x.as_str()).map(String::from)
+        }
+        _ => None,
     };
 
-    (v, activity_type, object_type)
+    Ok((v, activity_type, object_type))
 }
@@ -41,55 +41,20 @@ async fn handle_outbox(req: Request) -> anyhow::Result<impl IntoResponse> {
 }
 
 pub async fn get(_req: Request) -> Result<Response> {
-    let body = r#"
-    {
-      "id": "https://mastodon.jgarr.net/featured",
-      "type": "OrderedCollection",
-      "totalItems": 1,
-      "orderedItems": [
-        {
-          "id": "https://mastodon.jgarr.net/1",
-          "type": "Note",
-          "summary": null,
-          "inReplyTo": null,
-          "published": "2022-04-26T00:11:17Z",
-          "url": "https://mastodon.jgarr.net/1",
-          "attributedTo": "https://mastodon.jgarr.net/justin",
-          "to": [
-            "https://www.w3.org/ns/activitystreams#Public"
-          ],
-          "cc": [
-            "https://mastodon.jgarr.net/followers"
-          ],
-          "sensitive": false,
-          "atomUri": "https://mastodon.jgarr.net/1",
-          "inReplyToAtomUri": null,
-          "conversation.rs": "tag:mastodon.jgarr.net,2022-04-26:objectId=288755344:objectType=Conversation",
-          "content": "<p>THIS IS PINNED</p>",
-          "contentMap": {
-            "en": "<p>THIS IS PINNED</p>"
-          },
-          "attachment": [],
-          "tag": [],
-          "replies": {
-            "id": "https://mastodon.jgarr.net/1_replies",
-            "type": "Collection",
-            "first": {
-              "type": "CollectionPage",
-              "next": "https://mastodon.jgarr.net/1_replies_more",
-              "partOf": "https://mastodon.jgarr.net/1_replies",
-              "items": []
-            }
-          }
-        }
-      ]
-    }"#;
-
-    //let b: Value = serde_json::from_str(body).unwrap();
+    // Todo: Process default account only for now.
+    let (default_account, _) = MAccount::default().await?;
+
+    let outbox = OrderedCollection::new(
+        default_account.outbox_url.unwrap_or_default(),
+        default_account.statuses_count as i64,
+    );
+
+    let json_string = serde_json::to_string(&outbox).unwrap();
+
     Ok(Response::builder()
         .status(200)
         .header("Content-Type", "application/activity+json")
-        .body(body.to_string())
+        .body(json_string)
         .build())
 }
 
@@ -302,9 +302,152 @@ CREATE TABLE follow (
     show_reblogs BOOLEAN DEFAULT true NOT NULL,
     uri TEXT,
     notify BOOLEAN DEFAULT false NOT NULL,
-    languages TEXT,
+    languages TEXT, pending BOOLEAN DEFAULT false NOT NULL,
     UNIQUE(account_uid,target_account_uid)
 );
+CREATE TABLE favourite (
+    uid TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    account_uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    uri TEXT,
+    UNIQUE(account_uid,status_uid)
+);
+CREATE TABLE status_edit (
+    uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    account_uid TEXT,
+    text TEXT DEFAULT '' NOT NULL,
+    spoiler_text TEXT DEFAULT '' NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    ordered_media_attachment_ids TEXT,
+    sensitive BOOLEAN
+);
+CREATE TABLE block (
+    uid TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    account_uid TEXT NOT NULL,
+    target_account_uid TEXT NOT NULL,
+    uri TEXT,
+    UNIQUE(account_uid,target_account_uid)
+);
+CREATE TABLE report (
+    uid TEXT NOT NULL UNIQUE,
+    status_uids TEXT DEFAULT '[]' NOT NULL,
+    comment TEXT DEFAULT '' NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    account_uid TEXT NOT NULL,
+    action_taken_by_account_uid TEXT,
+    target_account_uid TEXT NOT NULL,
+    assigned_account_uid TEXT,
+    uri TEXT,
+    forwarded BOOLEAN,
+    category TEXT DEFAULT 'other' NOT NULL,
+    action_taken_at TIMESTAMP,
+    rule_ids TEXT
+);
+CREATE TABLE status_pin (
+    uid TEXT NOT NULL,
+    account_uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    UNIQUE(account_uid,status_uid)
+);
+CREATE TABLE poll (
+    uid TEXT NOT NULL UNIQUE,
+    account_uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    expires_at TIMESTAMP,
+    options TEXT DEFAULT '[]' NOT NULL,
+    cached_tallies TEXT DEFAULT '[]' NOT NULL,
+    multiple BOOLEAN DEFAULT FALSE NOT NULL,
+    hide_totals BOOLEAN DEFAULT FALSE NOT NULL,
+    votes_count INTEGER DEFAULT 0 NOT NULL,
+    last_fetched_at TIMESTAMP,
+    closed_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    voters_count INTEGER
+);
+CREATE TABLE poll_vote (
+    uid TEXT NOT NULL UNIQUE,
+    account_uid TEXT NOT NULL,
+    poll_uid TEXT NOT NULL,
+    choice INTEGER DEFAULT 0 NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    uri TEXT,
+    UNIQUE(account_uid,poll_uid,choice)
+);
+CREATE TABLE inbox_activity (
+    uri TEXT NOT NULL UNIQUE,
+    actor TEXT,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+CREATE UNIQUE INDEX index_domain_block_on_domain ON domain_block (domain);
+CREATE TABLE job (
+    uid TEXT NOT NULL UNIQUE,
+    queue TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER DEFAULT 0 NOT NULL,
+    max_attempts INTEGER DEFAULT 8 NOT NULL,
+    run_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    locked_at TIMESTAMP,
+    last_error TEXT,
+    finished_at TIMESTAMP,
+    failed_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+CREATE TABLE delivery (
+    uid TEXT NOT NULL UNIQUE,
+    inbox_url TEXT NOT NULL,
+    account_uid TEXT NOT NULL,
+    activity_id TEXT NOT NULL,
+    body TEXT NOT NULL,
+    attempts INTEGER DEFAULT 0 NOT NULL,
+    run_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    locked_at TIMESTAMP,
+    last_status TEXT,
+    delivered_at TIMESTAMP,
+    failed_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    UNIQUE (inbox_url, activity_id)
+);
+CREATE TABLE unavailable_domain (
+    uid TEXT NOT NULL UNIQUE,
+    domain TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+CREATE TABLE delivery_failure (
+    domain TEXT NOT NULL UNIQUE,
+    failures INTEGER DEFAULT 0 NOT NULL,
+    first_failed_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    last_failed_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+CREATE TABLE signature_scheme (
+    domain TEXT NOT NULL UNIQUE,
+    scheme TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+CREATE TABLE account_key (
+    uid TEXT NOT NULL UNIQUE,
+    key_id TEXT NOT NULL UNIQUE,
+    controller TEXT NOT NULL,
+    key_type TEXT DEFAULT "Multikey" NOT NULL,
+    public_key TEXT NOT NULL,
+    private_key TEXT,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
 CREATE VIEW instance AS
  WITH domain_counts(domain, account_count) AS (
          SELECT account.domain,
@@ -0,0 +1,19 @@
+-- Add migration script here
+
+--     CREATE TABLE public.favourites (
+--     id bigint NOT NULL,
+--     created_at timestamp without time zone NOT NULL,
+--     updated_at timestamp without time zone NOT NULL,
+--     account_id bigint NOT NULL,
+--     status_id bigint NOT NULL
+-- );
+
+CREATE TABLE favourite (
+    uid TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    account_uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    uri TEXT,
+    UNIQUE(account_uid,status_uid)
+);
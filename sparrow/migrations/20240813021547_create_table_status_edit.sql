@@ -0,0 +1,27 @@
+-- Add migration script here
+
+--     CREATE TABLE public.status_edits (
+--     id bigint NOT NULL,
+--     status_id bigint NOT NULL,
+--     account_id bigint,
+--     text text DEFAULT ''::text NOT NULL,
+--     spoiler_text text DEFAULT ''::text NOT NULL,
+--     created_at timestamp(6) without time zone NOT NULL,
+--     updated_at timestamp(6) without time zone NOT NULL,
+--     ordered_media_attachment_ids bigint[],
+--     media_descriptions text[],
+--     poll_options character varying[],
+--     sensitive boolean
+-- );
+
+CREATE TABLE status_edit (
+    uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    account_uid TEXT,
+    text TEXT DEFAULT '' NOT NULL,
+    spoiler_text TEXT DEFAULT '' NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    ordered_media_attachment_ids TEXT,
+    sensitive BOOLEAN
+);
@@ -0,0 +1,4 @@
+-- Add migration script here
+
+-- Follow request which is not accepted yet.
+ALTER TABLE follow ADD COLUMN pending BOOLEAN DEFAULT false NOT NULL;
@@ -0,0 +1,20 @@
+-- Add migration script here
+
+--     CREATE TABLE public.blocks (
+--     id bigint NOT NULL,
+--     created_at timestamp without time zone NOT NULL,
+--     updated_at timestamp without time zone NOT NULL,
+--     account_id bigint NOT NULL,
+--     target_account_id bigint NOT NULL,
+--     uri character varying
+-- );
+
+CREATE TABLE block (
+    uid TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    account_uid TEXT NOT NULL,
+    target_account_uid TEXT NOT NULL,
+    uri TEXT,
+    UNIQUE(account_uid,target_account_uid)
+);
@@ -0,0 +1,35 @@
+-- Add migration script here
+
+-- CREATE TABLE public.reports (
+--     id bigint NOT NULL,
+--     status_ids bigint[] DEFAULT '{}'::bigint[] NOT NULL,
+--     comment text DEFAULT ''::text NOT NULL,
+--     created_at timestamp without time zone NOT NULL,
+--     updated_at timestamp without time zone NOT NULL,
+--     account_id bigint NOT NULL,
+--     action_taken_by_account_id bigint,
+--     target_account_id bigint NOT NULL,
+--     assigned_account_id bigint,
+--     uri character varying,
+--     forwarded boolean,
+--     category integer DEFAULT 0 NOT NULL,
+--     action_taken_at timestamp without time zone,
+--     rule_ids bigint[]
+-- );
+
+CREATE TABLE report (
+    uid TEXT NOT NULL UNIQUE,
+    status_uids TEXT DEFAULT '[]' NOT NULL,
+    comment TEXT DEFAULT '' NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP,
+    account_uid TEXT NOT NULL,
+    action_taken_by_account_uid TEXT,
+    target_account_uid TEXT NOT NULL,
+    assigned_account_uid TEXT,
+    uri TEXT,
+    forwarded BOOLEAN,
+    category TEXT DEFAULT 'other' NOT NULL,
+    action_taken_at TIMESTAMP,
+    rule_ids TEXT
+);
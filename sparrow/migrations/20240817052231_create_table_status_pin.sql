@@ -0,0 +1,18 @@
+-- Add migration script here
+
+-- CREATE TABLE public.status_pins (
+--     id bigint NOT NULL,
+--     account_id bigint NOT NULL,
+--     status_id bigint NOT NULL,
+--     created_at timestamp without time zone DEFAULT now() NOT NULL,
+--     updated_at timestamp without time zone DEFAULT now() NOT NULL
+-- );
+
+CREATE TABLE status_pin (
+    uid TEXT NOT NULL,
+    account_uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    UNIQUE(account_uid,status_uid)
+);
@@ -0,0 +1,56 @@
+-- Add migration script here
+
+-- CREATE TABLE public.polls (
+--     id bigint NOT NULL,
+--     account_id bigint,
+--     status_id bigint,
+--     expires_at timestamp without time zone,
+--     options character varying[] DEFAULT '{}'::character varying[] NOT NULL,
+--     cached_tallies bigint[] DEFAULT '{}'::bigint[] NOT NULL,
+--     multiple boolean DEFAULT false NOT NULL,
+--     hide_totals boolean DEFAULT false NOT NULL,
+--     votes_count bigint DEFAULT 0 NOT NULL,
+--     last_fetched_at timestamp without time zone,
+--     created_at timestamp without time zone NOT NULL,
+--     updated_at timestamp without time zone NOT NULL,
+--     lock_version integer DEFAULT 0 NOT NULL,
+--     voters_count bigint
+-- );
+
+-- CREATE TABLE public.poll_votes (
+--     id bigint NOT NULL,
+--     account_id bigint,
+--     poll_id bigint,
+--     choice integer DEFAULT 0 NOT NULL,
+--     created_at timestamp without time zone NOT NULL,
+--     updated_at timestamp without time zone NOT NULL,
+--     uri character varying
+-- );
+
+CREATE TABLE poll (
+    uid TEXT NOT NULL UNIQUE,
+    account_uid TEXT NOT NULL,
+    status_uid TEXT NOT NULL,
+    expires_at TIMESTAMP,
+    options TEXT DEFAULT '[]' NOT NULL,
+    cached_tallies TEXT DEFAULT '[]' NOT NULL,
+    multiple BOOLEAN DEFAULT FALSE NOT NULL,
+    hide_totals BOOLEAN DEFAULT FALSE NOT NULL,
+    votes_count INTEGER DEFAULT 0 NOT NULL,
+    last_fetched_at TIMESTAMP,
+    closed_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    voters_count INTEGER
+);
+
+CREATE TABLE poll_vote (
+    uid TEXT NOT NULL UNIQUE,
+    account_uid TEXT NOT NULL,
+    poll_uid TEXT NOT NULL,
+    choice INTEGER DEFAULT 0 NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    uri TEXT,
+    UNIQUE(account_uid,poll_uid,choice)
+);
@@ -0,0 +1,16 @@
+-- Add migration script here
+
+-- Ids of inbound activities already processed.
+-- Retried deliveries and shared inbox duplicates are acknowledged without side effects.
+
+CREATE TABLE inbox_activity (
+    uri TEXT NOT NULL UNIQUE,
+    actor TEXT,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+
+INSERT INTO setting (
+    var,
+    value
+) VALUES
+('inbox_dedupe_window', '604800');
@@ -0,0 +1,5 @@
+-- Add migration script here
+
+-- domain_block is created by 20240508073529_create_table_domain_block.sql.
+-- Blocks are looked up by domain, one record per domain.
+CREATE UNIQUE INDEX IF NOT EXISTS index_domain_block_on_domain ON domain_block (domain);
@@ -0,0 +1,19 @@
+-- Add migration script here
+
+-- Background jobs. Inbound activities are queued here by the inbox and
+-- processed by the worker, so slow remote servers don't hold the request.
+
+CREATE TABLE job (
+    uid TEXT NOT NULL UNIQUE,
+    queue TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER DEFAULT 0 NOT NULL,
+    max_attempts INTEGER DEFAULT 8 NOT NULL,
+    run_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    locked_at TIMESTAMP,
+    last_error TEXT,
+    finished_at TIMESTAMP,
+    failed_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
@@ -0,0 +1,28 @@
+-- Add migration script here
+
+-- Outbound activities waiting for delivery, keyed by recipient's inbox url.
+-- Failed deliveries are retried with exponential backoff until
+-- `delivery_retry_horizon` seconds passed since they were queued.
+
+CREATE TABLE delivery (
+    uid TEXT NOT NULL UNIQUE,
+    inbox_url TEXT NOT NULL,
+    account_uid TEXT NOT NULL,
+    activity_id TEXT NOT NULL,
+    body TEXT NOT NULL,
+    attempts INTEGER DEFAULT 0 NOT NULL,
+    run_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    locked_at TIMESTAMP,
+    last_status TEXT,
+    delivered_at TIMESTAMP,
+    failed_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    UNIQUE (inbox_url, activity_id)
+);
+
+INSERT INTO setting (
+    var,
+    value
+) VALUES
+('delivery_retry_horizon', '172800');
@@ -0,0 +1,32 @@
+-- Add migration script here
+
+-- CREATE TABLE public.unavailable_domains (
+--     id bigint NOT NULL,
+--     domain character varying DEFAULT ''::character varying NOT NULL,
+--     created_at timestamp without time zone NOT NULL,
+--     updated_at timestamp without time zone NOT NULL
+-- );
+
+CREATE TABLE unavailable_domain (
+    uid TEXT NOT NULL UNIQUE,
+    domain TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+
+-- Consecutive delivery failures per domain. Mastodon keeps them in redis.
+-- Cleared on successful delivery or inbound request from the domain.
+
+CREATE TABLE delivery_failure (
+    domain TEXT NOT NULL UNIQUE,
+    failures INTEGER DEFAULT 0 NOT NULL,
+    first_failed_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    last_failed_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+
+INSERT INTO setting (
+    var,
+    value
+) VALUES
+('unavailable_domain_threshold', '10'),
+('unavailable_domain_window', '86400');
@@ -0,0 +1,17 @@
+-- Add migration script here
+
+-- HTTP signature scheme each domain accepts. Learnt by double-knocking,
+-- RFC 9421 first then draft-cavage.
+
+CREATE TABLE signature_scheme (
+    domain TEXT NOT NULL UNIQUE,
+    scheme TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
+
+INSERT INTO setting (
+    var,
+    value
+) VALUES
+('signature_scheme_recheck', '2592000');
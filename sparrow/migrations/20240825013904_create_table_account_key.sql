@@ -0,0 +1,16 @@
+-- Add migration script here
+
+-- Ed25519 keys of actors, published as Multikey in assertionMethod.
+-- Local accounts have the private key. Remote ones only the public key.
+-- key_id is the verificationMethod, e.g. https://example.com/users/alice#ed25519-key
+
+CREATE TABLE account_key (
+    uid TEXT NOT NULL UNIQUE,
+    key_id TEXT NOT NULL UNIQUE,
+    controller TEXT NOT NULL,
+    key_type TEXT DEFAULT "Multikey" NOT NULL,
+    public_key TEXT NOT NULL,
+    private_key TEXT,
+    created_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL,
+    updated_at TIMESTAMP DEFAULT (unixepoch()) NOT NULL
+);
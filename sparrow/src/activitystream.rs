@@ -16,6 +16,9 @@ pub mod collection;
 pub mod object;
 pub mod ordered_collection;
 
+/// Special collection addressing everyone.
+pub const PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
 pub trait Execute {
     /// Execute given activity.  
     /// Check actor. If actor is self, publish(send)
@@ -25,9 +28,10 @@ pub trait Execute {
 
 /// Helper function that remove @context key from serde_json::Value object.
 pub fn remove_context(mut v: Value) -> Value {
-    let a = v.as_object_mut().unwrap();
-    a.remove_entry("@context");
-    serde_json::to_value(a).unwrap()
+    if let Some(a) = v.as_object_mut() {
+        a.remove_entry("@context");
+    }
+    v
 }
 
 pub(self) fn default_context() -> Value {
@@ -24,19 +24,36 @@ use crate::mastodon::account::Account as MAccount;
 use crate::mastodon::account::Get as _;
 
 pub mod accept;
+pub mod add;
+pub mod announce;
+pub mod block;
 pub mod create;
 pub mod delete;
+pub mod flag;
 pub mod follow;
+pub mod like;
+pub mod r#move;
+pub mod reject;
+pub mod remove;
 pub mod undo;
+pub mod update;
 
 /// ActivityPub Object Types
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
 pub enum ActivityType {
     Follow,
     Accept,
+    Add,
+    Announce,
     Delete,
     Undo,
+    Update,
+    Block,
+    Flag,
+    Like,
+    Move,
     Reject,
+    Remove,
     Note,
     Create,
     Replies,
@@ -53,9 +70,17 @@ impl FromStr for ActivityType {
         match input {
             "Follow" => Ok(ActivityType::Follow),
             "Accept" => Ok(ActivityType::Accept),
+            "Add" => Ok(ActivityType::Add),
+            "Announce" => Ok(ActivityType::Announce),
             "Delete" => Ok(ActivityType::Delete),
             "Undo" => Ok(ActivityType::Undo),
+            "Update" => Ok(ActivityType::Update),
+            "Block" => Ok(ActivityType::Block),
+            "Flag" => Ok(ActivityType::Flag),
+            "Like" => Ok(ActivityType::Like),
+            "Move" => Ok(ActivityType::Move),
             "Reject" => Ok(ActivityType::Reject),
+            "Remove" => Ok(ActivityType::Remove),
             "Note" => Ok(ActivityType::Note),
             "Create" => Ok(ActivityType::Create),
             "Replies" => Ok(ActivityType::Replies),
@@ -94,6 +119,8 @@ where
     pub cc: Option<Vec<String>>,
     #[serde(rename = "object")]
     pub activity_object: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
 }
 
 impl<T> Activity<T>
@@ -131,6 +158,7 @@ where
             to,
             cc,
             activity_object: activity,
+            target: None,
         }
     }
 
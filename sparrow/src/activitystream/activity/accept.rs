@@ -22,9 +22,10 @@ use crate::mastodon::account::Account as MAccount;
 use crate::mastodon::account::Get as _;
 use crate::mastodon::activity_log::ActivityLog;
 use crate::mastodon::follow::Follow as MFollow;
+use crate::mastodon::inbox_error::InboxError;
 use crate::mastodon::post_activity;
 use crate::mastodon::setting::Setting;
-use crate::mastodon::{ACTOR_ACCOUNT, ME_ACCOUNT};
+use crate::mastodon::{actor_account, recipient_with_url};
 
 /*
 {
@@ -89,47 +90,96 @@ impl Execute for Accept {
         // Check activiy.object is what I really sent.
         // https://dev.prefer.social/0190fcb0-5272-77c3-acb1-3e9be71ff930
         // SELECT * FROM activity_log WHERE JSON_EXTRACT(body, '$.id') = ?
-        match ActivityLog::get_with_id(
-            self.0.get("id").unwrap().as_str().unwrap(),
-        )
-        .await
-        .unwrap()
-        {
+        // Object can be the Follow or only its id.
+        let follow_id = match &self.0 {
+            Value::String(id) => id.to_owned(),
+            o => o
+                .get("id")
+                .and_then(|x| x.as_str())
+                .map(|x| x.to_string())
+                .ok_or(InboxError::BadJson(
+                    "Accept object has no id".into(),
+                ))?,
+        };
+        let follow = match ActivityLog::get_with_id(follow_id.as_str()).await? {
             None => {
-                tracing::error!(
-                    "Havn't published this acticity {}",
-                    self.0.get("id").unwrap().to_string()
-                )
+                tracing::error!("Havn't published this acticity {}", follow_id);
+                return Err(anyhow::Error::msg(
+                    "Accepted Follow is not published by SELF!",
+                ));
             }
             Some(x) => {
-                let log_obj = activitystream::remove_context(x);
+                let log_obj = activitystream::remove_context(x.to_owned());
                 let given_obj =
                     activitystream::remove_context(self.0.to_owned());
-                if given_obj != log_obj {
+                if self.0.is_object() && given_obj != log_obj {
                     tracing::error!(
                         "Integration error! No matching follow was published! {}",
-                        self.0.get("id").unwrap().to_string()
+                        follow_id
                     );
                     return Err(anyhow::Error::msg(
                         "Given activity is not published by SELF!",
                     ));
                 }
+                x
             }
         };
 
-        let subj_account = ME_ACCOUNT.get().unwrap().to_owned();
+        // Only the followed actor accepts the Follow.
+        let actor_account = actor_account()?;
+        let (follower, followee) =
+            accepted_follow(&follow, &actor_account.actor_url.to_string())
+                .ok_or(InboxError::BadJson(format!(
+                    "Follow {} is not sent to {}",
+                    follow_id, actor_account.actor_url
+                )))?;
+        let subj_account = recipient_with_url(follower)?;
         let subj_account_id = subj_account.uid;
 
-        let obj = ActorUrl::new(
-            self.0.get("object").unwrap().as_str().unwrap().to_string(),
-        )
-        .unwrap();
-        let obj_id = self.0.get("id").unwrap().as_str().unwrap().to_string();
+        let obj = ActorUrl::new(followee.to_string())?;
         let obj_account = MAccount::get(obj).await?;
         let obj_account_id = obj_account.uid;
 
-        MFollow::new(obj_id, subj_account_id, obj_account_id).await?;
+        MFollow::new(follow_id, subj_account_id, obj_account_id).await?;
 
         Ok(())
     }
 }
+
+/// Follower and followee of the Follow when it is sent to the accepting
+/// actor.
+fn accepted_follow<'a>(
+    follow: &'a Value,
+    accepting_actor: &str,
+) -> Option<(&'a str, &'a str)> {
+    let follower = follow.get("actor").and_then(|x| x.as_str())?;
+    let followee = follow.get("object").and_then(|x| x.as_str())?;
+    (followee == accepting_actor).then_some((follower, followee))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn accepted_by_third_party() {
+        let follow = json!({
+            "id": "https://local.example/0190f4bf-aad1-7290-ac1f-86333df63b82",
+            "type": "Follow",
+            "actor": "https://local.example/self",
+            "object": "https://mas.to/users/seungjin",
+        });
+        assert_eq!(
+            accepted_follow(&follow, "https://mas.to/users/seungjin"),
+            Some((
+                "https://local.example/self",
+                "https://mas.to/users/seungjin"
+            ))
+        );
+        assert_eq!(
+            accepted_follow(&follow, "https://evil.example/users/mallory"),
+            None
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Add activity. Pinning status to featured collection.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-add>
+//! Mastodon doc: <https://docs.joinmastodon.org/spec/activitypub/#featured>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::{Error, Result};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::activitystream::activity::announce::get_or_fetch_status;
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::activity::Execute;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::status_pin::StatusPin as MStatusPin;
+use crate::mastodon::actor_account;
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/users/seungjin#adds/112941245370331112",
+  "type":"Add",
+  "actor":"https://mas.to/users/seungjin",
+  "object":"https://mas.to/users/seungjin/statuses/112941245370331112",
+  "target":"https://mas.to/users/seungjin/collections/featured"
+}
+*/
+
+/// Add activity struct. Object is a status url or Note object.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Add(pub Value);
+
+impl Add {
+    /// resturn Add object. Object is added to target collection.
+    pub async fn new(
+        actor: String,
+        object: Value,
+        target: String,
+    ) -> Activity<Add> {
+        let uuid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uuid);
+
+        let mut add_object = Activity::new(
+            true,
+            id,
+            ActivityType::Add,
+            actor,
+            None,
+            None,
+            None,
+            Add(object),
+        );
+        add_object.target = Some(target);
+
+        add_object
+    }
+}
+
+impl fmt::Display for Add {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Add {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl Execute for Add {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Add execute <---------------");
+
+        let actor_account = actor_account()?;
+        let object_id =
+            featured_object_id(&self.0, &activity_val, &actor_account)?;
+
+        let tstatus = match get_or_fetch_status(object_id.to_owned()).await? {
+            Some(s) => s,
+            None => {
+                return Err(Error::msg(format!(
+                    "Pinned status {} can not be stored",
+                    object_id
+                )))
+            }
+        };
+
+        // Only the author can pin the status.
+        if tstatus.account_id != actor_account.uid.to_string() {
+            tracing::error!(
+                "{} is not the author of {}",
+                actor_account.actor_url,
+                object_id
+            );
+            return Err(Error::msg("Only the author can pin the status"));
+        }
+
+        MStatusPin::new(&actor_account, &tstatus).await
+    }
+}
+
+/// Object id of Add/Remove targeting actor's featured collection.
+pub(crate) fn featured_object_id(
+    object: &Value,
+    activity_val: &Value,
+    actor_account: &MAccount,
+) -> Result<String> {
+    let target = activity_val.get("target").and_then(|x| match x {
+        Value::String(s) => Some(s.to_owned()),
+        x => x.get("id").and_then(|y| y.as_str()).map(String::from),
+    });
+
+    if target.is_none() || target != actor_account.featured_collection_url {
+        tracing::error!(
+            "Target {:?} is not featured collection of {}",
+            target,
+            actor_account.actor_url
+        );
+        return Err(Error::msg(format!(
+            "Target {:?} is not implemented!",
+            target
+        )));
+    }
+
+    let object_id = match object {
+        Value::String(s) => Some(s.to_owned()),
+        x => x.get("id").and_then(|y| y.as_str()).map(String::from),
+    };
+    object_id.ok_or(Error::msg("Object has no id"))
+}
@@ -0,0 +1,241 @@
+//! Announce activity. (aka. boost/reblog)
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-announce>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::{Error, Result};
+use chrono::Utc;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::activity::Execute;
+use crate::activitystream::object;
+use crate::activitystream::object::article::Article as ArticleObject;
+use crate::activitystream::object::note::Note as NoteObject;
+use crate::activitystream::object::ObjectType;
+use crate::activitystream::PUBLIC;
+use crate::mastodon::account::actor_url::ActorUrl;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::notification::Notification as MNotification;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::status::Status as MStatus;
+use crate::mastodon::{actor_account, recipient_with_uid};
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+use crate::table::New as _;
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/users/seungjin/statuses/112941245370331112/activity",
+  "type":"Announce",
+  "actor":"https://mas.to/users/seungjin",
+  "published":"2024-08-12T15:05:43Z",
+  "to":["https://www.w3.org/ns/activitystreams#Public"],
+  "cc":[
+    "https://dev.prefer.social/self",
+    "https://mas.to/users/seungjin/followers"
+  ],
+  "object":"https://dev.prefer.social/0191475c-3a68-7c51-9f35-4c1f5c7a59b2"
+}
+*/
+
+/// Announce activity struct.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Announce(pub Value);
+
+impl Announce {
+    /// resturn Announce object.
+    pub async fn new(actor: String, object: Value) -> Activity<Announce> {
+        let uuid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uuid);
+        let published = Utc::now();
+
+        let announce_object = Activity::new(
+            true,
+            id,
+            ActivityType::Announce,
+            actor.clone(),
+            Some(published),
+            Some(vec![PUBLIC.to_string()]),
+            Some(vec![format!("{}/followers", actor)]),
+            Announce(object),
+        );
+
+        announce_object
+    }
+
+    /// id of the announced object. Object can be given as url or embedded.
+    pub fn object_id(&self) -> Option<String> {
+        match self.0.as_str() {
+            Some(id) => Some(id.to_string()),
+            None => self.0.get("id")?.as_str().map(String::from),
+        }
+    }
+}
+
+impl fmt::Display for Announce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Announce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl Execute for Announce {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Announce execute <---------------");
+
+        let activity =
+            serde_json::from_value::<Activity<Announce>>(activity_val)?;
+        let actor_account = actor_account()?;
+
+        // Same Announce can be delivered more than once.
+        if TStatus::get(("uri".to_string(), activity.id.to_owned()))
+            .await?
+            .last()
+            .is_some()
+        {
+            tracing::debug!("Announce {} is already stored", activity.id);
+            return Ok(());
+        }
+
+        let object_id = match self.object_id() {
+            Some(id) => id,
+            None => {
+                return Err(Error::msg(format!(
+                    "Announce {} has no object id",
+                    activity.id
+                )))
+            }
+        };
+
+        let original = match get_or_fetch_status(object_id).await? {
+            Some(s) => s,
+            None => {
+                return Err(Error::msg(format!(
+                    "Announced object of {} can not be stored",
+                    activity.id
+                )))
+            }
+        };
+
+        let to = activity.to.unwrap_or_default();
+        let cc = activity.cc.unwrap_or_default();
+        let visibility = if to.contains(&PUBLIC.to_string()) {
+            0
+        } else if cc.contains(&PUBLIC.to_string()) {
+            1
+        } else {
+            2
+        };
+
+        let created_at = activity.published.unwrap_or(Utc::now()).timestamp();
+
+        let reblog = TStatus {
+            uid: Uuid::now_v7().to_string(),
+            uri: Some(activity.id.to_owned()),
+            created_at,
+            updated_at: Utc::now().timestamp(),
+            reblog_of_id: Some(original.uid.to_owned()),
+            visibility,
+            local: Some(false),
+            account_id: actor_account.uid.to_string(),
+            ..Default::default()
+        };
+        reblog.new().await?;
+
+        // Someone boosted a status of the addressed local account.
+        if let Some(author) = recipient_with_uid(&original.account_id) {
+            MNotification::new(
+                "reblog",
+                &author,
+                &actor_account,
+                reblog.uid,
+                "Status",
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Return status row with uri. Fetch and store it first if it is unknown.
+pub(crate) async fn get_or_fetch_status(
+    uri: String,
+) -> Result<Option<TStatus>> {
+    if let Some(s) = TStatus::get(("uri".to_string(), uri.to_owned()))
+        .await?
+        .last()
+    {
+        return Ok(Some(s.to_owned()));
+    }
+
+    let obj = object::fetch(uri.as_str()).await?;
+    let obj_type = obj
+        .get("type")
+        .and_then(|t| t.as_str())
+        .and_then(|t| ObjectType::from_str(t).ok())
+        .unwrap_or_default();
+
+    match obj_type {
+        ObjectType::Note => {
+            let note = serde_json::from_value::<NoteObject>(obj)?;
+            let attributed_to = match note.attributed_to.to_owned() {
+                Some(a) => a,
+                None => {
+                    return Err(Error::msg(format!(
+                        "Note {} has no attributedTo",
+                        uri
+                    )))
+                }
+            };
+            let author = MAccount::fetch(ActorUrl::new(attributed_to)?).await?;
+            MStatus::new(note, author).await?;
+        }
+        ObjectType::Article
+        | ObjectType::Page
+        | ObjectType::Event
+        | ObjectType::Video
+        | ObjectType::Audio
+        | ObjectType::Image => {
+            let note = serde_json::from_value::<ArticleObject>(obj)?.to_note();
+            let attributed_to = match note.attributed_to.to_owned() {
+                Some(a) => a,
+                None => {
+                    return Err(Error::msg(format!(
+                        "{} has no attributedTo",
+                        uri
+                    )))
+                }
+            };
+            let author = MAccount::fetch(ActorUrl::new(attributed_to)?).await?;
+            MStatus::new(note, author).await?;
+        }
+        unknown_type => {
+            tracing::error!(
+                "Announce '{:?}' is not implemented!",
+                unknown_type
+            );
+            return Ok(None);
+        }
+    }
+
+    Ok(TStatus::get(("uri".to_string(), uri))
+        .await?
+        .last()
+        .cloned())
+}
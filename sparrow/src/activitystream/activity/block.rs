@@ -0,0 +1,95 @@
+//! Block activity.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-block>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::Execute;
+use crate::mastodon::block::Block as MBlock;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::{actor_account, recipient_with_url};
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/a2ae3ad0-a2cb-4aa6-9fa2-a3aeb1b6c0c5",
+  "type":"Block",
+  "actor":"https://mas.to/users/seungjin",
+  "object":"https://dev.prefer.social/self"
+}
+*/
+
+/// Block activity object. Blocked actor's url.
+#[derive(Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Block(pub String);
+
+impl Serialize for Block {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(self.0.as_str())
+    }
+}
+
+impl Block {
+    /// resturn Block object.
+    pub async fn new(actor: String, object: String) -> Activity<Block> {
+        let uid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uid);
+
+        let block_object = Activity::new(
+            true,
+            id,
+            ActivityType::Block,
+            actor,
+            None,
+            None,
+            None,
+            Block(object),
+        );
+
+        block_object
+    }
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.as_str())
+    }
+}
+
+impl fmt::Debug for Block {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Execute for Block {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Block execute <---------------");
+
+        let activity_id = activity_val
+            .get("id")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+
+        let blocked_account = recipient_with_url(&self.0)?;
+        let blocker_account = actor_account()?;
+
+        MBlock::new(
+            activity_id.to_string(),
+            &blocker_account,
+            &blocked_account,
+        )
+        .await
+    }
+}
@@ -14,15 +14,25 @@ use crate::activitystream::activity::Activity;
 use crate::activitystream::activity::ActivityType;
 use crate::activitystream::activity::Execute;
 use crate::activitystream::actor::person::Person as PersonActor;
+use crate::activitystream::object::article::Article as ArticleObject;
+use crate::activitystream::object::document::Document;
 use crate::activitystream::object::note::Note as NoteObject;
+use crate::activitystream::object::question::Question as QuestionObject;
 use crate::activitystream::object::ObjectType;
 use crate::mastodon::account::actor_url::ActorUrl;
 use crate::mastodon::account::Account as MAccount;
 use crate::mastodon::account::Get as _;
 use crate::mastodon::activity_log::ActivityLog;
+use crate::mastodon::inbox_error::InboxError;
+use crate::mastodon::media_attachment::MediaAttachment as MMediaAttachment;
+use crate::mastodon::poll::Poll as MPoll;
 use crate::mastodon::setting::Setting;
 use crate::mastodon::status::Status as MStatus;
-use crate::mastodon::ACTOR_ACCOUNT;
+use crate::mastodon::{actor_account, recipient_with_uid};
+use crate::table::poll::Get as _;
+use crate::table::poll::Poll as TPoll;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
 use chrono::Utc;
 
 /// Accept activity struct.  
@@ -69,14 +79,37 @@ impl fmt::Debug for Create {
 
 impl Execute for Create {
     async fn execute(&self, activity_val: Value) -> Result<()> {
-        let a: &str = self.0.get("type").unwrap().as_str().unwrap();
-        let object_type = ObjectType::from_str(a).unwrap();
+        let a = self
+            .0
+            .get("type")
+            .and_then(|x| x.as_str())
+            .ok_or(InboxError::BadJson("Object has no type".into()))?;
+        let object_type = ObjectType::from_str(a)
+            .map_err(|_| InboxError::UnknownType(a.to_string()))?;
 
-        let actor_account = ACTOR_ACCOUNT.get().unwrap().to_owned();
+        let actor_account = actor_account()?;
         match object_type {
+            ObjectType::Note
+                if self.0.get("name").is_some_and(|x| x.is_string()) =>
+            {
+                create_vote(self.to_owned(), activity_val, actor_account).await
+            }
             ObjectType::Note => {
                 create_note(self.to_owned(), activity_val, actor_account).await
             }
+            ObjectType::Question => {
+                create_question(self.to_owned(), activity_val, actor_account)
+                    .await
+            }
+            ObjectType::Article
+            | ObjectType::Page
+            | ObjectType::Event
+            | ObjectType::Video
+            | ObjectType::Audio
+            | ObjectType::Image => {
+                create_article(self.to_owned(), activity_val, actor_account)
+                    .await
+            }
             unkown_type => unkown(unkown_type).await,
         }
     }
@@ -88,7 +121,10 @@ async fn create_note(
     actor_account: MAccount,
 ) -> Result<()> {
     match serde_json::from_value::<NoteObject>(s.0.to_owned()) {
-        Ok(note) => MStatus::new(note, actor_account).await,
+        Ok(note) => {
+            MStatus::new(note.to_owned(), actor_account.to_owned()).await?;
+            save_attachments(&note, &actor_account).await
+        }
         Err(e) => {
             tracing::error!("Error from Parsing NoteObject: {e:?}");
             tracing::error!("{:?}", s.0);
@@ -98,6 +134,155 @@ async fn create_note(
     }
 }
 
+async fn create_question(
+    s: Create,
+    activity: Value,
+    actor_account: MAccount,
+) -> Result<()> {
+    let question =
+        match serde_json::from_value::<QuestionObject>(s.0.to_owned()) {
+            Ok(question) => question,
+            Err(e) => {
+                tracing::error!("Error from Parsing QuestionObject: {e:?}");
+                tracing::error!("{activity:?}");
+                return Err(Error::msg(format!(
+                    "Error from Parsing QuestionObject: {e:?}"
+                )));
+            }
+        };
+
+    let uri = question.note.id.to_owned();
+    MStatus::new(question.note.to_owned(), actor_account.to_owned()).await?;
+
+    let tstatus = match TStatus::get(("uri".to_string(), uri.to_owned()))
+        .await?
+        .last()
+    {
+        Some(t) => t.to_owned(),
+        None => {
+            return Err(Error::msg(format!("Question {} is not stored", uri)))
+        }
+    };
+
+    MPoll::save_question(&question, &actor_account, tstatus.uid).await?;
+    save_attachments(&question.note, &actor_account).await
+}
+
+/// Article, Page, Event, Video, Audio and Image are stored as a status.
+async fn create_article(
+    s: Create,
+    activity: Value,
+    actor_account: MAccount,
+) -> Result<()> {
+    let article = match serde_json::from_value::<ArticleObject>(s.0.to_owned())
+    {
+        Ok(article) => article,
+        Err(e) => {
+            tracing::error!("Error from Parsing ArticleObject: {e:?}");
+            tracing::error!("{activity:?}");
+            return Err(Error::msg(format!(
+                "Error from Parsing ArticleObject: {e:?}"
+            )));
+        }
+    };
+
+    let note = article.to_note();
+    MStatus::new(note.to_owned(), actor_account.to_owned()).await?;
+    save_attachments(&note, &actor_account).await
+}
+
+/// Keep media attached to the stored status.
+async fn save_attachments(
+    note: &NoteObject,
+    actor_account: &MAccount,
+) -> Result<()> {
+    let tstatus = match TStatus::get(("uri".to_string(), note.id.to_owned()))
+        .await?
+        .last()
+    {
+        Some(t) => t.to_owned(),
+        None => return Ok(()),
+    };
+
+    let attachment = note.attachment.to_owned().map(Value::Array);
+    for document in Document::list(attachment.as_ref())
+        .iter()
+        .filter(|x| !x.is_link())
+    {
+        MMediaAttachment::save_remote(
+            actor_account,
+            tstatus.uid.to_owned(),
+            document,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Vote on our poll. Note with `name` replying to the Question.
+async fn create_vote(
+    s: Create,
+    activity: Value,
+    actor_account: MAccount,
+) -> Result<()> {
+    let note = match serde_json::from_value::<NoteObject>(s.0.to_owned()) {
+        Ok(note) => note,
+        Err(e) => {
+            tracing::error!("Error from Parsing NoteObject: {e:?}");
+            tracing::error!("{activity:?}");
+            return Err(Error::msg(format!(
+                "Error from Parsing NoteObject: {e:?}"
+            )));
+        }
+    };
+
+    let in_reply_to = note.in_reply_to.to_owned().unwrap_or_default();
+    let tstatus = TStatus::get(("uri".to_string(), in_reply_to.to_owned()))
+        .await?
+        .last()
+        .cloned()
+        .filter(|x| recipient_with_uid(&x.account_id).is_some());
+
+    let tpoll = match tstatus.and_then(|x| x.poll_id) {
+        Some(poll_id) => TPoll::get(("uid".to_string(), poll_id))
+            .await?
+            .last()
+            .cloned(),
+        None => None,
+    };
+    let tpoll = match tpoll {
+        Some(p) => p,
+        None => {
+            tracing::error!("{} is not our poll", in_reply_to);
+            return Err(Error::msg("Vote is not on our poll"));
+        }
+    };
+
+    if MPoll::is_expired(&tpoll) {
+        tracing::error!("Poll {} is expired", tpoll.uid);
+        return Err(Error::msg("Poll is expired"));
+    }
+
+    let titles: Vec<String> = serde_json::from_str(&tpoll.options)?;
+    let choice = match titles.iter().position(|x| Some(x) == note.name.as_ref())
+    {
+        Some(c) => c as i64,
+        None => {
+            tracing::error!("{:?} is not an option of the poll", note.name);
+            return Err(Error::msg("Vote is not an option of the poll"));
+        }
+    };
+
+    // Single choice poll counts first vote of the account only.
+    let choices = MPoll::choices(&actor_account, &tpoll).await?;
+    if choices.contains(&choice) || (!tpoll.multiple && !choices.is_empty()) {
+        tracing::debug!("{} voted already", actor_account.actor_url);
+        return Ok(());
+    }
+
+    MPoll::vote(&actor_account, &tpoll, vec![choice], Some(note.id)).await
+}
+
 async fn unkown(unknown_type: ObjectType) -> Result<()> {
     tracing::error!("Create '{:?}' is not implemented!", unknown_type);
     Err(Error::msg(format!(
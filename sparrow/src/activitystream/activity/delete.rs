@@ -1,8 +1,8 @@
 //! Delete activity.  
 //! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-delete>
 
-use anyhow::Result;
-use serde::{Deserialize, Serialize, Serializer};
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use spin_sdk::http::{Method, RequestBuilder, Response};
 use std::fmt;
@@ -20,22 +20,31 @@ use crate::mastodon::account::Account as MAccount;
 use crate::mastodon::account::Get as _;
 use crate::mastodon::account::Remove as _;
 use crate::mastodon::follow::Follow as MFollow;
+use crate::mastodon::inbox_error::InboxError;
 use crate::mastodon::setting::Setting;
 use crate::mastodon::ACTOR_ACCOUNT;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
 
 const MAX_RETRY: usize = 8;
 
-#[derive(Deserialize, Default, PartialEq, Eq, Clone)]
-pub struct Delete(Value);
-
-impl Serialize for Delete {
-    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        s.serialize_str(serde_json::to_string(&self.0).unwrap().as_str())
-    }
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mstd.seungjin.net/users/wsj/statuses/112818277812710620#delete",
+  "type":"Delete",
+  "actor":"https://mstd.seungjin.net/users/wsj",
+  "to":["https://www.w3.org/ns/activitystreams#Public"],
+  "object":{
+    "id":"https://mstd.seungjin.net/users/wsj/statuses/112818277812710620",
+    "type":"Tombstone",
+    "atomUri":"https://mstd.seungjin.net/users/wsj/statuses/112818277812710620"
+  }
 }
+*/
+
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Delete(pub Value);
 
 impl Delete {
     /// resturn Follow object.  
@@ -70,8 +79,8 @@ impl Delete {
     pub async fn parse<T>(
         activity: Activity<Delete>,
     ) -> Result<Activity<Self>> {
-        let subj = ActorUrl::new(activity.actor.clone()).unwrap();
-        let obj = ActorUrl::new(activity.activity_object.to_string()).unwrap();
+        let subj = ActorUrl::new(activity.actor.clone())?;
+        let obj = ActorUrl::new(activity.activity_object.to_string())?;
         let obj_id = obj.to_string();
 
         let subj_account = MAccount::get(subj).await?;
@@ -91,6 +100,7 @@ impl Delete {
             to: None,
             cc: None,
             activity_object: activity.activity_object,
+            target: None,
         };
 
         Ok(follow_activity)
@@ -112,25 +122,47 @@ impl fmt::Debug for Delete {
 impl Execute for Delete {
     async fn execute(&self, activity_val: Value) -> Result<()> {
         // Check object
-        let object_type_str = match self.0.is_object() {
-            true => self.0.get("type").unwrap().as_str().unwrap(),
-            _ => self.0.as_str().unwrap(),
+        let object_type_str = match &self.0 {
+            Value::String(s) => s.as_str(),
+            o => o.get("type").and_then(|x| x.as_str()).ok_or(
+                InboxError::BadJson("Deleted object has no type".into()),
+            )?,
         };
 
-        let object_type = ObjectType::from_str(object_type_str).unwrap();
+        let object_type = ObjectType::from_str(object_type_str)
+            .map_err(|_| InboxError::UnknownType(object_type_str.into()))?;
 
         match object_type {
-            ObjectType::Tombstone => {
-                // Get id and delete it from database' status table.
-                tracing::warn!("Delete from statuse/tombstone process not yet implemented");
-                return Ok(());
+            ObjectType::Tombstone
+            | ObjectType::Note
+            | ObjectType::Question
+            | ObjectType::Article
+            | ObjectType::Page
+            | ObjectType::Event
+            | ObjectType::Video
+            | ObjectType::Audio
+            | ObjectType::Image => {
+                let id = self.0.get("id").and_then(|x| x.as_str());
+                return match id {
+                    Some(id) => delete_status(id, activity_val).await,
+                    None => Err(Error::msg("Deleted object has no id")),
+                };
+            }
+            ObjectType::Url(url)
+                if activity_val.get("actor").and_then(|x| x.as_str())
+                    != Some(url.as_str()) =>
+            {
+                // Object is referred by its id.
+                return delete_status(url.as_str(), activity_val).await;
             }
             ObjectType::Url(url) => {
                 // Todo: Validate URL
                 // Delete this url from account.
-                let actor =
-                    activity_val.get("actor").unwrap().as_str().unwrap();
-                let actor_url = ActorUrl::new(actor.to_owned()).unwrap();
+                let actor = activity_val
+                    .get("actor")
+                    .and_then(|x| x.as_str())
+                    .ok_or(InboxError::BadJson("actor is missing".into()))?;
+                let actor_url = ActorUrl::new(actor.to_owned())?;
 
                 let response =
                     redirect_http_request(url.as_str(), MAX_RETRY).await?;
@@ -167,6 +199,44 @@ impl Execute for Delete {
     }
 }
 
+/// Soft delete the status. Only the author can delete it.
+async fn delete_status(uri: &str, activity_val: Value) -> Result<()> {
+    let tstatus = match TStatus::get(("uri".to_string(), uri.to_string()))
+        .await?
+        .last()
+    {
+        Some(t) => t.to_owned(),
+        None => {
+            tracing::debug!("Deleted status is unknown: {}", uri);
+            return Ok(());
+        }
+    };
+
+    let actor_account = match ACTOR_ACCOUNT.get() {
+        Some(a) => a.to_owned(),
+        None => {
+            let actor = activity_val
+                .get("actor")
+                .and_then(|x| x.as_str())
+                .unwrap_or_default();
+            MAccount::get(ActorUrl::new(actor.to_owned())?).await?
+        }
+    };
+
+    if tstatus.account_id != actor_account.uid.to_string() {
+        tracing::error!(
+            "{} is not the author of {}",
+            actor_account.actor_url.to_string(),
+            uri
+        );
+        return Err(Error::msg("Only the author can delete the status"));
+    }
+
+    TStatus::delete(tstatus.uid).await?;
+    tracing::debug!("Status {} is deleted", uri);
+    Ok(())
+}
+
 async fn redirect_http_request(
     url: &str,
     max_retry: usize,
@@ -176,23 +246,20 @@ async fn redirect_http_request(
         .header("Content-Type", "application/activity+json")
         .build();
 
-    let response: Response = spin_sdk::http::send(request).await.unwrap();
+    let response: Response = spin_sdk::http::send(request).await?;
     if max_retry <= 1 {
         tracing::warn!("Number of max retries attemption reached!");
         return Ok(response);
     }
 
-    if vec![301u16, 307u16, 308u16].contains(response.status()) {
-        tracing::debug!(
-            "Requesting redirected location: {}",
-            response.header("Location").unwrap().as_str().unwrap()
-        );
-        Box::pin(redirect_http_request(
-            response.header("Location").unwrap().as_str().unwrap(),
-            max_retry - 1,
-        ))
-        .await?;
-    };
-
-    Ok(response)
+    let location = response.header("Location").and_then(|x| x.as_str());
+    match location {
+        Some(location)
+            if [301u16, 307u16, 308u16].contains(response.status()) =>
+        {
+            tracing::debug!("Requesting redirected location: {}", location);
+            Box::pin(redirect_http_request(location, max_retry - 1)).await
+        }
+        _ => Ok(response),
+    }
 }
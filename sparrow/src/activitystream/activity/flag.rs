@@ -0,0 +1,154 @@
+//! Flag activity. Report forwarded from remote server.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-flag>
+//! Mastodon doc: <https://docs.joinmastodon.org/spec/activitypub/#Flag>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::{Error, Result};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::activitystream::activity::Execute;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::notification::Notification as MNotification;
+use crate::mastodon::report::Report as MReport;
+use crate::mastodon::user_role::UserRole;
+use crate::mastodon::{actor_account, recipients};
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/0191585e-8f0e-7a36-a0a1-0bd0e2a7f4d4",
+  "type":"Flag",
+  "actor":"https://mas.to/actor",
+  "content":"Please take a look at this user and their posts",
+  "object":[
+    "https://dev.prefer.social/self",
+    "https://dev.prefer.social/statuses/0191585e-3a1c-7a9b-8d8e-6e1f0c3f2a11"
+  ]
+}
+*/
+
+/// Flag activity struct. Object is an actor url or array of actor and status urls.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Flag(pub Value);
+
+impl Flag {
+    /// Urls in the object.
+    pub fn object_ids(&self) -> Vec<String> {
+        match &self.0 {
+            Value::String(s) => vec![s.to_owned()],
+            Value::Array(a) => a
+                .iter()
+                .filter_map(|x| match x {
+                    Value::String(s) => Some(s.to_owned()),
+                    x => x.get("id").and_then(|y| y.as_str()).map(String::from),
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl Execute for Flag {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Flag execute <---------------");
+
+        let activity_id = activity_val
+            .get("id")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let comment = activity_val
+            .get("content")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let reporter_account = actor_account()?;
+
+        let object_ids = self.object_ids();
+
+        // Only reports about our accounts are accepted.
+        let targets: Vec<MAccount> = recipients()
+            .into_iter()
+            .filter(|x| object_ids.contains(&x.actor_url.to_string()))
+            .collect();
+        if targets.is_empty() {
+            tracing::error!("Flag does not target us: {:?}", object_ids);
+            return Err(Error::msg("Flag does not target us"));
+        }
+
+        let mut statuses: Vec<TStatus> = Vec::new();
+        for id in object_ids.iter() {
+            if let Some(s) = TStatus::get(("uri".to_string(), id.to_owned()))
+                .await?
+                .last()
+            {
+                statuses.push(s.to_owned());
+            }
+        }
+
+        // Moderators are notified of the reports.
+        let moderators =
+            UserRole::accounts_with(UserRole::MANAGE_REPORTS).await?;
+
+        // One report for each reported account with its statuses.
+        for target in targets.iter() {
+            let status_uids: Vec<String> = statuses
+                .iter()
+                .filter(|x| x.account_id == target.uid.to_string())
+                .map(|x| x.uid.to_owned())
+                .collect();
+
+            let report_uid = MReport::new(
+                activity_id.to_owned(),
+                &reporter_account,
+                target,
+                status_uids,
+                comment.to_owned(),
+            )
+            .await?;
+
+            for moderator in moderators.iter() {
+                notify(moderator, &reporter_account, report_uid.to_owned())
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn notify(
+    moderator_account: &MAccount,
+    reporter_account: &MAccount,
+    report_uid: String,
+) -> Result<()> {
+    MNotification::new(
+        "admin.report",
+        moderator_account,
+        reporter_account,
+        report_uid,
+        "Report",
+    )
+    .await
+}
@@ -4,12 +4,12 @@ use std::fmt;
 use std::fmt::Debug;
 
 use anyhow::{Error, Result};
-use chrono::Utc;
 use serde::{Deserialize, Serialize, Serializer};
-use serde_json::Value;
+use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::activitystream::activity::accept::Accept;
+use crate::activitystream::activity::reject::Reject;
 use crate::activitystream::activity::Activity;
 use crate::activitystream::activity::ActivityType;
 use crate::activitystream::default_context;
@@ -19,8 +19,10 @@ use crate::mastodon::account::actor_url::ActorUrl;
 use crate::mastodon::account::Account as MAccount;
 use crate::mastodon::account::Get as _;
 use crate::mastodon::follow::Follow as MFollow;
+use crate::mastodon::inbox_error::InboxError;
+use crate::mastodon::notification::Notification as MNotification;
 use crate::mastodon::setting::Setting;
-use crate::mastodon::ME_ACCOUNT;
+use crate::mastodon::{actor_account, recipient_with_url};
 
 /// Follow actor object.  
 #[derive(Deserialize, Default, PartialEq, Eq, Clone)]
@@ -63,8 +65,8 @@ impl Follow {
     pub async fn parse<T>(
         activity: Activity<Follow>,
     ) -> Result<Activity<Self>> {
-        let subj = ActorUrl::new(activity.actor.clone()).unwrap();
-        let obj = ActorUrl::new(activity.activity_object.to_string()).unwrap();
+        let subj = ActorUrl::new(activity.actor.clone())?;
+        let obj = ActorUrl::new(activity.activity_object.to_string())?;
         let obj_id = obj.to_string();
 
         let subj_account = MAccount::get(subj).await?;
@@ -84,10 +86,39 @@ impl Follow {
             to: None,
             cc: None,
             activity_object: Follow(activity.activity_object.to_string()),
+            target: None,
         };
 
         Ok(follow_activity)
     }
+
+    /// Send Accept of the follow to the follower.
+    pub async fn accept(me_account: MAccount, follow: Value) -> Result<()> {
+        let accept_activity =
+            Accept::new(me_account.actor_url.to_string(), follow).await;
+        let res =
+            crate::mastodon::post_activity(me_account, accept_activity).await?;
+        published(res)
+    }
+
+    /// Send Reject of the follow to the follower.
+    pub async fn reject(me_account: MAccount, follow: Value) -> Result<()> {
+        let reject_activity =
+            Reject::new(me_account.actor_url.to_string(), follow).await;
+        let res =
+            crate::mastodon::post_activity(me_account, reject_activity).await?;
+        published(res)
+    }
+
+    /// Follow activity which was received from follower.
+    pub fn received(uri: String, follower: &MAccount, me: &MAccount) -> Value {
+        json!({
+            "id": uri,
+            "type": "Follow",
+            "actor": follower.actor_url.to_string(),
+            "object": me.actor_url.to_string(),
+        })
+    }
 }
 
 impl fmt::Display for Follow {
@@ -104,70 +135,63 @@ impl fmt::Debug for Follow {
 
 impl Execute for Follow {
     async fn execute(&self, activity_val: Value) -> Result<()> {
-        let v = serde_json::to_value(self).unwrap();
-        let actor = activity_val.get("actor").unwrap().as_str().unwrap();
-
-        let activity_id = activity_val.get("id").unwrap().to_string();
-
-        let subj_account = ME_ACCOUNT.get().unwrap().to_owned(); // Todo: Error checking
-        let subj_account_id = subj_account.uid.to_owned();
-
-        let obj = ActorUrl::new(self.0.to_string()).unwrap();
-        let obj_id = obj.to_string();
-        let obj_account = MAccount::get(obj).await?;
-        let obj_account_id = obj_account.uid;
+        let activity_id = activity_val
+            .get("id")
+            .and_then(|x| x.as_str())
+            .ok_or(InboxError::BadJson("Follow has no id".into()))?
+            .to_string();
+
+        let followee_account = recipient_with_url(&self.0)?;
+        let follower_account = actor_account()?;
+
+        // Locked account approves followers manually.
+        if followee_account.locked {
+            MFollow::request(
+                activity_id.to_owned(),
+                follower_account.uid.to_owned(),
+                followee_account.uid.to_owned(),
+            )
+            .await?;
+            return MNotification::new(
+                "follow_request",
+                &followee_account,
+                &follower_account,
+                activity_id,
+                "Follow",
+            )
+            .await;
+        }
 
         MFollow::new(
-            //obj_id.clone(),
+            activity_id.to_owned(),
+            follower_account.uid.to_owned(),
+            followee_account.uid.to_owned(),
+        )
+        .await?;
+        MNotification::new(
+            "follow",
+            &followee_account,
+            &follower_account,
             activity_id,
-            subj_account_id.to_owned(),
-            obj_account_id.to_owned(),
+            "Follow",
         )
         .await?;
 
         // Now send back "accept" signal to follower.
+        Self::accept(followee_account, remove_context(activity_val)).await
+    }
+}
 
-        let published = Some(Utc::now());
-        let my_actor_url = obj_id;
-        let accept = Accept(remove_context(activity_val.to_owned()));
-        let to = None;
-        let cc = None;
-        let accept_activity = Activity::new(
-            true,
-            format!(
-                "https://{}#accepts/follows/{}",
-                my_actor_url, subj_account_id
-            ),
-            ActivityType::Accept,
-            my_actor_url,
-            published,
-            to,
-            cc,
-            accept,
-        );
-
-        let res =
-            crate::mastodon::post_activity(subj_account, accept_activity)
-                .await?;
-        match res {
-            202u16 => {
-                tracing::debug!("Activity published({})", res);
-                Ok(())
-            }
-            _ => {
-                tracing::error!("Activity published({})", res);
-                Err(Error::msg(format!("Published Activity received {}", res)))
-            }
+/// Check response code of published activity.
+fn published(res: u16) -> Result<()> {
+    match res {
+        200u16 | 202u16 => {
+            tracing::debug!("Activity published({})", res);
+            Ok(())
+        }
+        _ => {
+            tracing::error!("Activity published({})", res);
+            Err(Error::msg(format!("Published Activity received {}", res)))
         }
-
-        // tracing::debug!("{:?}", accept_activity);
-        // match accept_activity.activity_object.execute(activity_val).await {
-        //     Ok(_) => return Ok(()),
-        //     Err(e) => {
-        //         tracing::error!("Resending Accept signal error: {:?}", e);
-        //         tracing::error!("{:?}", accept_activity);
-        //         return Err(Error::msg("{e:?}"));
-        //     }
-        // }
     }
 }
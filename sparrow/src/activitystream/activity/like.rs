@@ -0,0 +1,174 @@
+//! Like activity. (aka. favourite)
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-like>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::{Error, Result};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::activity::Execute;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::notification::Notification as MNotification;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::{actor_account, recipient_with_uid};
+use crate::table::favourite::Favourite as TFavourite;
+use crate::table::favourite::Get as _;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/users/seungjin#likes/229105813",
+  "type":"Like",
+  "actor":"https://mas.to/users/seungjin",
+  "object":"https://dev.prefer.social/0191475c-3a68-7c51-9f35-4c1f5c7a59b2"
+}
+*/
+
+/// Like activity struct.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Like(pub Value);
+
+impl Like {
+    /// resturn Like object.
+    pub async fn new(actor: String, object: String) -> Activity<Like> {
+        let uuid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uuid);
+
+        let like_object = Activity::new(
+            true,
+            id,
+            ActivityType::Like,
+            actor,
+            None,
+            None,
+            None,
+            Like(Value::String(object)),
+        );
+
+        like_object
+    }
+
+    /// id of the liked object. Object can be given as url or embedded.
+    pub fn object_id(&self) -> Option<String> {
+        match self.0.as_str() {
+            Some(id) => Some(id.to_string()),
+            None => self.0.get("id")?.as_str().map(String::from),
+        }
+    }
+
+    /// Reverse favourite recorded by Like activity with given id.
+    pub async fn undo(like_id: String, actor_account: &MAccount) -> Result<()> {
+        let favourite =
+            match TFavourite::get(("uri".to_string(), like_id)).await?.last() {
+                Some(f) => f.to_owned(),
+                None => {
+                    tracing::debug!("No favourite to undo");
+                    return Ok(());
+                }
+            };
+
+        if favourite.account_uid != actor_account.uid.to_string() {
+            return Err(Error::msg(
+                "Only the actor who liked can undo the Like",
+            ));
+        }
+
+        TFavourite::remove(favourite.uri.unwrap_or_default()).await?;
+        MNotification::remove(favourite.uid, "Favourite").await?;
+
+        Ok(())
+    }
+
+    /// Is the Like stored as a favourite?
+    pub async fn is_stored(like_id: &str) -> Result<bool> {
+        let favourites =
+            TFavourite::get(("uri".to_string(), like_id.to_string())).await?;
+        Ok(!favourites.is_empty())
+    }
+}
+
+impl fmt::Display for Like {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Like {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl Execute for Like {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Like execute <---------------");
+
+        let activity_id = match activity_val.get("id").and_then(|v| v.as_str())
+        {
+            Some(id) => id.to_string(),
+            None => return Err(Error::msg("Like has no id")),
+        };
+        let actor_account = actor_account()?;
+
+        let object_id = match self.object_id() {
+            Some(id) => id,
+            None => {
+                return Err(Error::msg(format!(
+                    "Like {} has no object id",
+                    activity_id
+                )))
+            }
+        };
+
+        // Nobody can see like of unknown status here.
+        let status =
+            match TStatus::get(("uri".to_string(), object_id)).await?.last() {
+                Some(s) => s.to_owned(),
+                None => {
+                    tracing::debug!("Liked status is unknown: {}", activity_id);
+                    return Ok(());
+                }
+            };
+
+        if TFavourite::is_exist(
+            actor_account.uid.to_string(),
+            status.uid.to_owned(),
+        )
+        .await?
+        {
+            tracing::debug!("Like {} is already stored", activity_id);
+            return Ok(());
+        }
+
+        let favourite_uid = TFavourite::new(
+            activity_id,
+            actor_account.uid.to_string(),
+            status.uid.to_owned(),
+        )
+        .await?;
+
+        // Someone favourited a status of the addressed local account.
+        if let Some(author) = recipient_with_uid(&status.account_id) {
+            MNotification::new(
+                "favourite",
+                &author,
+                &actor_account,
+                favourite_uid,
+                "Favourite",
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+//! Move activity. Account migration.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-move>
+//! Mastodon doc: <https://docs.joinmastodon.org/spec/activitypub/#Move>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::activitystream::activity::follow::Follow;
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::Execute;
+use crate::mastodon::account::actor_url::ActorUrl;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::follow::Follow as MFollow;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::{actor_account, recipient_with_url, recipients};
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/users/seungjin#moves/1",
+  "type":"Move",
+  "actor":"https://mas.to/users/seungjin",
+  "object":"https://mas.to/users/seungjin",
+  "target":"https://dev.prefer.social/self"
+}
+*/
+
+/// Move activity object. Moving actor's url.
+#[derive(Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Move(pub String);
+
+impl Serialize for Move {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(self.0.as_str())
+    }
+}
+
+impl Move {
+    /// resturn Move object. Actor moves itself to target.
+    pub async fn new(actor: String, target: String) -> Activity<Move> {
+        let uid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uid);
+
+        let mut move_object = Activity::new(
+            true,
+            id,
+            ActivityType::Move,
+            actor.to_owned(),
+            None,
+            None,
+            None,
+            Move(actor),
+        );
+        move_object.target = Some(target);
+
+        move_object
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.as_str())
+    }
+}
+
+impl fmt::Debug for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Execute for Move {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Move execute <---------------");
+
+        let old_account = actor_account()?;
+
+        // Only the actor itself can move its account.
+        if self.0 != old_account.actor_url.to_string() {
+            tracing::error!(
+                "Move object {} is not the signing actor {}",
+                self.0,
+                old_account.actor_url
+            );
+            return Err(Error::msg("Move object is not the signing actor"));
+        }
+
+        let target = match activity_val.get("target").and_then(|x| x.as_str()) {
+            Some(t) => t.to_string(),
+            None => return Err(Error::msg("Move without target")),
+        };
+        let target_url = ActorUrl::new(target.to_owned())?;
+
+        // Target's alsoKnownAs must point back to the old account.
+        let new_account = match recipient_with_url(&target).ok() {
+            Some(local) => local,
+            None => {
+                // Refetch the target. Its aliases may be updated just now.
+                target_url.actor().await?.store().await?;
+                MAccount::get(target_url).await?
+            }
+        };
+        if !new_account
+            .also_known_as
+            .contains(&old_account.actor_url.to_string())
+        {
+            tracing::error!(
+                "{} is not an alias of {}",
+                old_account.actor_url,
+                target
+            );
+            return Err(Error::msg("Move target does not have the alias"));
+        }
+
+        old_account.move_to(&new_account).await?;
+
+        // Local followers of the old account re-follow the new one.
+        for follower in recipients() {
+            if follower.uid != new_account.uid {
+                refollow(&follower, &old_account, &new_account).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Follow the new account if follower was following the old one.
+async fn refollow(
+    follower: &MAccount,
+    old_account: &MAccount,
+    new_account: &MAccount,
+) -> Result<()> {
+    let following = match MFollow::follow_record(follower, old_account).await?
+    {
+        Some(f) => f.pending != Some(true),
+        None => false,
+    };
+    if !following {
+        return Ok(());
+    }
+
+    let follow_object = Follow::new::<Follow>(
+        follower.actor_url.to_string(),
+        new_account.actor_url.to_string(),
+    )
+    .await;
+    let send_result = crate::mastodon::post_activity(
+        follower.to_owned(),
+        follow_object.to_owned(),
+    )
+    .await?;
+    if send_result != 200u16 && send_result != 202u16 {
+        return Err(Error::msg(format!(
+            "Follow to {} got {}",
+            new_account.actor_url, send_result
+        )));
+    }
+
+    // Wait for Accept from the new account.
+    MFollow::request(
+        follow_object.id,
+        follower.uid.to_owned(),
+        new_account.uid.to_owned(),
+    )
+    .await?;
+    MFollow::remove(follower, old_account).await
+}
@@ -0,0 +1,106 @@
+//! Reject activity.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-reject>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::{Error, Result};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::activity::Execute;
+use crate::mastodon::follow::Follow as MFollow;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::{actor_account, recipient_with_url};
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/users/seungjin#rejects/follows/",
+  "type":"Reject",
+  "actor":"https://mas.to/users/seungjin",
+  "object": {
+      "id":"https://dev.prefer.social/0190f4bf-aad1-7290-ac1f-86333df63b82",
+      "type":"Follow",
+      "actor":"https://dev.prefer.social/self",
+      "object":"https://mas.to/users/seungjin"
+   }
+}
+*/
+
+/// Reject activity struct.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Reject(pub Value);
+
+impl Reject {
+    /// resturn Reject object.
+    pub async fn new(actor: String, object: Value) -> Activity<Reject> {
+        let uuid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uuid);
+
+        let reject_object = Activity::new(
+            true,
+            id,
+            ActivityType::Reject,
+            actor,
+            None,
+            None,
+            None,
+            Reject(object),
+        );
+
+        reject_object
+    }
+}
+
+impl fmt::Display for Reject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Reject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl Execute for Reject {
+    async fn execute(&self, _activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Reject execute <---------------");
+
+        let object_type = self.0.get("type").and_then(|x| x.as_str());
+        if object_type != Some("Follow") {
+            return Err(Error::msg(format!(
+                "Reject '{:?}' is not implemented!",
+                object_type
+            )));
+        }
+
+        let actor_account = actor_account()?;
+
+        // Rejected follow must be ours and sent to the rejecting actor.
+        let follow_actor = self.0.get("actor").and_then(|x| x.as_str());
+        let follow_object = self.0.get("object").and_then(|x| x.as_str());
+        let actor_url = actor_account.actor_url.to_string();
+        let follower_account = follow_actor
+            .filter(|_| follow_object == Some(actor_url.as_str()))
+            .and_then(|x| recipient_with_url(x).ok());
+        let follower_account = match follower_account {
+            Some(a) => a,
+            None => {
+                tracing::error!("Rejected Follow is not ours: {:?}", self.0);
+                return Err(Error::msg("Rejected Follow is not ours"));
+            }
+        };
+
+        // Pending request is dropped. Existing follow is removed too.
+        MFollow::remove(&follower_account, &actor_account).await
+    }
+}
@@ -0,0 +1,98 @@
+//! Remove activity. Unpinning status from featured collection.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-remove>
+//! Mastodon doc: <https://docs.joinmastodon.org/spec/activitypub/#featured>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::Result;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::activitystream::activity::add::featured_object_id;
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::activity::Execute;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::status_pin::StatusPin as MStatusPin;
+use crate::mastodon::actor_account;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+
+/*
+{
+  "@context":"https://www.w3.org/ns/activitystreams",
+  "id":"https://mas.to/users/seungjin#removes/112941245370331112",
+  "type":"Remove",
+  "actor":"https://mas.to/users/seungjin",
+  "object":"https://mas.to/users/seungjin/statuses/112941245370331112",
+  "target":"https://mas.to/users/seungjin/collections/featured"
+}
+*/
+
+/// Remove activity struct. Object is a status url or Note object.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Remove(pub Value);
+
+impl Remove {
+    /// resturn Remove object. Object is removed from target collection.
+    pub async fn new(
+        actor: String,
+        object: Value,
+        target: String,
+    ) -> Activity<Remove> {
+        let uuid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uuid);
+
+        let mut remove_object = Activity::new(
+            true,
+            id,
+            ActivityType::Remove,
+            actor,
+            None,
+            None,
+            None,
+            Remove(object),
+        );
+        remove_object.target = Some(target);
+
+        remove_object
+    }
+}
+
+impl fmt::Display for Remove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Remove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl Execute for Remove {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Remove execute <---------------");
+
+        let actor_account = actor_account()?;
+        let object_id =
+            featured_object_id(&self.0, &activity_val, &actor_account)?;
+
+        match TStatus::get(("uri".to_string(), object_id.to_owned()))
+            .await?
+            .last()
+        {
+            Some(tstatus) => MStatusPin::undo(&actor_account, tstatus).await,
+            None => {
+                tracing::debug!("Unpinned status is unknown: {}", object_id);
+                Ok(())
+            }
+        }
+    }
+}
@@ -5,15 +5,23 @@ use std::fmt::Debug;
 
 use anyhow::{Error, Result};
 use serde_derive::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::str::FromStr;
+use uuid::Uuid;
 
+use crate::activitystream::activity::like::Like;
+use crate::activitystream::activity::Activity;
 use crate::activitystream::activity::ActivityType;
 use crate::activitystream::activity::Execute;
 use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::activity_log::ActivityLog;
+use crate::mastodon::block::Block as MBlock;
 use crate::mastodon::follow::Follow;
-use crate::mastodon::ACTOR_ACCOUNT;
-use crate::mastodon::ME_ACCOUNT;
+use crate::mastodon::notification::Notification;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::{actor_account, recipient_with_url};
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
 
 /*
 {
@@ -30,9 +38,30 @@ use crate::mastodon::ME_ACCOUNT;
 }
 */
 
-/// Accept activity struct.  
+/// Undo activity struct.  
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
-pub struct Undo(Value);
+pub struct Undo(pub Value);
+
+impl Undo {
+    /// resturn Undo object.  
+    pub async fn new(actor: String, object: Value) -> Activity<Undo> {
+        let uuid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uuid);
+
+        let undo_object = Activity::new(
+            true,
+            id,
+            ActivityType::Undo,
+            actor,
+            None,
+            None,
+            None,
+            Undo(object),
+        );
+
+        undo_object
+    }
+}
 
 impl fmt::Display for Undo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -50,43 +79,167 @@ impl fmt::Debug for Undo {
 
 impl Execute for Undo {
     async fn execute(&self, activity_val: Value) -> Result<()> {
-        let a: &str = activity_val
-            .get("object")
-            .unwrap()
+        let actor = activity_val
+            .get("actor")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+        let original = match original_activity(&self.0).await {
+            Ok(o) => o,
+            // Like given only by its id may be missing in the log.
+            // Its favourite is kept with the id.
+            Err(e) => match self.0.as_str() {
+                Some(id) if Like::is_stored(id).await? => {
+                    return undo_like(json!({ "id": id })).await
+                }
+                _ => return Err(e),
+            },
+        };
+
+        // Only the actor of the original activity can undo it.
+        if original.get("actor").and_then(|x| x.as_str()) != Some(actor) {
+            tracing::error!(
+                "{} is not the actor of {}",
+                actor,
+                id_of(&original)
+            );
+            return Err(Error::msg(
+                "Only the actor of the activity can undo it",
+            ));
+        }
+
+        let a = original
             .get("type")
-            .unwrap()
-            .as_str()
-            .unwrap();
-        let activity_type = ActivityType::from_str(a).unwrap();
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+        let activity_type =
+            ActivityType::from_str(a).unwrap_or(ActivityType::NotDefined);
 
         match activity_type {
-            ActivityType::Follow => undo_follow(activity_val).await,
+            ActivityType::Follow => undo_follow(original).await,
+            ActivityType::Like => undo_like(original).await,
+            ActivityType::Announce => undo_announce(original).await,
+            ActivityType::Block => undo_block(original).await,
+            ActivityType::Accept => undo_accept(original).await,
             unknown_type => unknown(unknown_type).await,
         }
     }
 }
 
-async fn undo_follow(activity: Value) -> Result<()> {
+/// The activity to undo. Logged one is preferred over the embedded object,
+/// since the object can be only its id or forged.
+async fn original_activity(object: &Value) -> Result<Value> {
+    let id = match object {
+        Value::String(s) => s.to_owned(),
+        o => match o.get("id").and_then(|x| x.as_str()) {
+            Some(id) => id.to_string(),
+            None => return Err(Error::msg("Undo object has no id")),
+        },
+    };
+
+    match ActivityLog::get_with_id(id.as_str()).await? {
+        Some(logged) => Ok(logged),
+        None if object.is_object() => Ok(object.to_owned()),
+        None => Err(Error::msg(format!("Activity {} to undo is unknown", id))),
+    }
+}
+
+fn id_of(activity: &Value) -> String {
+    activity
+        .get("id")
+        .and_then(|x| x.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+async fn undo_follow(follow: Value) -> Result<()> {
     tracing::debug!("Undo follow");
 
-    let a = serde_json::to_string(&activity).unwrap();
-    tracing::debug!("{:?}", a);
+    // Only the follower undoes its Follow, logged or embedded.
+    let actor_url = actor_account()?.actor_url.to_string();
+    if follow.get("actor").and_then(|x| x.as_str()) != Some(actor_url.as_str())
+    {
+        return Err(Error::msg("Only the follower can undo the Follow"));
+    }
 
-    let actor = activity.get("actor").unwrap();
-    let follow_object = activity.get("object").unwrap();
-    let follow_object_id = follow_object.get("id").unwrap();
+    let follow_id = id_of(&follow);
+    Notification::remove(follow_id.to_owned(), "Follow").await?;
+    Follow::undo(follow_id).await
+}
+
+async fn undo_like(like: Value) -> Result<()> {
+    tracing::debug!("Undo like");
+
+    let like_id = like
+        .get("id")
+        .and_then(|x| x.as_str())
+        .ok_or(Error::msg("Like has no id"))?;
+    let actor_account = actor_account()?;
+    Like::undo(like_id.to_string(), &actor_account).await
+}
+
+async fn undo_announce(announce: Value) -> Result<()> {
+    tracing::debug!("Undo announce");
+
+    let announce_id = id_of(&announce);
+    let reblog = match TStatus::get(("uri".to_string(), announce_id.to_owned()))
+        .await?
+        .last()
+    {
+        Some(t) => t.to_owned(),
+        None => {
+            tracing::debug!("No reblog to undo: {}", announce_id);
+            return Ok(());
+        }
+    };
 
-    if actor.to_owned() == follow_object.get("actor").unwrap().to_owned() {
-        return Follow::undo(follow_object_id.to_string()).await;
+    let actor_account = actor_account()?;
+    if reblog.account_id != actor_account.uid.to_string()
+        || reblog.reblog_of_id.is_none()
+    {
+        return Err(Error::msg("Only the actor who boosted can undo it"));
     }
 
-    Err(Error::msg("Something wrong when undo follower"))
+    TStatus::delete(reblog.uid.to_owned()).await?;
+    Notification::remove(reblog.uid, "Status").await
+}
+
+async fn undo_block(block: Value) -> Result<()> {
+    tracing::debug!("Undo block");
+
+    let blocked = block
+        .get("object")
+        .and_then(|x| x.as_str())
+        .ok_or(Error::msg("Block has no object"))?;
+    let blocked_account = recipient_with_url(blocked)?;
+    let actor_account = actor_account()?;
+    MBlock::undo(&actor_account, &blocked_account).await
+}
+
+/// Followee revoked the accepted follow. It goes back to a follow request.
+async fn undo_accept(accept: Value) -> Result<()> {
+    tracing::debug!("Undo accept");
+
+    let follow = match accept.get("object") {
+        Some(Value::String(s)) => ActivityLog::get_with_id(s)
+            .await?
+            .ok_or(Error::msg(format!("Follow {} is unknown", s)))?,
+        Some(o) => o.to_owned(),
+        None => return Err(Error::msg("Accept has no object")),
+    };
+    let follower = follow
+        .get("actor")
+        .and_then(|x| x.as_str())
+        .ok_or(Error::msg("Follow has no actor"))?;
+
+    let follower_account = recipient_with_url(follower)?;
+    let actor_account = actor_account()?;
+    Follow::revoke(id_of(&follow), &follower_account, &actor_account).await
 }
 
 async fn unknown(unknown_type: ActivityType) -> Result<()> {
-    tracing::error!("Create '{:?}' is not implemented!", unknown_type);
+    tracing::error!("Undo '{:?}' is not implemented!", unknown_type);
     Err(Error::msg(format!(
-        "Create '{:?}' is not implemented!",
+        "Undo '{:?}' is not implemented!",
         unknown_type
     )))
 }
@@ -0,0 +1,258 @@
+//! Update activity
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-update>
+
+use std::fmt;
+use std::fmt::Debug;
+
+use anyhow::{Error, Result};
+use chrono::Utc;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::activity::Execute;
+use crate::activitystream::actor::person::Person as PersonActor;
+use crate::activitystream::object::note::Note as NoteObject;
+use crate::activitystream::object::question::Question as QuestionObject;
+use crate::activitystream::PUBLIC;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::poll::Poll as MPoll;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::status::Status as MStatus;
+use crate::mastodon::actor_account;
+use crate::table::account::Account as TAccount;
+use crate::table::account::New as _;
+use crate::table::poll::Get as _;
+use crate::table::poll::Poll as TPoll;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+
+/*
+{
+  "@context":[
+    "https://www.w3.org/ns/activitystreams",
+    "https://w3id.org/security/v1",
+  ],
+  "id":"https://mas.to/users/seungjin#updates/1723475143",
+  "type":"Update",
+  "actor":"https://mas.to/users/seungjin",
+  "to":["https://www.w3.org/ns/activitystreams#Public"],
+  "object":{
+    "id":"https://mas.to/users/seungjin",
+    "type":"Person",
+    "preferredUsername":"seungjin",
+    "name":"Seungjin Kim",
+    "summary":"<p>new bio</p>",
+    "publicKey":{
+      "id":"https://mas.to/users/seungjin#main-key",
+      "owner":"https://mas.to/users/seungjin",
+      "publicKeyPem":"-----BEGIN PUBLIC KEY-----\n...\n-----END PUBLIC KEY-----\n"
+    },
+    ...
+  }
+}
+*/
+
+/// Update activity struct.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+pub struct Update(pub Value);
+
+impl Update {
+    /// resturn Update object.
+    pub async fn new(actor: String, object: Value) -> Activity<Update> {
+        let uuid = Uuid::now_v7().to_string();
+        let id = format!("https://{}/{}", Setting::domain().await, uuid);
+
+        Activity::new(
+            true,
+            id,
+            ActivityType::Update,
+            actor.to_owned(),
+            Some(Utc::now()),
+            Some(vec![PUBLIC.to_string()]),
+            Some(vec![format!("{}/followers", actor)]),
+            Update(object),
+        )
+    }
+}
+
+impl fmt::Display for Update {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Update {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl Execute for Update {
+    async fn execute(&self, activity_val: Value) -> Result<()> {
+        tracing::debug!("-----------> Update execute <---------------");
+
+        let object_type = self
+            .0
+            .get("type")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+
+        let actor_account = actor_account()?;
+
+        match object_type {
+            "Person" | "Service" | "Group" | "Organization" | "Application" => {
+                update_actor(self.to_owned(), activity_val, actor_account).await
+            }
+            "Note" => {
+                update_note(self.to_owned(), activity_val, actor_account).await
+            }
+            "Question" => {
+                update_question(self.to_owned(), activity_val, actor_account)
+                    .await
+            }
+            unknown_type => unknown(unknown_type).await,
+        }
+    }
+}
+
+async fn update_actor(
+    s: Update,
+    activity: Value,
+    actor_account: MAccount,
+) -> Result<()> {
+    let actor = activity.get("actor").and_then(|x| x.as_str());
+    let object_id = s.0.get("id").and_then(|x| x.as_str());
+
+    // Only the actor itself can update its profile.
+    if actor.is_none()
+        || actor != object_id
+        || actor != Some(actor_account.actor_url.to_string().as_str())
+    {
+        tracing::error!(
+            "Person id {:?} does not match the signing actor {:?}",
+            object_id,
+            actor_account.actor_url.to_string()
+        );
+        return Err(Error::msg("Person id does not match the signing actor"));
+    }
+
+    match serde_json::from_value::<PersonActor>(s.0.to_owned()) {
+        Ok(person) => TAccount::update(person).await,
+        Err(e) => {
+            tracing::error!("Error from Parsing PersonActor: {e:?}");
+            tracing::error!("{:?}", s.0);
+            Err(Error::msg(format!("Error from Parsing PersonActor: {e:?}")))
+        }
+    }
+}
+
+async fn update_note(
+    s: Update,
+    activity: Value,
+    actor_account: MAccount,
+) -> Result<()> {
+    let note = match serde_json::from_value::<NoteObject>(s.0.to_owned()) {
+        Ok(note) => note,
+        Err(e) => {
+            tracing::error!("Error from Parsing NoteObject: {e:?}");
+            tracing::error!("{activity:?}");
+            return Err(Error::msg(format!(
+                "Error from Parsing NoteObject: {e:?}"
+            )));
+        }
+    };
+
+    let tstatus = match TStatus::get(("uri".to_string(), note.id.to_owned()))
+        .await?
+        .last()
+    {
+        Some(t) => t.to_owned(),
+        None => {
+            tracing::debug!("Edited status is unknown: {}", note.id);
+            return Ok(());
+        }
+    };
+
+    // Only the author can edit the status.
+    if tstatus.account_id != actor_account.uid.to_string() {
+        tracing::error!(
+            "{} is not the author of {}",
+            actor_account.actor_url.to_string(),
+            note.id
+        );
+        return Err(Error::msg("Only the author can edit the status"));
+    }
+
+    MStatus::edit(tstatus, note).await
+}
+
+/// Edited content and new votes count of the poll.
+async fn update_question(
+    s: Update,
+    activity: Value,
+    actor_account: MAccount,
+) -> Result<()> {
+    let question =
+        match serde_json::from_value::<QuestionObject>(s.0.to_owned()) {
+            Ok(question) => question,
+            Err(e) => {
+                tracing::error!("Error from Parsing QuestionObject: {e:?}");
+                tracing::error!("{activity:?}");
+                return Err(Error::msg(format!(
+                    "Error from Parsing QuestionObject: {e:?}"
+                )));
+            }
+        };
+
+    let tstatus =
+        match TStatus::get(("uri".to_string(), question.note.id.to_owned()))
+            .await?
+            .last()
+        {
+            Some(t) => t.to_owned(),
+            None => {
+                tracing::debug!(
+                    "Updated poll is unknown: {}",
+                    question.note.id
+                );
+                return Ok(());
+            }
+        };
+
+    if tstatus.account_id != actor_account.uid.to_string() {
+        tracing::error!(
+            "{} is not the author of {}",
+            actor_account.actor_url.to_string(),
+            question.note.id
+        );
+        return Err(Error::msg("Only the author can update the poll"));
+    }
+
+    if let Some(poll_id) = tstatus.poll_id.to_owned() {
+        if let Some(tpoll) =
+            TPoll::get(("uid".to_string(), poll_id)).await?.last()
+        {
+            MPoll::refresh(tpoll, &question).await?;
+        }
+    }
+
+    // Content is edited only when it is changed.
+    match question.note.updated.is_some() {
+        true => MStatus::edit(tstatus, question.note).await,
+        false => Ok(()),
+    }
+}
+
+async fn unknown(unknown_type: &str) -> Result<()> {
+    tracing::error!("Update '{}' is not implemented!", unknown_type);
+    Err(Error::msg(format!(
+        "Update '{}' is not implemented!",
+        unknown_type
+    )))
+}
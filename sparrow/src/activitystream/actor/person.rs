@@ -11,6 +11,7 @@ use std::{fmt, str};
 
 use crate::activitystream::actor::ActorType;
 use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account_key::AccountKey;
 use crate::mastodon::setting::Setting;
 use crate::mastodon::user::Get;
 use crate::mastodon::user::User;
@@ -33,15 +34,20 @@ pub struct Person {
     pub id: String,
     #[serde(rename = "type")]
     pub actor_type: ActorType,
+    // Instance actor(Application) has no following, followers, name and url.
+    #[serde(default)]
     pub following: String,
+    #[serde(default)]
     pub followers: String,
     pub inbox: String,
     pub outbox: String,
     pub featured: Option<String>,
     pub featured_tags: Option<String>,
     pub preferred_username: String,
+    #[serde(default)]
     pub name: String,
     pub summary: Option<String>,
+    #[serde(default)]
     pub url: String,
     pub manually_approves_followers: Option<bool>,
     pub discoverable: Option<bool>,
@@ -52,8 +58,15 @@ pub struct Person {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub devices: Option<String>,
     pub public_key: PublicKey,
+    /// Multikeys. A key object, a list of them or their ids.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assertion_method: Option<Value>,
     pub tag: Option<Vec<Value>>,
     pub attachment: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub also_known_as: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved_to: Option<String>,
     pub endpoints: Endpoints,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<Image>,
@@ -69,6 +82,33 @@ pub struct PublicKey {
     pub public_key_pem: String,
 }
 
+/// Ed25519 key in assertionMethod. FEP-521a
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Multikey {
+    /// Key id, e.g. `https://example.com/users/a#ed25519-key`.
+    pub id: String,
+    /// Always `Multikey`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Actor url owning the key.
+    pub controller: String,
+    /// Base58-btc multibase of the multicodec Ed25519 public key.
+    pub public_key_multibase: String,
+}
+
+impl Multikey {
+    /// Multikey of the id, owned by controller.
+    pub fn new(id: String, controller: String, multibase: String) -> Self {
+        Multikey {
+            id,
+            kind: "Multikey".to_string(),
+            controller,
+            public_key_multibase: multibase,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Image {
@@ -96,6 +136,20 @@ impl Person {
             _ => a.account_uri.to_owned().domain.unwrap(),
         };
 
+        let also_known_as = match a.also_known_as.is_empty() {
+            true => None,
+            false => Some(a.also_known_as.to_owned()),
+        };
+        let moved_to = a.moved_to().await?.map(|x| x.actor_url.to_string());
+
+        // Local actors publish their Ed25519 key.
+        let assertion_method = match a.local() {
+            true => Some(serde_json::to_value(vec![
+                AccountKey::multikey(&a).await?,
+            ])?),
+            false => None,
+        };
+
         let pk = PublicKey {
             id: format!(
                 "{}#main-key",
@@ -122,6 +176,7 @@ impl Person {
         let ct = vec![
             "https://www.w3.org/ns/activitystreams".to_string(),
             "https://w3id.org/security/v1".to_string(),
+            "https://w3id.org/security/multikey/v1".to_string(),
         ];
         let ct_val = serde_json::to_value(&ct).unwrap();
 
@@ -143,7 +198,7 @@ impl Person {
             name: a.display_name.to_owned(),
             summary: Some(a.note.to_owned()),
             url: a.url.to_owned(),
-            manually_approves_followers: Some(false), // Todo:
+            manually_approves_followers: Some(a.locked),
             discoverable: Some(a.discoverable.to_owned()),
             indexable: a.indexable.to_owned(),
             published: Some(crate::utils::convert_epoch_to_iso_8601(
@@ -152,8 +207,11 @@ impl Person {
             memorial: Some(false),
             devices: None,
             public_key: pk,
+            assertion_method,
             tag: Some(Vec::new()),
             attachment: Some(Vec::new()),
+            also_known_as,
+            moved_to,
             endpoints: endpoints,
             icon: Some(icon),
             image: Some(image),
@@ -163,15 +221,29 @@ impl Person {
     }
 
     /// Stores this actor to Account table.  
+    /// Its Multikeys go to AccountKey table.
     pub async fn store(&self) -> Result<()> {
-        Ok(TAccount::new(self.to_owned()).await?)
+        TAccount::new(self.to_owned()).await?;
+        AccountKey::store(self).await
+    }
+
+    /// Multikeys embedded in assertionMethod. Referenced ones are skipped.
+    pub fn multikeys(&self) -> Vec<Multikey> {
+        let keys = match self.assertion_method.to_owned() {
+            Some(Value::Array(a)) => a,
+            Some(v) => vec![v],
+            None => Vec::new(),
+        };
+        keys.into_iter()
+            .filter_map(|x| serde_json::from_value::<Multikey>(x).ok())
+            .filter(|x| x.kind == "Multikey")
+            .collect()
     }
 }
 
 impl TryFrom<serde_json::Value> for Person {
     type Error = ();
     fn try_from(actor_value: serde_json::Value) -> Result<Self, Self::Error> {
-        let person = serde_json::from_value::<Person>(actor_value).unwrap();
-        Ok(person)
+        serde_json::from_value::<Person>(actor_value).map_err(|_| ())
     }
 }
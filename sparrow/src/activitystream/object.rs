@@ -1,24 +1,37 @@
 //! Describes an object of any kind. The Object type serves as the base type for most of the other kinds of objects defined in the Activity Vocabulary, including other Core types such as Activity, IntransitiveActivity, Collection and OrderedCollection.
 //! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-object>
 
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
+use std::str;
 use std::str::FromStr;
 use url::Url;
 
+use crate::mastodon;
+
+pub mod article;
+pub mod document;
 pub mod note;
+pub mod question;
+pub mod tombstone;
 
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
 pub enum ObjectType {
     Article,
+    Audio,
     Document,
     Event,
+    Image,
     Note,
+    Page,
     Place,
     Profile,
+    Question,
     Relationship,
     Tombstone,
+    Video,
     Url(String),
     #[default]
     NotDefined,
@@ -38,14 +51,29 @@ impl FromStr for ObjectType {
         }
         match input {
             "Article" => Ok(ObjectType::Article),
+            "Audio" => Ok(ObjectType::Audio),
             "Document" => Ok(ObjectType::Document),
             "Event" => Ok(ObjectType::Event),
+            "Image" => Ok(ObjectType::Image),
             "Note" => Ok(ObjectType::Note),
+            "Page" => Ok(ObjectType::Page),
             "Place" => Ok(ObjectType::Place),
             "Profile" => Ok(ObjectType::Profile),
+            "Question" => Ok(ObjectType::Question),
             "Relationship" => Ok(ObjectType::Relationship),
             "Tombstone" => Ok(ObjectType::Tombstone),
+            "Video" => Ok(ObjectType::Video),
             _ => Ok(ObjectType::NotDefined),
         }
     }
 }
+
+/// Fetch an object(json) from remote server with its id.  
+/// Deleted object is `FetchError::Gone`.
+pub async fn fetch(id: &str) -> Result<Value> {
+    let me_account = mastodon::me_account()?;
+    let response = mastodon::get_fediverse(Url::parse(id)?, me_account).await?;
+
+    let body = str::from_utf8(response.body())?;
+    Ok(serde_json::from_str::<Value>(body)?)
+}
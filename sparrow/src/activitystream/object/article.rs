@@ -0,0 +1,225 @@
+//! Represents any kind of multi-paragraph written work.
+//!
+//! Page, Event, Video, Audio and Image objects are read with the same struct.
+//! They come from WriteFreely, Lemmy, Mobilizon, PeerTube and so on.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-article>
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+use crate::activitystream::object::document::{href, Document};
+use crate::activitystream::object::note::Note;
+use crate::activitystream::object::ObjectType;
+
+/*
+{
+  "id":"https://write.as/seungjin/hello-world",
+  "type":"Article",
+  "name":"Hello World",
+  "summary":"<p>First post of the blog</p>",
+  "content":"<p>First post of the blog. ...</p>",
+  "mediaType":"text/html",
+  "url":"https://write.as/seungjin/hello-world",
+  "attributedTo":"https://write.as/api/collections/seungjin",
+  "published":"2024-08-12T04:45:51Z",
+  "to":["https://www.w3.org/ns/activitystreams#Public"],
+  "cc":["https://write.as/api/collections/seungjin/followers"],
+  "tag":[]
+}
+
+{
+  "id":"https://peertube.example/videos/watch/9c9de5e8",
+  "type":"Video",
+  "name":"A video",
+  "duration":"PT22S",
+  "content":"<p>Description of the video</p>",
+  "url":[
+    {"type":"Link","mediaType":"text/html","href":"https://peertube.example/w/9c9de5e8"},
+    {"type":"Link","mediaType":"video/mp4","href":"https://peertube.example/static/web-videos/9c9de5e8-720.mp4"}
+  ],
+  "attributedTo":[
+    {"type":"Person","id":"https://peertube.example/accounts/seungjin"},
+    {"type":"Group","id":"https://peertube.example/video-channels/seungjin_channel"}
+  ],
+  "published":"2024-08-12T04:45:51.000Z"
+}
+*/
+
+/// Article struct. Also covers Page, Event, Video, Audio and Image.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Article {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub article_type: ObjectType,
+    /// Title.
+    pub name: Option<String>,
+    pub summary: Option<String>,
+    pub content: Option<String>,
+    pub media_type: Option<String>,
+    /// String, Link object or array of Link objects.
+    pub url: Option<Value>,
+    /// String, actor object or array of them.
+    pub attributed_to: Option<Value>,
+    pub in_reply_to: Option<String>,
+    pub published: Option<String>,
+    pub updated: Option<String>,
+    pub to: Option<Value>,
+    pub cc: Option<Value>,
+    pub sensitive: Option<bool>,
+    pub attachment: Option<Value>,
+    pub tag: Option<Vec<Value>>,
+    /// Event only.
+    pub start_time: Option<String>,
+    /// Event only.
+    pub end_time: Option<String>,
+}
+
+impl Article {
+    /// Link to the html representation.
+    pub fn link(&self) -> String {
+        self.url
+            .as_ref()
+            .and_then(href)
+            .unwrap_or(self.id.to_owned())
+    }
+
+    /// Author's actor url. Person is preferred over Group(channel).
+    pub fn author(&self) -> Option<String> {
+        match self.attributed_to.as_ref()? {
+            Value::Array(a) => a
+                .iter()
+                .find(|x| {
+                    x.get("type").and_then(|y| y.as_str()) == Some("Person")
+                })
+                .or(a.first())
+                .and_then(actor_id),
+            v => actor_id(v),
+        }
+    }
+
+    /// Media and links attached to the object.
+    /// Video, Audio and Image carry their media in `url`.
+    pub fn attachments(&self) -> Vec<Document> {
+        let mut documents = Document::list(self.attachment.as_ref());
+
+        let is_media = matches!(
+            self.article_type,
+            ObjectType::Video | ObjectType::Audio | ObjectType::Image
+        );
+        if is_media && documents.iter().all(|x| x.is_link()) {
+            let media = match self.url.as_ref() {
+                Some(Value::Array(a)) => a.to_owned(),
+                Some(v) => vec![v.to_owned()],
+                None => Vec::new(),
+            }
+            .into_iter()
+            .filter_map(|x| match x {
+                Value::String(s) => Some(Document {
+                    document_type: Some(format!("{}", self.article_type)),
+                    url: Some(Value::String(s)),
+                    ..Default::default()
+                }),
+                x => serde_json::from_value::<Document>(x).ok(),
+            })
+            .find(|x| !x.is_link());
+            documents.extend(media);
+        }
+
+        documents
+    }
+
+    /// Note to be stored as a status. Title, summary and link are rendered.
+    pub fn to_note(&self) -> Note {
+        let link = self.link();
+
+        let mut content = String::new();
+        if let Some(name) = self.name.as_ref().filter(|x| !x.is_empty()) {
+            content
+                .push_str(&format!("<p><strong>{}</strong></p>", escape(name)));
+        }
+        if let Some(start_time) = self.start_time.as_ref() {
+            content.push_str(&format!("<p>{}</p>", escape(start_time)));
+        }
+        if let Some(body) = self.summary.to_owned().or(self.content.to_owned())
+        {
+            content.push_str(&body);
+        }
+        for l in self.attachments().iter().filter(|x| x.is_link()) {
+            if let Some(u) = l.url().filter(|x| *x != link) {
+                content.push_str(&anchor(&u));
+            }
+        }
+        content.push_str(&anchor(&link));
+
+        let attachment = self
+            .attachments()
+            .into_iter()
+            .filter(|x| !x.is_link())
+            .filter_map(|x| serde_json::to_value(x).ok())
+            .collect();
+
+        Note {
+            id: self.id.to_owned(),
+            note_type: self.article_type.to_owned(),
+            in_reply_to: self.in_reply_to.to_owned(),
+            published: self.published.to_owned(),
+            updated: self.updated.to_owned(),
+            url: Some(link),
+            attributed_to: self.author(),
+            to: self.to.as_ref().map(addresses),
+            cc: self.cc.as_ref().map(addresses),
+            sensitive: self.sensitive,
+            content: Some(content),
+            attachment: Some(attachment),
+            tag: self.tag.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+fn actor_id(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.to_owned()),
+        x => x.get("id").and_then(|y| y.as_str()).map(String::from),
+    }
+}
+
+fn addresses(v: &Value) -> Vec<String> {
+    match v {
+        Value::String(s) => vec![s.to_owned()],
+        Value::Array(a) => a
+            .iter()
+            .filter_map(|x| x.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn anchor(url: &str) -> String {
+    let u = escape(url);
+    format!("<p><a href=\"{}\">{}</a></p>", u, u)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl fmt::Display for Article {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Article {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
@@ -0,0 +1,120 @@
+//! Represents a document of any kind. Used for media attached to objects.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-document>
+//! Mastodon doc: <https://docs.joinmastodon.org/spec/activitypub/#as>
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/*
+{
+  "type":"Document",
+  "mediaType":"image/png",
+  "url":"https://files.mastodon.social/media_attachments/files/112/947/original/a.png",
+  "name":"A cat sleeping on a keyboard",
+  "blurhash":"UFE{9#%M00Rj~qt7IURj4nRj%MofM{ayRjof",
+  "width":1200,
+  "height":800
+}
+*/
+
+/// Document struct. Also covers Image, Video, Audio and Link attachments.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    #[serde(rename = "type")]
+    pub document_type: Option<String>,
+    pub media_type: Option<String>,
+    /// String, Link object or array of Link objects.
+    pub url: Option<Value>,
+    /// Link objects have href instead of url.
+    pub href: Option<String>,
+    /// Alt text.
+    pub name: Option<String>,
+    pub blurhash: Option<String>,
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+}
+
+impl Document {
+    /// Documents of `attachment` field. Single object or array.
+    pub fn list(attachment: Option<&Value>) -> Vec<Document> {
+        match attachment {
+            Some(Value::Array(a)) => a
+                .iter()
+                .filter_map(|x| serde_json::from_value(x.to_owned()).ok())
+                .collect(),
+            Some(v @ Value::Object(_)) => {
+                serde_json::from_value(v.to_owned()).into_iter().collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Is it a link to a web page rather than media?
+    pub fn is_link(&self) -> bool {
+        let media_type = self.media_type.as_deref().unwrap_or("text/html");
+        self.document_type.as_deref() == Some("Link")
+            && media_type.starts_with("text/")
+    }
+
+    /// Location of the document.
+    pub fn url(&self) -> Option<String> {
+        match &self.url {
+            Some(v) => href(v),
+            None => self.href.to_owned(),
+        }
+    }
+
+    /// Mastodon's media type. image, video, audio or unknown.
+    pub fn media_kind(&self) -> &str {
+        let media_type = self.media_type.as_deref().unwrap_or_default();
+        match media_type.split('/').next() {
+            Some("image") => return "image",
+            Some("video") => return "video",
+            Some("audio") => return "audio",
+            _ => (),
+        }
+        match self.document_type.as_deref() {
+            Some("Image") => "image",
+            Some("Video") => "video",
+            Some("Audio") => "audio",
+            _ => "unknown",
+        }
+    }
+}
+
+/// Url of string, Link object or array of them. Html link is preferred.
+pub fn href(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.to_owned()),
+        Value::Object(o) => o
+            .get("href")
+            .or(o.get("url"))
+            .and_then(|x| x.as_str())
+            .map(String::from),
+        Value::Array(a) => a
+            .iter()
+            .find(|x| {
+                x.get("mediaType").and_then(|y| y.as_str()) == Some("text/html")
+            })
+            .or(a.first())
+            .and_then(href),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Document {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
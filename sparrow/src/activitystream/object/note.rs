@@ -11,7 +11,15 @@ use std::fmt;
 use crate::activitystream::activity::Activity;
 use crate::activitystream::collection::Collection;
 use crate::activitystream::object::ObjectType;
+use crate::activitystream::PUBLIC;
+use crate::mastodon::account::uid::Uid as AccountUid;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::setting::Setting;
 use crate::mastodon::status::Status as MStatus;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+use crate::utils::convert_epoch_to_iso_8601;
 
 /*
 
@@ -90,27 +98,88 @@ pub struct Note {
     pub id: String,
     #[serde(rename = "type")]
     pub note_type: ObjectType,
+    /// Chosen option of the poll when the note is a vote.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     pub summary: Option<String>,
     pub in_reply_to: Option<String>,
     pub published: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
     pub url: Option<String>,
     pub attributed_to: Option<String>,
     pub to: Option<Vec<String>>,
     pub bto: Option<Vec<String>>,
     pub cc: Option<Vec<String>>,
     pub bcc: Option<Vec<String>>,
-    pub sensitivity: Option<bool>,
+    pub sensitive: Option<bool>,
     pub atom_url: Option<String>,
     pub in_reply_to_atom_uri: Option<String>,
     pub conversation: Option<String>,
     pub content: Option<String>,
     pub content_map: Option<HashMap<String, String>>, // Object {"en": String("<p>999</p>")},
-    pub attachment: Option<Vec<String>>,              // Array [],
-    pub tag: Option<Vec<String>>,                     // Array [],
+    pub attachment: Option<Vec<Value>>,               // Array [],
+    pub tag: Option<Vec<Value>>,                      // Array [],
     // Todo: Value for now.
     pub replies: Option<Value>,
 }
 
+impl Note {
+    /// Note object of the status row.
+    pub async fn fr_tstatus(tstatus: TStatus) -> Result<Self> {
+        let author =
+            MAccount::get(AccountUid(tstatus.account_id.to_owned())).await?;
+
+        let id = match tstatus.uri.to_owned() {
+            Some(uri) => uri,
+            None => format!(
+                "https://{}/statuses/{}",
+                Setting::domain().await,
+                tstatus.uid
+            ),
+        };
+
+        let followers = author.followers_url.to_owned().unwrap_or_default();
+        let (to, cc) = match tstatus.visibility {
+            0 => (vec![PUBLIC.to_string()], vec![followers]),
+            1 => (vec![followers], vec![PUBLIC.to_string()]),
+            2 => (vec![followers], Vec::new()),
+            _ => (Vec::new(), Vec::new()),
+        };
+
+        let in_reply_to = match tstatus.in_reply_to_id.to_owned() {
+            Some(uid) => TStatus::get(("uid".to_string(), uid))
+                .await?
+                .last()
+                .and_then(|x| x.uri.to_owned()),
+            None => None,
+        };
+
+        let summary = match tstatus.spoiler_text.is_empty() {
+            true => None,
+            false => Some(tstatus.spoiler_text),
+        };
+
+        Ok(Note {
+            id: id.to_owned(),
+            note_type: ObjectType::Note,
+            summary,
+            in_reply_to,
+            published: Some(convert_epoch_to_iso_8601(tstatus.created_at)),
+            updated: tstatus.edited_at.map(convert_epoch_to_iso_8601),
+            url: Some(tstatus.url.unwrap_or(id)),
+            attributed_to: Some(author.actor_url.to_string()),
+            to: Some(to),
+            cc: Some(cc),
+            sensitive: Some(tstatus.sensitive != 0),
+            content: Some(tstatus.text),
+            attachment: Some(Vec::new()),
+            tag: Some(Vec::new()),
+            ..Default::default()
+        })
+    }
+}
+
 impl fmt::Display for Note {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let a = serde_json::to_string(self).unwrap();
@@ -0,0 +1,170 @@
+//! Represents a question being asked. Mastodon uses it for polls.
+//!
+//! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-question>
+//! Mastodon doc: <https://docs.joinmastodon.org/spec/activitypub/#Question>
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+use crate::activitystream::object::note::Note;
+use crate::activitystream::object::ObjectType;
+use crate::table::poll::Get as _;
+use crate::table::poll::Poll as TPoll;
+use crate::table::status::Status as TStatus;
+use crate::utils::{convert_epoch_to_iso_8601, get_current_epoch};
+
+/*
+{
+  "id":"https://mas.to/users/seungjin/statuses/112947437208434425",
+  "type":"Question",
+  "summary":null,
+  "inReplyTo":null,
+  "published":"2024-08-12T04:45:51Z",
+  "url":"https://mas.to/@seungjin/112947437208434425",
+  "attributedTo":"https://mas.to/users/seungjin",
+  "to":["https://www.w3.org/ns/activitystreams#Public"],
+  "cc":["https://mas.to/users/seungjin/followers"],
+  "sensitive":false,
+  "content":"<p>Lunch?</p>",
+  "endTime":"2024-08-13T04:45:51Z",
+  "votersCount":0,
+  "oneOf":[
+    {"type":"Note","name":"Pizza","replies":{"type":"Collection","totalItems":0}},
+    {"type":"Note","name":"Ramen","replies":{"type":"Collection","totalItems":0}}
+  ]
+}
+*/
+
+/// Question object. Note with poll options.
+#[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Question {
+    #[serde(flatten)]
+    pub note: Note,
+    /// Options of single choice poll.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<QuestionOption>>,
+    /// Options of multiple choice poll.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub any_of: Option<Vec<QuestionOption>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
+    /// Datetime string or `true` when the poll is closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voters_count: Option<i64>,
+}
+
+/// Poll option. Votes count is in `replies.totalItems`.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionOption {
+    #[serde(rename = "type")]
+    pub option_type: String,
+    pub name: String,
+    pub replies: Option<QuestionOptionReplies>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionOptionReplies {
+    #[serde(rename = "type")]
+    pub replies_type: String,
+    pub total_items: i64,
+}
+
+impl Question {
+    /// Is it a multiple choice poll?
+    pub fn multiple(&self) -> bool {
+        self.any_of.is_some()
+    }
+
+    /// Options of the poll.
+    pub fn options(&self) -> Vec<QuestionOption> {
+        self.one_of
+            .to_owned()
+            .or(self.any_of.to_owned())
+            .unwrap_or_default()
+    }
+
+    /// Option titles.
+    pub fn titles(&self) -> Vec<String> {
+        self.options().into_iter().map(|x| x.name).collect()
+    }
+
+    /// Votes count of each option.
+    pub fn tallies(&self) -> Vec<i64> {
+        self.options()
+            .into_iter()
+            .map(|x| x.replies.map(|y| y.total_items).unwrap_or_default())
+            .collect()
+    }
+
+    /// Question object of the status row with a poll.
+    pub async fn fr_tstatus(tstatus: TStatus) -> Result<Self> {
+        let poll_id = match tstatus.poll_id.to_owned() {
+            Some(p) => p,
+            None => return Err(Error::msg("Status has no poll")),
+        };
+        let tpoll = match TPoll::get(("uid".to_string(), poll_id)).await?.last()
+        {
+            Some(p) => p.to_owned(),
+            None => return Err(Error::msg("Poll is not found")),
+        };
+
+        let mut note = Note::fr_tstatus(tstatus).await?;
+        note.note_type = ObjectType::Question;
+
+        let titles: Vec<String> = serde_json::from_str(&tpoll.options)?;
+        let tallies: Vec<i64> =
+            serde_json::from_str(&tpoll.cached_tallies).unwrap_or_default();
+        let options: Vec<QuestionOption> = titles
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| QuestionOption {
+                option_type: "Note".to_string(),
+                name,
+                replies: Some(QuestionOptionReplies {
+                    replies_type: "Collection".to_string(),
+                    total_items: tallies.get(i).copied().unwrap_or_default(),
+                }),
+            })
+            .collect();
+
+        let closed = tpoll
+            .expires_at
+            .filter(|x| *x <= get_current_epoch())
+            .map(|x| Value::String(convert_epoch_to_iso_8601(x)));
+
+        let (one_of, any_of) = match tpoll.multiple {
+            true => (None, Some(options)),
+            false => (Some(options), None),
+        };
+
+        Ok(Question {
+            note,
+            one_of,
+            any_of,
+            end_time: tpoll.expires_at.map(convert_epoch_to_iso_8601),
+            closed,
+            voters_count: tpoll.voters_count,
+        })
+    }
+}
+
+impl fmt::Display for Question {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
+
+impl fmt::Debug for Question {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let a = serde_json::to_string(self).unwrap();
+        write!(f, "{}", a)
+    }
+}
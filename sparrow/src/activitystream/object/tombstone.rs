@@ -2,14 +2,12 @@
 //!
 //! <https://www.w3.org/TR/activitystreams-vocabulary/#dfn-tombstone>
 
-use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
 use std::fmt;
 
+use crate::activitystream::default_context;
 use crate::activitystream::object::ObjectType;
-use crate::activitystream::Execute;
 
 /*
 {
@@ -22,9 +20,37 @@ use crate::activitystream::Execute;
 #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Tombstone {
-    id: String,
-    tombstone_type: String,
-    atom_uri: String,
+    #[serde(rename = "@context", skip_serializing_if = "Option::is_none")]
+    pub context: Option<Value>,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub tombstone_type: ObjectType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub atom_uri: Option<String>,
+    /// Type of the deleted object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub former_type: Option<String>,
+    /// When the object was deleted. ISO 8601 Datetime.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<String>,
+}
+
+impl Tombstone {
+    /// Tombstone of deleted object.
+    pub fn new(
+        id: String,
+        former_type: Option<String>,
+        deleted: Option<String>,
+    ) -> Self {
+        Tombstone {
+            context: Some(default_context()),
+            id: id.to_owned(),
+            tombstone_type: ObjectType::Tombstone,
+            atom_uri: Some(id),
+            former_type,
+            deleted,
+        }
+    }
 }
 
 impl fmt::Display for Tombstone {
@@ -40,12 +66,3 @@ impl fmt::Debug for Tombstone {
         write!(f, "{}", a)
     }
 }
-
-impl Execute for Tombstone {
-    async fn execute(&self, actor: String) -> Result<()> {
-        tracing::debug!("##########################");
-        Ok(())
-    }
-}
-
-todo!();
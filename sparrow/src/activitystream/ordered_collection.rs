@@ -43,6 +43,8 @@ pub struct OrderedCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub ordered_items: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub current: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub first: Option<String>,
@@ -0,0 +1,1178 @@
+//! HTTP Signatures.
+//!
+//! Two schemes are in use on the fediverse.
+//!
+//! draft-cavage: signing string is built from the `headers` parameter of the
+//! Signature header, so servers signing different header sets (Mastodon,
+//! GoToSocial, Pleroma, Misskey) are verified. `hs2019`, `(created)` and
+//! `(expires)` are supported.
+//!
+//! RFC 9421: `Signature-Input` and `Signature` with `Content-Digest` of
+//! RFC 9530. Requests are signed and verified with `rsa-v1_5-sha256`.
+//!
+//! Clock skew and the body digest are enforced on both.
+//!
+//! <https://datatracker.ietf.org/doc/html/draft-cavage-http-signatures-12>
+//! <https://www.rfc-editor.org/rfc/rfc9421>
+//! <https://docs.joinmastodon.org/spec/security/#http-verify>
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::DateTime;
+use regex::Regex;
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::sha2::{Digest, Sha256, Sha512};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use spin_sdk::http::Request;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use url::Url;
+
+/// Signed requests older than this are rejected. 12 hours.
+pub const MAX_SIGNATURE_AGE: i64 = 43200;
+
+/// Allowed clock difference to the future. 1 hour.
+pub const CLOCK_SKEW_MARGIN: i64 = 3600;
+
+/// Errors of HTTP signature verification.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum SignatureError {
+    /// Signature header or one of its parameters is malformed.
+    #[error("Malformed signature: {0}")]
+    Malformed(String),
+    /// Header listed in `headers` is absent in the request.
+    #[error("Signed header is missing: {0}")]
+    MissingHeader(String),
+    /// Signature algorithm is not supported.
+    #[error("Unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    /// Date or `(created)` is out of the allowed window.
+    #[error("Clock skew: {0}")]
+    ClockSkew(String),
+    /// `(expires)` has passed.
+    #[error("Signature expired")]
+    Expired,
+    /// Digest header does not match the body.
+    #[error("Digest mismatch: {0}")]
+    DigestMismatch(String),
+    /// Derived component of RFC 9421 is not supported.
+    #[error("Unsupported component: {0}")]
+    UnsupportedComponent(String),
+    /// Public key is not usable.
+    #[error("Bad public key: {0}")]
+    BadKey(String),
+    /// Signature does not match the signing string.
+    #[error("Signature does not match")]
+    BadSignature,
+}
+
+/// Parsed Signature header.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SignatureHeader {
+    /// Url of the signing key, e.g. `https://example.com/users/a#main-key`.
+    pub key_id: String,
+    /// `rsa-sha256`, `hs2019` or none.
+    pub algorithm: Option<String>,
+    /// Lowercased names of signed headers in order.
+    pub headers: Vec<String>,
+    /// Decoded signature bytes.
+    pub signature: Vec<u8>,
+    /// `(created)` in Unix time.
+    pub created: Option<i64>,
+    /// `(expires)` in Unix time.
+    pub expires: Option<i64>,
+}
+
+impl SignatureHeader {
+    /// Parse `keyId="..",algorithm="..",headers="..",signature=".."`.
+    pub fn parse(value: &str) -> Result<Self, SignatureError> {
+        let re = Regex::new(r#"([a-zA-Z]+)=(?:"([^"]*)"|([^,\s]*))"#).unwrap();
+        let params: HashMap<String, String> = re
+            .captures_iter(value)
+            .map(|c| {
+                let v = c.get(2).or(c.get(3)).map(|x| x.as_str());
+                (c[1].to_string(), v.unwrap_or_default().to_string())
+            })
+            .collect();
+
+        let key_id = params
+            .get("keyId")
+            .ok_or(SignatureError::Malformed("keyId is missing".to_string()))?
+            .to_owned();
+        let signature = params
+            .get("signature")
+            .ok_or(SignatureError::Malformed(
+                "signature is missing".to_string(),
+            ))
+            .and_then(|x| {
+                general_purpose::STANDARD
+                    .decode(x)
+                    .map_err(|e| SignatureError::Malformed(e.to_string()))
+            })?;
+        // Mastodon assumes Date header if none are provided.
+        let headers = match params.get("headers") {
+            Some(h) => h.split_whitespace().map(|x| x.to_lowercase()).collect(),
+            None => vec!["date".to_string()],
+        };
+
+        Ok(SignatureHeader {
+            key_id,
+            algorithm: params.get("algorithm").map(|x| x.to_lowercase()),
+            headers,
+            signature,
+            created: timestamp(&params, "created")?,
+            expires: timestamp(&params, "expires")?,
+        })
+    }
+}
+
+/// Signature scheme of a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// draft-cavage-http-signatures
+    Cavage,
+    /// RFC 9421 HTTP Message Signatures
+    Rfc9421,
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scheme::Cavage => write!(f, "cavage"),
+            Scheme::Rfc9421 => write!(f, "rfc9421"),
+        }
+    }
+}
+
+impl FromStr for Scheme {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cavage" => Ok(Scheme::Cavage),
+            "rfc9421" => Ok(Scheme::Rfc9421),
+            _ => Err(SignatureError::Malformed(format!("unknown scheme {s}"))),
+        }
+    }
+}
+
+/// Request parts covered by the signature.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SignedRequest {
+    /// Lowercased method.
+    pub method: String,
+    /// `https` unless told otherwise.
+    pub scheme: String,
+    /// Path and query.
+    pub path: String,
+    /// Lowercased header names. Repeated headers are joined by ", ".
+    pub headers: HashMap<String, String>,
+    /// Raw body. Empty for GET.
+    pub body: Vec<u8>,
+}
+
+impl SignedRequest {
+    /// Request of the method to the path, without headers.
+    pub fn new(method: &str, path: &str) -> Self {
+        SignedRequest {
+            method: method.to_lowercase(),
+            scheme: "https".to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Request to the url with its Host header.
+    pub fn from_url(method: &str, url: &Url) -> Self {
+        let path = match url.query() {
+            Some(q) => format!("{}?{}", url.path(), q),
+            None => url.path().to_string(),
+        };
+        let host = match url.port() {
+            Some(port) => {
+                format!("{}:{}", url.host_str().unwrap_or_default(), port)
+            }
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let mut signed =
+            SignedRequest::new(method, &path).header("host", &host);
+        signed.scheme = url.scheme().to_string();
+        signed
+    }
+
+    /// Add a header. Repeated header is appended to the previous value.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        let name = name.to_lowercase();
+        let value = value.trim().to_string();
+        let joined = match self.headers.remove(&name) {
+            Some(v) => format!("{}, {}", v, value),
+            None => value,
+        };
+        self.headers.insert(name, joined);
+        self
+    }
+
+    /// Set the body.
+    pub fn body(mut self, body: &[u8]) -> Self {
+        self.body = body.to_vec();
+        self
+    }
+
+    /// Signed parts of the Spin request.
+    /// `x-forwarded-host` and `x-forwarded-proto` stand for Host and the
+    /// scheme behind a proxy.
+    pub fn from_request(req: &Request) -> Self {
+        let uri = url::Url::parse(req.uri()).ok();
+        let path = match uri.as_ref().and_then(|x| x.query()) {
+            Some(q) => format!("{}?{}", uri.as_ref().unwrap().path(), q),
+            None => uri.as_ref().map(|x| x.path()).unwrap_or("/").to_string(),
+        };
+        let mut signed = SignedRequest::new(&req.method().to_string(), &path);
+        for (name, value) in req.headers() {
+            if let Some(v) = value.as_str() {
+                signed = signed.header(name, v);
+            }
+        }
+        if !signed.headers.contains_key("host") {
+            if let Some(h) = signed.headers.get("x-forwarded-host").cloned() {
+                signed = signed.header("host", &h);
+            }
+        }
+        if let Some(proto) = signed.headers.get("x-forwarded-proto") {
+            signed.scheme = proto.to_lowercase();
+        }
+        signed.body(req.body())
+    }
+}
+
+/// Signing string of the request built from the declared header list.
+pub fn signing_string(
+    sig: &SignatureHeader,
+    req: &SignedRequest,
+) -> Result<String, SignatureError> {
+    let mut lines = Vec::new();
+    for name in sig.headers.iter() {
+        let value = match name.as_str() {
+            "(request-target)" => format!("{} {}", req.method, req.path),
+            "(created)" => sig
+                .created
+                .ok_or(SignatureError::Malformed("created is missing".into()))?
+                .to_string(),
+            "(expires)" => sig
+                .expires
+                .ok_or(SignatureError::Malformed("expires is missing".into()))?
+                .to_string(),
+            h => req
+                .headers
+                .get(h)
+                .ok_or(SignatureError::MissingHeader(h.to_string()))?
+                .to_owned(),
+        };
+        lines.push(format!("{}: {}", name, value));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Verify the request's signature with the public key pem at `now`.
+pub fn verify(
+    sig: &SignatureHeader,
+    req: &SignedRequest,
+    public_key_pem: &str,
+    now: i64,
+) -> Result<(), SignatureError> {
+    // hs2019 derives the algorithm from the key. Actor keys are RSA.
+    let algorithm = sig.algorithm.to_owned().unwrap_or("hs2019".to_string());
+    if !["hs2019", "rsa-sha256"].contains(&algorithm.as_str()) {
+        return Err(SignatureError::UnsupportedAlgorithm(algorithm));
+    }
+    // (created) is not defined for rsa-, hmac- and ecdsa- algorithms.
+    if algorithm != "hs2019" && sig.headers.iter().any(|x| x == "(created)") {
+        return Err(SignatureError::Malformed(format!(
+            "(created) is not allowed with {}",
+            algorithm
+        )));
+    }
+
+    check_time(sig, req, now)?;
+    check_digest(sig, req)?;
+
+    let signing_string = signing_string(sig, req)?;
+    verify_rsa(public_key_pem, signing_string.as_bytes(), &sig.signature)
+}
+
+/// Signed Date or (created) must be recent. (expires) must not have passed.
+fn check_time(
+    sig: &SignatureHeader,
+    req: &SignedRequest,
+    now: i64,
+) -> Result<(), SignatureError> {
+    let signed = |x: &str| sig.headers.iter().any(|h| h == x);
+
+    let signed_at = if signed("(created)") {
+        sig.created
+    } else if signed("date") {
+        let date = req
+            .headers
+            .get("date")
+            .ok_or(SignatureError::MissingHeader("date".to_string()))?;
+        let parsed = DateTime::parse_from_rfc2822(date)
+            .map_err(|e| SignatureError::Malformed(e.to_string()))?;
+        Some(parsed.timestamp())
+    } else {
+        return Err(SignatureError::Malformed(
+            "neither date nor (created) is signed".to_string(),
+        ));
+    };
+
+    if let Some(t) = signed_at {
+        check_window(t, now)?;
+    }
+    if signed("(expires)") {
+        check_expires(sig.expires, now)?;
+    }
+    Ok(())
+}
+
+/// Signed time must be within the allowed window.
+fn check_window(t: i64, now: i64) -> Result<(), SignatureError> {
+    if t < now - MAX_SIGNATURE_AGE {
+        return Err(SignatureError::ClockSkew(format!("{} is too old", t)));
+    }
+    if t > now + CLOCK_SKEW_MARGIN {
+        return Err(SignatureError::ClockSkew(format!("{} is ahead", t)));
+    }
+    Ok(())
+}
+
+fn check_expires(expires: Option<i64>, now: i64) -> Result<(), SignatureError> {
+    match expires {
+        Some(e) if e + CLOCK_SKEW_MARGIN < now => Err(SignatureError::Expired),
+        _ => Ok(()),
+    }
+}
+
+/// Verify RSASSA-PKCS1-v1_5 SHA-256 signature of the message.
+fn verify_rsa(
+    public_key_pem: &str,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), SignatureError> {
+    let public_key = RsaPublicKey::from_public_key_pem(public_key_pem)
+        .map_err(|e| SignatureError::BadKey(e.to_string()))?;
+    let verifying_key: VerifyingKey<Sha256> = VerifyingKey::new(public_key);
+    let signature = Signature::try_from(signature)
+        .map_err(|_| SignatureError::BadSignature)?;
+    verifying_key
+        .verify(message, &signature)
+        .map_err(|_| SignatureError::BadSignature)
+}
+
+/// Body with a request must be covered by a signed Digest that matches it.
+fn check_digest(
+    sig: &SignatureHeader,
+    req: &SignedRequest,
+) -> Result<(), SignatureError> {
+    let digest = match req.headers.get("digest") {
+        Some(d) => d,
+        None if req.method == "post" || !req.body.is_empty() => {
+            return Err(SignatureError::MissingHeader("digest".to_string()))
+        }
+        None => return Ok(()),
+    };
+    if req.method == "post" && !sig.headers.iter().any(|x| x == "digest") {
+        return Err(SignatureError::Malformed(
+            "digest is not signed".to_string(),
+        ));
+    }
+
+    // e.g. "SHA-256=base64, SHA-512=base64"
+    let mut checked = false;
+    for d in digest.split(',') {
+        let (alg, value) = match d.trim().split_once('=') {
+            Some(x) => x,
+            None => continue,
+        };
+        let computed = match alg.to_lowercase().as_str() {
+            "sha-256" => {
+                general_purpose::STANDARD.encode(Sha256::digest(&req.body))
+            }
+            "sha-512" => {
+                general_purpose::STANDARD.encode(Sha512::digest(&req.body))
+            }
+            _ => continue,
+        };
+        if computed != value {
+            return Err(SignatureError::DigestMismatch(alg.to_string()));
+        }
+        checked = true;
+    }
+    if !checked {
+        return Err(SignatureError::DigestMismatch(
+            "no supported algorithm".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Label of signatures this server makes.
+pub const SIGNATURE_LABEL: &str = "sig1";
+
+/// Algorithm of signatures this server makes.
+pub const MESSAGE_SIGNATURE_ALG: &str = "rsa-v1_5-sha256";
+
+/// Signature of RFC 9421 picked from `Signature-Input` and `Signature`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MessageSignature {
+    /// Dictionary key of the signature, e.g. `sig1`.
+    pub label: String,
+    /// Covered component identifiers in order, e.g. `@method`.
+    pub components: Vec<String>,
+    /// Serialized inner list and parameters. The `@signature-params` value.
+    pub params: String,
+    /// `keyid` parameter. Url of the signing key.
+    pub key_id: Option<String>,
+    /// `alg` parameter. `rsa-v1_5-sha256` when present.
+    pub alg: Option<String>,
+    /// `created` parameter in Unix time.
+    pub created: Option<i64>,
+    /// `expires` parameter in Unix time.
+    pub expires: Option<i64>,
+    /// Decoded signature bytes.
+    pub signature: Vec<u8>,
+}
+
+impl MessageSignature {
+    /// Parse the first signature of `Signature-Input` which has its value in
+    /// `Signature`.
+    pub fn parse(
+        signature_input: &str,
+        signature: &str,
+    ) -> Result<Self, SignatureError> {
+        let signatures = dictionary(signature);
+        let (label, input, value) = dictionary(signature_input)
+            .into_iter()
+            .find_map(|(label, input)| {
+                signatures
+                    .iter()
+                    .find(|(l, _)| *l == label)
+                    .map(|(_, v)| (label, input, v.to_owned()))
+            })
+            .ok_or(SignatureError::Malformed(
+                "no signature matches Signature-Input".to_string(),
+            ))?;
+
+        let close = match input.strip_prefix('(').and_then(|x| x.find(')')) {
+            Some(i) => i + 1,
+            None => {
+                return Err(SignatureError::Malformed(format!(
+                    "{} is not an inner list",
+                    label
+                )))
+            }
+        };
+        let mut components = Vec::new();
+        for item in input[1..close].split_whitespace() {
+            let name = item
+                .strip_prefix('"')
+                .and_then(|x| x.strip_suffix('"'))
+                .ok_or(SignatureError::UnsupportedComponent(
+                    item.to_string(),
+                ))?;
+            components.push(name.to_string());
+        }
+
+        let mut params: HashMap<String, String> = HashMap::new();
+        for param in split_outside_quotes(&input[close + 1..], ';') {
+            if let Some((k, v)) = param.split_once('=') {
+                params.insert(k.trim().to_string(), v.trim().to_string());
+            }
+        }
+        let string_param =
+            |k: &str| params.get(k).map(|v| v.trim_matches('"').to_string());
+
+        let signature = value
+            .strip_prefix(':')
+            .and_then(|x| x.strip_suffix(':'))
+            .ok_or(SignatureError::Malformed(
+                "signature is not a byte sequence".to_string(),
+            ))
+            .and_then(|x| {
+                general_purpose::STANDARD
+                    .decode(x)
+                    .map_err(|e| SignatureError::Malformed(e.to_string()))
+            })?;
+
+        Ok(MessageSignature {
+            components,
+            key_id: string_param("keyid"),
+            alg: string_param("alg"),
+            created: timestamp(&params, "created")?,
+            expires: timestamp(&params, "expires")?,
+            params: input.to_string(),
+            label,
+            signature,
+        })
+    }
+}
+
+/// Signature base of RFC 9421 section 2.5.
+pub fn signature_base(
+    sig: &MessageSignature,
+    req: &SignedRequest,
+) -> Result<String, SignatureError> {
+    let host = || {
+        req.headers
+            .get("host")
+            .map(|x| x.to_lowercase())
+            .ok_or(SignatureError::MissingHeader("host".to_string()))
+    };
+    let (path, query) = match req.path.split_once('?') {
+        Some((p, q)) => (p, Some(q)),
+        None => (req.path.as_str(), None),
+    };
+
+    let mut lines = Vec::new();
+    for name in sig.components.iter() {
+        let value = match name.as_str() {
+            "@method" => req.method.to_uppercase(),
+            "@target-uri" => {
+                format!("{}://{}{}", req.scheme, host()?, req.path)
+            }
+            "@authority" => host()?,
+            "@scheme" => req.scheme.to_owned(),
+            "@request-target" => req.path.to_owned(),
+            "@path" => path.to_string(),
+            "@query" => format!("?{}", query.unwrap_or_default()),
+            c if c.starts_with('@') => {
+                return Err(SignatureError::UnsupportedComponent(c.to_string()))
+            }
+            h => req
+                .headers
+                .get(h)
+                .ok_or(SignatureError::MissingHeader(h.to_string()))?
+                .to_owned(),
+        };
+        lines.push(format!("\"{}\": {}", name, value));
+    }
+    lines.push(format!("\"@signature-params\": {}", sig.params));
+    Ok(lines.join("\n"))
+}
+
+/// Verify the RFC 9421 signature with the public key pem at `now`.
+pub fn verify_message(
+    sig: &MessageSignature,
+    req: &SignedRequest,
+    public_key_pem: &str,
+    now: i64,
+) -> Result<(), SignatureError> {
+    // Without alg, the algorithm comes from the key. Actor keys are RSA.
+    if let Some(alg) = sig.alg.as_ref() {
+        if alg != MESSAGE_SIGNATURE_ALG {
+            return Err(SignatureError::UnsupportedAlgorithm(alg.to_owned()));
+        }
+    }
+
+    let covered = |x: &str| sig.components.iter().any(|c| c == x);
+    match sig.created {
+        Some(t) => check_window(t, now)?,
+        None if covered("date") => {
+            let date = req
+                .headers
+                .get("date")
+                .ok_or(SignatureError::MissingHeader("date".to_string()))?;
+            let parsed = DateTime::parse_from_rfc2822(date)
+                .map_err(|e| SignatureError::Malformed(e.to_string()))?;
+            check_window(parsed.timestamp(), now)?;
+        }
+        None => {
+            return Err(SignatureError::Malformed(
+                "neither created nor date is signed".to_string(),
+            ))
+        }
+    }
+    check_expires(sig.expires, now)?;
+
+    if req.method == "post" || !req.body.is_empty() {
+        if !covered("content-digest") {
+            return Err(SignatureError::Malformed(
+                "content-digest is not signed".to_string(),
+            ));
+        }
+        check_content_digest(req)?;
+    }
+
+    let base = signature_base(sig, req)?;
+    verify_rsa(public_key_pem, base.as_bytes(), &sig.signature)
+}
+
+/// Sign the request with RFC 9421.
+/// Returns `Signature-Input` and `Signature` header values.
+pub fn sign_message(
+    req: &SignedRequest,
+    components: &[&str],
+    key_id: &str,
+    private_key_pem: &str,
+    created: i64,
+) -> Result<(String, String), SignatureError> {
+    let inner_list = components
+        .iter()
+        .map(|x| format!("\"{}\"", x))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let sig = MessageSignature {
+        label: SIGNATURE_LABEL.to_string(),
+        components: components.iter().map(|x| x.to_string()).collect(),
+        params: format!(
+            r#"({});created={};keyid="{}";alg="{}""#,
+            inner_list, created, key_id, MESSAGE_SIGNATURE_ALG
+        ),
+        ..Default::default()
+    };
+    let base = signature_base(&sig, req)?;
+
+    let private_key = RsaPrivateKey::from_pkcs8_pem(private_key_pem)
+        .map_err(|e| SignatureError::BadKey(e.to_string()))?;
+    let signing_key: SigningKey<Sha256> = SigningKey::new(private_key);
+    let signature = signing_key.sign(base.as_bytes());
+    let encoded = general_purpose::STANDARD.encode(signature.to_bytes());
+
+    Ok((
+        format!("{}={}", sig.label, sig.params),
+        format!("{}=:{}:", sig.label, encoded),
+    ))
+}
+
+/// `Content-Digest` value of the body. RFC 9530.
+pub fn content_digest(body: &[u8]) -> String {
+    format!(
+        "sha-256=:{}:",
+        general_purpose::STANDARD.encode(Sha256::digest(body))
+    )
+}
+
+/// Content-Digest must match the body.
+fn check_content_digest(req: &SignedRequest) -> Result<(), SignatureError> {
+    let digest = req
+        .headers
+        .get("content-digest")
+        .ok_or(SignatureError::MissingHeader("content-digest".to_string()))?;
+
+    // e.g. "sha-256=:base64:, sha-512=:base64:"
+    let mut checked = false;
+    for (alg, value) in dictionary(digest) {
+        let computed = match alg.as_str() {
+            "sha-256" => {
+                general_purpose::STANDARD.encode(Sha256::digest(&req.body))
+            }
+            "sha-512" => {
+                general_purpose::STANDARD.encode(Sha512::digest(&req.body))
+            }
+            _ => continue,
+        };
+        if value.trim_matches(':') != computed {
+            return Err(SignatureError::DigestMismatch(alg));
+        }
+        checked = true;
+    }
+    if !checked {
+        return Err(SignatureError::DigestMismatch(
+            "no supported algorithm".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Signature of either scheme found in the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestSignature {
+    /// `Signature` header of draft-cavage.
+    Cavage(SignatureHeader),
+    /// `Signature-Input` and `Signature` of RFC 9421.
+    Rfc9421(MessageSignature),
+}
+
+impl RequestSignature {
+    /// RFC 9421 when `Signature-Input` is present, draft-cavage otherwise.
+    pub fn parse(req: &SignedRequest) -> Result<Self, SignatureError> {
+        let signature = req
+            .headers
+            .get("signature")
+            .ok_or(SignatureError::MissingHeader("signature".to_string()))?;
+        match req.headers.get("signature-input") {
+            Some(input) => Ok(RequestSignature::Rfc9421(
+                MessageSignature::parse(input, signature)?,
+            )),
+            None => {
+                Ok(RequestSignature::Cavage(SignatureHeader::parse(signature)?))
+            }
+        }
+    }
+
+    /// Scheme of the signature.
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            RequestSignature::Cavage(_) => Scheme::Cavage,
+            RequestSignature::Rfc9421(_) => Scheme::Rfc9421,
+        }
+    }
+
+    /// Url of the signing key. RFC 9421 signature may not have `keyid`.
+    pub fn key_id(&self) -> Option<&str> {
+        match self {
+            RequestSignature::Cavage(sig) => Some(sig.key_id.as_str()),
+            RequestSignature::Rfc9421(sig) => sig.key_id.as_deref(),
+        }
+    }
+
+    /// Verify the request with the public key pem at `now`.
+    pub fn verify(
+        &self,
+        req: &SignedRequest,
+        public_key_pem: &str,
+        now: i64,
+    ) -> Result<(), SignatureError> {
+        match self {
+            RequestSignature::Cavage(sig) => {
+                verify(sig, req, public_key_pem, now)
+            }
+            RequestSignature::Rfc9421(sig) => {
+                verify_message(sig, req, public_key_pem, now)
+            }
+        }
+    }
+}
+
+/// Members of a structured field dictionary as raw `(key, value)` strings.
+fn dictionary(value: &str) -> Vec<(String, String)> {
+    split_outside_quotes(value, ',')
+        .into_iter()
+        .filter_map(|member| {
+            member
+                .split_once('=')
+                .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Split at the delimiter outside quoted strings and inner lists.
+fn split_outside_quotes(value: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut quoted, mut depth, mut start) = (false, 0, 0);
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            c if c == delimiter && !quoted && depth == 0 => {
+                parts.push(value[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(value[start..].trim());
+    parts.into_iter().filter(|x| !x.is_empty()).collect()
+}
+
+fn timestamp(
+    params: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<i64>, SignatureError> {
+    match params.get(key) {
+        Some(v) => v.parse::<f64>().map(|x| Some(x as i64)).map_err(|_| {
+            SignatureError::Malformed(format!("{} is invalid", key))
+        }),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// Requests signed the way Mastodon, GoToSocial, Pleroma and Misskey do.
+    const FIXTURES: &str =
+        include_str!("../tests/fixtures/http_signature.json");
+
+    fn fixtures() -> Value {
+        serde_json::from_str(FIXTURES).unwrap()
+    }
+
+    fn now() -> i64 {
+        fixtures()["now"].as_i64().unwrap()
+    }
+
+    fn public_key() -> String {
+        fixtures()["public_key"].as_str().unwrap().to_string()
+    }
+
+    fn request(fixtures: &Value, name: &str) -> SignedRequest {
+        let f = fixtures["requests"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|x| x["name"] == name)
+            .unwrap()
+            .to_owned();
+        let mut req = SignedRequest::new(
+            f["method"].as_str().unwrap(),
+            f["path"].as_str().unwrap(),
+        )
+        .body(f["body"].as_str().unwrap().as_bytes());
+        for (k, v) in f["headers"].as_object().unwrap() {
+            req = req.header(k, v.as_str().unwrap());
+        }
+        req
+    }
+
+    fn fixture(name: &str) -> (SignatureHeader, SignedRequest) {
+        let req = request(&fixtures(), name);
+        let sig = SignatureHeader::parse(&req.headers["signature"]).unwrap();
+        (sig, req)
+    }
+
+    /// RFC 9421 requests, signed the way Mastodon and GoToSocial do.
+    const MESSAGE_FIXTURES: &str =
+        include_str!("../tests/fixtures/http_message_signature.json");
+
+    fn message_fixtures() -> Value {
+        serde_json::from_str(MESSAGE_FIXTURES).unwrap()
+    }
+
+    fn message_key(name: &str) -> String {
+        message_fixtures()[name].as_str().unwrap().to_string()
+    }
+
+    fn message_fixture(name: &str) -> (MessageSignature, SignedRequest) {
+        let req = request(&message_fixtures(), name);
+        let sig = MessageSignature::parse(
+            &req.headers["signature-input"],
+            &req.headers["signature"],
+        )
+        .unwrap();
+        (sig, req)
+    }
+
+    #[test]
+    fn parse_signature_header() {
+        let (sig, _) = fixture("created");
+        assert_eq!(sig.key_id, "https://misskey.example/users/9d#main-key");
+        assert_eq!(sig.algorithm.as_deref(), Some("hs2019"));
+        assert_eq!(
+            sig.headers,
+            vec![
+                "(request-target)",
+                "(created)",
+                "(expires)",
+                "host",
+                "digest"
+            ]
+        );
+        assert_eq!(sig.created, Some(1724147970));
+        assert_eq!(sig.expires, Some(1724148300));
+        assert_eq!(sig.signature.len(), 256);
+    }
+
+    #[test]
+    fn headers_default_to_date() {
+        let sig = SignatureHeader::parse(r#"keyId="k",signature="AAAA""#);
+        assert_eq!(sig.unwrap().headers, vec!["date"]);
+    }
+
+    #[test]
+    fn signing_string_follows_headers_order() {
+        let (sig, req) = fixture("pleroma");
+        let s = signing_string(&sig, &req).unwrap();
+        let names: Vec<&str> =
+            s.lines().map(|x| x.split(": ").next().unwrap()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "(request-target)",
+                "content-length",
+                "date",
+                "digest",
+                "host"
+            ]
+        );
+        assert!(s.starts_with("(request-target): post /inbox\n"));
+    }
+
+    #[test]
+    fn verify_fixtures() {
+        for name in ["mastodon", "gotosocial", "pleroma", "created", "get"] {
+            let (sig, req) = fixture(name);
+            assert_eq!(
+                verify(&sig, &req, &public_key(), now()),
+                Ok(()),
+                "{}",
+                name
+            );
+        }
+    }
+
+    /// Request and signature published with draft-cavage, made by a signer
+    /// other than ours.
+    const DRAFT_FIXTURES: &str =
+        include_str!("../tests/fixtures/http_signature_draft_cavage.json");
+
+    #[test]
+    fn verify_draft_test_vector() {
+        let f: Value = serde_json::from_str(DRAFT_FIXTURES).unwrap();
+        let req = request(&f, "all_headers");
+        let sig = SignatureHeader::parse(&req.headers["signature"]).unwrap();
+        assert_eq!(sig.key_id, "Test");
+        assert_eq!(
+            verify(
+                &sig,
+                &req,
+                f["public_key"].as_str().unwrap(),
+                f["now"].as_i64().unwrap()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn request_target_keeps_query() {
+        let (sig, req) = fixture("get");
+        let s = signing_string(&sig, &req).unwrap();
+        assert!(
+            s.starts_with("(request-target): get /users/self/outbox?page=true")
+        );
+    }
+
+    #[test]
+    fn wrong_key_is_rejected() {
+        let (sig, req) = fixture("mastodon");
+        let other =
+            fixtures()["other_public_key"].as_str().unwrap().to_string();
+        assert_eq!(
+            verify(&sig, &req, &other, now()),
+            Err(SignatureError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn tampered_body_is_rejected() {
+        let (sig, req) = fixture("gotosocial");
+        let req = req.body(br#"{"type":"Delete"}"#);
+        assert_eq!(
+            verify(&sig, &req, &public_key(), now()),
+            Err(SignatureError::DigestMismatch("SHA-256".to_string()))
+        );
+    }
+
+    #[test]
+    fn tampered_signed_header_is_rejected() {
+        let (sig, mut req) = fixture("mastodon");
+        req.headers
+            .insert("host".to_string(), "evil.example".to_string());
+        assert_eq!(
+            verify(&sig, &req, &public_key(), now()),
+            Err(SignatureError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn missing_signed_header_is_rejected() {
+        let (sig, mut req) = fixture("pleroma");
+        req.headers.remove("content-length");
+        assert_eq!(
+            verify(&sig, &req, &public_key(), now()),
+            Err(SignatureError::MissingHeader("content-length".to_string()))
+        );
+    }
+
+    #[test]
+    fn unsigned_digest_is_rejected() {
+        let (mut sig, req) = fixture("gotosocial");
+        sig.headers.retain(|x| x != "digest");
+        assert!(matches!(
+            verify(&sig, &req, &public_key(), now()),
+            Err(SignatureError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn clock_skew_is_enforced() {
+        let (sig, req) = fixture("mastodon");
+        let old = now() + MAX_SIGNATURE_AGE + 1;
+        assert!(matches!(
+            verify(&sig, &req, &public_key(), old),
+            Err(SignatureError::ClockSkew(_))
+        ));
+        let early = now() - CLOCK_SKEW_MARGIN - 1;
+        assert!(matches!(
+            verify(&sig, &req, &public_key(), early),
+            Err(SignatureError::ClockSkew(_))
+        ));
+    }
+
+    #[test]
+    fn expired_signature_is_rejected() {
+        let (sig, req) = fixture("created");
+        let later = sig.expires.unwrap() + CLOCK_SKEW_MARGIN + 1;
+        assert_eq!(
+            verify(&sig, &req, &public_key(), later),
+            Err(SignatureError::Expired)
+        );
+    }
+
+    #[test]
+    fn created_needs_hs2019() {
+        let (mut sig, req) = fixture("created");
+        sig.algorithm = Some("rsa-sha256".to_string());
+        assert!(matches!(
+            verify(&sig, &req, &public_key(), now()),
+            Err(SignatureError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_algorithm_is_rejected() {
+        let (mut sig, req) = fixture("mastodon");
+        sig.algorithm = Some("hmac-sha256".to_string());
+        assert_eq!(
+            verify(&sig, &req, &public_key(), now()),
+            Err(SignatureError::UnsupportedAlgorithm(
+                "hmac-sha256".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_message_signature() {
+        let (sig, _) = message_fixture("derived");
+        assert_eq!(sig.label, "sig1");
+        assert_eq!(
+            sig.components,
+            vec![
+                "@method",
+                "@authority",
+                "@path",
+                "content-type",
+                "content-digest"
+            ]
+        );
+        assert_eq!(
+            sig.key_id.as_deref(),
+            Some("https://gts.example/users/bob/main-key")
+        );
+        assert_eq!(sig.alg.as_deref(), Some("rsa-v1_5-sha256"));
+        assert_eq!(sig.created, Some(1724147970));
+        assert_eq!(sig.expires, Some(1724148300));
+        assert_eq!(sig.signature.len(), 256);
+    }
+
+    #[test]
+    fn signature_base_ends_with_params() {
+        let (sig, req) = message_fixture("get");
+        let base = signature_base(&sig, &req).unwrap();
+        assert_eq!(
+            base.lines().take(2).collect::<Vec<&str>>(),
+            vec![
+                r#""@method": GET"#,
+                r#""@target-uri": https://dev.prefer.social/users/self/outbox?page=true"#,
+            ]
+        );
+        assert!(
+            base.ends_with(&format!("\"@signature-params\": {}", sig.params))
+        );
+    }
+
+    #[test]
+    fn verify_message_fixtures() {
+        let now = message_fixtures()["now"].as_i64().unwrap();
+        for name in ["mastodon", "derived", "get"] {
+            let (sig, req) = message_fixture(name);
+            let key = message_key("public_key");
+            assert_eq!(
+                verify_message(&sig, &req, &key, now),
+                Ok(()),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn message_with_wrong_key_is_rejected() {
+        let now = message_fixtures()["now"].as_i64().unwrap();
+        let (sig, req) = message_fixture("mastodon");
+        assert_eq!(
+            verify_message(&sig, &req, &message_key("other_public_key"), now),
+            Err(SignatureError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn message_with_tampered_body_is_rejected() {
+        let now = message_fixtures()["now"].as_i64().unwrap();
+        let (sig, req) = message_fixture("derived");
+        let req = req.body(br#"{"type":"Delete"}"#);
+        assert_eq!(
+            verify_message(&sig, &req, &message_key("public_key"), now),
+            Err(SignatureError::DigestMismatch("sha-512".to_string()))
+        );
+    }
+
+    #[test]
+    fn message_without_content_digest_is_rejected() {
+        let now = message_fixtures()["now"].as_i64().unwrap();
+        let (mut sig, req) = message_fixture("mastodon");
+        sig.components.retain(|x| x != "content-digest");
+        assert!(matches!(
+            verify_message(&sig, &req, &message_key("public_key"), now),
+            Err(SignatureError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn sign_message_round_trip() {
+        let now = message_fixtures()["now"].as_i64().unwrap();
+        let url = Url::parse("https://mastodon.example/inbox").unwrap();
+        let body = br#"{"type":"Follow"}"#;
+        let req = SignedRequest::from_url("POST", &url)
+            .header("content-digest", &content_digest(body))
+            .body(body);
+        let (input, signature) = sign_message(
+            &req,
+            &["@method", "@target-uri", "content-digest"],
+            "https://dev.prefer.social/self#main-key",
+            &message_key("private_key"),
+            now,
+        )
+        .unwrap();
+        assert!(input.starts_with(
+            r#"sig1=("@method" "@target-uri" "content-digest");created="#
+        ));
+
+        let req = req
+            .header("signature-input", &input)
+            .header("signature", &signature);
+        let parsed = RequestSignature::parse(&req).unwrap();
+        assert_eq!(parsed.scheme(), Scheme::Rfc9421);
+        assert_eq!(
+            parsed.key_id(),
+            Some("https://dev.prefer.social/self#main-key")
+        );
+        assert_eq!(
+            parsed.verify(&req, &message_key("public_key"), now),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn cavage_is_picked_without_signature_input() {
+        let (_, req) = fixture("mastodon");
+        let parsed = RequestSignature::parse(&req).unwrap();
+        assert_eq!(parsed.scheme(), Scheme::Cavage);
+        assert_eq!(parsed.verify(&req, &public_key(), now()), Ok(()));
+    }
+}
@@ -0,0 +1,273 @@
+//! Object integrity proofs. FEP-8b32.
+//!
+//! Activities are signed with `eddsa-jcs-2022` Data Integrity proofs by the
+//! actor's Ed25519 key, so they can be verified wherever they are relayed
+//! from. The proof covers the JCS canonical form of the document and of the
+//! proof options.
+//!
+//! <https://codeberg.org/fediverse/fep/src/branch/main/fep/8b32/fep-8b32.md>
+//! <https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022>
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use rsa::sha2::{Digest, Sha256};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+/// Context defining `DataIntegrityProof`.
+pub const DATA_INTEGRITY_CONTEXT: &str =
+    "https://w3id.org/security/data-integrity/v1";
+
+/// The only supported cryptosuite.
+pub const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+
+/// Errors of integrity proof creation and verification.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum ProofError {
+    /// Document has no proof.
+    #[error("Proof is missing")]
+    Missing,
+    /// Proof or document is malformed.
+    #[error("Malformed proof: {0}")]
+    Malformed(String),
+    /// Proof type or cryptosuite is not supported.
+    #[error("Unsupported cryptosuite: {0}")]
+    UnsupportedCryptosuite(String),
+    /// Proof @context is not the beginning of the document's @context.
+    #[error("Proof context does not match the document")]
+    ContextMismatch,
+    /// Proof value does not match.
+    #[error("Proof does not match")]
+    BadSignature,
+}
+
+/// Document with a proof by the key. Existing proof is replaced.
+pub fn sign(
+    document: &Value,
+    verification_method: &str,
+    key: &SigningKey,
+    created: DateTime<Utc>,
+) -> Result<Value, ProofError> {
+    let mut unsecured = document.to_owned();
+    let object = unsecured
+        .as_object_mut()
+        .ok_or(ProofError::Malformed("document is not an object".into()))?;
+    object.remove("proof");
+
+    let mut proof = json!({
+        "type": "DataIntegrityProof",
+        "cryptosuite": CRYPTOSUITE,
+        "verificationMethod": verification_method,
+        "proofPurpose": "assertionMethod",
+        "created": created.to_rfc3339_opts(SecondsFormat::Secs, true),
+    });
+    if let Some(context) = object.get("@context") {
+        proof["@context"] = context.to_owned();
+    }
+
+    let hash = hash_data(&proof, &unsecured)?;
+    let signature = key.sign(&hash);
+    proof["proofValue"] = Value::String(format!(
+        "z{}",
+        bs58::encode(signature.to_bytes()).into_string()
+    ));
+
+    let mut secured = unsecured;
+    secured["proof"] = proof;
+    Ok(secured)
+}
+
+/// `verificationMethod` of the document's proof.
+pub fn verification_method(document: &Value) -> Option<String> {
+    proof_of(document)
+        .ok()?
+        .get("verificationMethod")
+        .and_then(|x| x.as_str())
+        .map(|x| x.to_string())
+}
+
+/// Verify the document's proof with the key.
+pub fn verify(document: &Value, key: &VerifyingKey) -> Result<(), ProofError> {
+    let proof = proof_of(document)?;
+    let field = |k: &str| proof.get(k).and_then(|x| x.as_str());
+
+    let cryptosuite = field("cryptosuite").unwrap_or_default();
+    if field("type") != Some("DataIntegrityProof") || cryptosuite != CRYPTOSUITE
+    {
+        return Err(ProofError::UnsupportedCryptosuite(
+            cryptosuite.to_string(),
+        ));
+    }
+    if field("proofPurpose") != Some("assertionMethod") {
+        return Err(ProofError::Malformed(
+            "proofPurpose is not assertionMethod".to_string(),
+        ));
+    }
+    let signature = field("proofValue")
+        .and_then(|x| x.strip_prefix('z'))
+        .and_then(|x| bs58::decode(x).into_vec().ok())
+        .and_then(|x| Signature::from_slice(&x).ok())
+        .ok_or(ProofError::Malformed("proofValue is invalid".to_string()))?;
+
+    let mut options = proof.to_owned();
+    options.as_object_mut().map(|x| x.remove("proofValue"));
+    let mut unsecured = document.to_owned();
+    unsecured.as_object_mut().map(|x| x.remove("proof"));
+
+    // Proof context must be where the document's context starts.
+    if let Some(context) = options.get("@context") {
+        let listed = |v: &Value| match v {
+            Value::Array(a) => a.to_owned(),
+            v => vec![v.to_owned()],
+        };
+        let document_context =
+            listed(unsecured.get("@context").unwrap_or(&Value::Null));
+        if !document_context.starts_with(&listed(context)) {
+            return Err(ProofError::ContextMismatch);
+        }
+    }
+
+    let hash = hash_data(&options, &unsecured)?;
+    key.verify_strict(&hash, &signature)
+        .map_err(|_| ProofError::BadSignature)
+}
+
+/// Proof of eddsa-jcs-2022 among the document's proofs.
+fn proof_of(document: &Value) -> Result<&Value, ProofError> {
+    match document.get("proof") {
+        Some(Value::Array(proofs)) => proofs
+            .iter()
+            .find(|x| x.get("cryptosuite") == Some(&json!(CRYPTOSUITE)))
+            .or(proofs.first())
+            .ok_or(ProofError::Missing),
+        Some(proof) if proof.is_object() => Ok(proof),
+        Some(_) => Err(ProofError::Malformed("proof is not an object".into())),
+        None => Err(ProofError::Missing),
+    }
+}
+
+/// SHA-256 of canonical proof options followed by SHA-256 of canonical
+/// document.
+fn hash_data(options: &Value, document: &Value) -> Result<Vec<u8>, ProofError> {
+    let canonical = |v: &Value| {
+        serde_jcs::to_vec(v).map_err(|e| ProofError::Malformed(e.to_string()))
+    };
+    let mut hash = Sha256::digest(canonical(options)?).to_vec();
+    hash.extend(Sha256::digest(canonical(document)?));
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::{
+        multikey_decode, multikey_encode, secret_multikey_decode,
+    };
+
+    /// Activity signed with the key of the eddsa-jcs-2022 test vectors.
+    const FIXTURES: &str =
+        include_str!("../tests/fixtures/integrity_proof.json");
+
+    fn fixtures() -> Value {
+        serde_json::from_str(FIXTURES).unwrap()
+    }
+
+    fn secret_key() -> SigningKey {
+        let f = fixtures();
+        secret_multikey_decode(f["secret_key_multibase"].as_str().unwrap())
+            .unwrap()
+    }
+
+    fn public_key() -> VerifyingKey {
+        let f = fixtures();
+        multikey_decode(f["public_key_multibase"].as_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn multikey_matches_test_vector() {
+        assert_eq!(
+            multikey_encode(&secret_key().verifying_key()),
+            "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"
+        );
+        assert_eq!(secret_key().verifying_key(), public_key());
+    }
+
+    #[test]
+    fn verify_fixture() {
+        let activity = fixtures()["activity"].to_owned();
+        assert_eq!(
+            verification_method(&activity).as_deref(),
+            Some("https://server.example/users/alice#ed25519-key")
+        );
+        assert_eq!(verify(&activity, &public_key()), Ok(()));
+    }
+
+    #[test]
+    fn key_order_does_not_matter() {
+        let activity = fixtures()["activity"].to_owned();
+        let mut reordered = serde_json::Map::new();
+        for (k, v) in activity.as_object().unwrap().iter().rev() {
+            reordered.insert(k.to_owned(), v.to_owned());
+        }
+        assert_eq!(verify(&Value::Object(reordered), &public_key()), Ok(()));
+    }
+
+    #[test]
+    fn tampered_document_is_rejected() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["object"]["content"] = json!("Goodbye world");
+        assert_eq!(
+            verify(&activity, &public_key()),
+            Err(ProofError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn tampered_proof_is_rejected() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["proof"]["created"] = json!("2024-08-21T10:00:00Z");
+        assert_eq!(
+            verify(&activity, &public_key()),
+            Err(ProofError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn sign_round_trip() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity.as_object_mut().unwrap().remove("proof");
+        let created = DateTime::from_timestamp(1724148000, 0).unwrap();
+        let signed = sign(
+            &activity,
+            "https://server.example/users/alice#ed25519-key",
+            &secret_key(),
+            created,
+        )
+        .unwrap();
+        assert_eq!(signed["proof"]["created"], "2024-08-20T10:00:00Z");
+        assert_eq!(signed["proof"]["@context"], activity["@context"]);
+        assert_eq!(verify(&signed, &public_key()), Ok(()));
+
+        let other = SigningKey::from_bytes(&[7u8; 32]).verifying_key();
+        assert_eq!(verify(&signed, &other), Err(ProofError::BadSignature));
+    }
+
+    #[test]
+    fn unsupported_cryptosuite_is_rejected() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["proof"]["cryptosuite"] = json!("eddsa-rdfc-2022");
+        assert_eq!(
+            verify(&activity, &public_key()),
+            Err(ProofError::UnsupportedCryptosuite(
+                "eddsa-rdfc-2022".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn missing_proof() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity.as_object_mut().unwrap().remove("proof");
+        assert_eq!(verify(&activity, &public_key()), Err(ProofError::Missing));
+    }
+}
@@ -1,111 +1,140 @@
-use anyhow::{bail, Result};
-use base64::{engine, engine::general_purpose, Engine as _};
-use thiserror::Error;
-//use openssl::rsa::Rsa;
-use rsa::{Pkcs1v15Encrypt, RsaPrivateKey, RsaPublicKey};
-//use openssl::sign::Signer;
-//use openssl::hash::MessageDigest;
-//use openssl::pkey::PKey;
-use ed25519_dalek::pkcs8::{DecodePrivateKey, EncodePrivateKey};
-use ed25519_dalek::pkcs8::{DecodePublicKey, EncodePublicKey};
-use ed25519_dalek::Signature;
-use ed25519_dalek::{SecretKey, SigningKey, VerifyingKey};
-use ed25519_dalek::{KEYPAIR_LENGTH, PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH, SIGNATURE_LENGTH};
-use rand::rngs::OsRng;
-
-//use bech32::{self, FromBase32, ToBase32, Variant};
+//! Actor keys
+//!
+//! RSA keys sign HTTP requests. Ed25519 keys sign object integrity proofs and
+//! are published as Multikey.
+//! <https://www.w3.org/TR/controller-document/#multikey>
+
+use anyhow::Result;
+use bech32::{Bech32, Hrp};
 use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
-use pem::parse;
-use pkcs1::{EncodeRsaPrivateKey, EncodeRsaPublicKey};
+use ed25519_dalek::pkcs8::{
+    DecodePublicKey, EncodePrivateKey, EncodePublicKey,
+};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use ed25519_dalek::{PUBLIC_KEY_LENGTH, SECRET_KEY_LENGTH};
+use rand::rngs::OsRng;
+use rsa::pkcs1::EncodeRsaPrivateKey;
+use rsa::{RsaPrivateKey, RsaPublicKey};
 use std::convert::TryInto;
-use std::str;
+use thiserror::Error;
+
+/// Multicodec header of Ed25519 public key.
+const ED25519_PUB: [u8; 2] = [0xed, 0x01];
 
+/// Multicodec header of Ed25519 private key.
+const ED25519_PRIV: [u8; 2] = [0x80, 0x26];
+
+/// Type of a key pair to create.
 #[derive(Debug)]
 pub enum KeyType {
+    /// RSA 2048. Signs HTTP requests.
     Rsa,
+    /// Ed25519. Signs object integrity proofs.
     Ed25519,
 }
 
-#[derive(Error, Debug)]
+/// Errors of key encoding.
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum KeyError {
+    /// Key type is not supported.
     #[error("Unknown key type")]
     UnknownKeyType,
+    /// Multibase is not base58-btc or the key is broken.
+    #[error("Invalid multikey: {0}")]
+    InvalidMultikey(String),
 }
 
-#[derive(Error, Debug)]
-#[error("Signature error")]
-pub enum SingnatureError {}
-
+/// Create (private key pem, public key pem).
 pub async fn create_keypair(keytype: KeyType) -> Result<(String, String)> {
-    return match keytype {
+    match keytype {
         KeyType::Rsa => {
             let mut rng = rand::thread_rng();
             let bits = 2048;
-            let privkey = RsaPrivateKey::new(&mut rng, bits).expect("failed to generate a key");
+            let privkey = RsaPrivateKey::new(&mut rng, bits)
+                .expect("failed to generate a key");
             let pubkey = RsaPublicKey::from(&privkey);
             Ok((
-                privkey.to_pkcs1_pem(LineEnding::LF).unwrap().to_string(),
-                pubkey.to_public_key_pem(LineEnding::LF).unwrap(),
+                privkey.to_pkcs1_pem(LineEnding::LF)?.to_string(),
+                pubkey.to_public_key_pem(LineEnding::LF)?,
             ))
         }
         KeyType::Ed25519 => {
-            // Rename Keypair → SigningKey and PublicKey → VerifyingKey
             let mut csprng = OsRng;
             let signing_key: SigningKey = SigningKey::generate(&mut csprng);
             let verifying_key = signing_key.verifying_key();
-            let pubkey_pem =
-                VerifyingKey::to_public_key_pem(&verifying_key, Default::default()).unwrap();
-            let privkey_pem = SigningKey::to_pkcs8_pem(&signing_key, Default::default()).unwrap();
+            let pubkey_pem = verifying_key.to_public_key_pem(LineEnding::LF)?;
+            let privkey_pem = signing_key.to_pkcs8_pem(LineEnding::LF)?;
             Ok((privkey_pem.to_string(), pubkey_pem))
         }
-    };
+    }
 }
 
-/*
-pub async fn signature(privkey: &str, signed_signature: String) -> Result<String, SingnatureError> {
-    //let keypair = PKey::private_key_from_pem(
-    //    &*privkey.as_bytes().to_vec()).unwrap();
-    // signature = Base64.strict_encode64(
-    //             keypair.sign(OpenSSL::Digest::SHA256.new, signed_string)
-    // ) in Ruby
+/// Ed25519 public key in `publicKeyMultibase` form. e.g. `z6Mk...`
+pub fn multikey_encode(key: &VerifyingKey) -> String {
+    multibase_encode(&ED25519_PUB, key.as_bytes())
+}
 
-    let pem = parse(privkey).unwrap();
+/// Ed25519 public key from `publicKeyMultibase`.
+pub fn multikey_decode(multibase: &str) -> Result<VerifyingKey, KeyError> {
+    let bytes: [u8; PUBLIC_KEY_LENGTH] =
+        multibase_decode(&ED25519_PUB, multibase)?
+            .as_slice()
+            .try_into()
+            .map_err(|_| KeyError::InvalidMultikey(multibase.to_string()))?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| KeyError::InvalidMultikey(e.to_string()))
+}
 
+/// Ed25519 private key in `secretKeyMultibase` form.
+pub fn secret_multikey_encode(key: &SigningKey) -> String {
+    multibase_encode(&ED25519_PRIV, key.as_bytes())
+}
 
+/// Ed25519 private key from `secretKeyMultibase`.
+pub fn secret_multikey_decode(multibase: &str) -> Result<SigningKey, KeyError> {
+    let bytes: [u8; SECRET_KEY_LENGTH] =
+        multibase_decode(&ED25519_PRIV, multibase)?
+            .as_slice()
+            .try_into()
+            .map_err(|_| KeyError::InvalidMultikey(multibase.to_string()))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
 
-    let mut signer = Signer::new(MessageDigest::sha256(), &keypair).unwrap();
-    signer.update(signed_signature.as_bytes()).unwrap();
-    let signature = signer.sign_to_vec().unwrap();
-    // Encode the signature as a base64 string
-    let encoded_signature = general_purpose::STANDARD.encode(&signature);
-    Ok(encoded_signature)
+/// base58-btc multibase of the multicodec header and the key.
+fn multibase_encode(header: &[u8], key: &[u8]) -> String {
+    format!("z{}", bs58::encode([header, key].concat()).into_string())
 }
 
- */
+fn multibase_decode(
+    header: &[u8],
+    multibase: &str,
+) -> Result<Vec<u8>, KeyError> {
+    let invalid = || KeyError::InvalidMultikey(multibase.to_string());
+    let encoded = multibase.strip_prefix('z').ok_or_else(invalid)?;
+    let decoded = bs58::decode(encoded).into_vec().map_err(|_| invalid())?;
+    match decoded.strip_prefix(header) {
+        Some(key) => Ok(key.to_vec()),
+        None => Err(invalid()),
+    }
+}
 
-pub async fn bech32_encode(hrp: &str, src: &str) -> String {
-    let s: VerifyingKey = DecodePublicKey::from_public_key_pem(src).unwrap();
-    bech32::encode(hrp, s.as_bytes().to_base32(), Variant::Bech32).unwrap()
+/// Bech32 string of the Ed25519 public key pem with the human readable part.
+pub async fn bech32_encode(hrp: &str, src: &str) -> Result<String> {
+    let s = VerifyingKey::from_public_key_pem(src)?;
+    Ok(bech32::encode::<Bech32>(Hrp::parse(hrp)?, s.as_bytes())?)
 }
 
-pub async fn bech32_decode(src: &str) -> (String, String) {
-    let s = src.to_string();
-    let (hrp, data, _variant) = bech32::decode(&s).unwrap();
-    let d = Vec::<u8>::from_base32(&data).unwrap();
-    let d2: [u8; 32] = d.as_slice().try_into().unwrap();
-    let d3 = VerifyingKey::from_bytes(&d2).unwrap();
-    (
-        hrp.to_string(),
-        VerifyingKey::to_public_key_pem(&d3, Default::default()).unwrap(),
-    )
+/// Human readable part and Ed25519 public key pem of the bech32 string.
+pub async fn bech32_decode(src: &str) -> Result<(String, String)> {
+    let (hrp, data) = bech32::decode(src)?;
+    let d: [u8; PUBLIC_KEY_LENGTH] = data.as_slice().try_into()?;
+    let key = VerifyingKey::from_bytes(&d)?;
+    Ok((hrp.to_string(), key.to_public_key_pem(LineEnding::LF)?))
 }
 
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
-//     use ed25519_dalek::pkcs8;
-//     use ed25519_dalek::pkcs8::spki::der::Decode;
-//     //use nostr::key::SecretKey;
 
 //     #[tokio::test]
 //     async fn create_rsa_keypair_test() {
@@ -135,27 +164,4 @@ pub async fn bech32_decode(src: &str) -> (String, String) {
 //         //dbg!(&encoded);
 //         //dbg!(bech32_decode(&encoded.as_str()).await);
 //     }
-
-//     #[tokio::test]
-//     async fn signature_test() {
-//         use super::*;
-//     }
-
-//     #[tokio::test]
-//     async fn foo() {
-//         use super::*;
-//         let p = "nsec1xvxajydhmquq9u09r5ya07wtyzjh6s5y448auk5jjtr0wtyag60qn0w6ng";
-//         let s = "npub1r2dyxxpedec3jkh0fxcw4v2cmca9r7zjmykwmn5jgcf7tcq0tw0qdsg9e5";
-
-//         let (hrp, data, _variant) = bech32::decode(&s).unwrap();
-//         let d = Vec::<u8>::from_base32(&data).unwrap();
-//         let d2: [u8; 32] = d.as_slice().try_into().unwrap();
-//         let d3 = SigningKey::from_bytes(&d2);
-//         //let d3 = VerifyingKey::from_bytes(&d2).unwrap();
-
-//         let d4 = SigningKey::to_pkcs8_pem(&d3, Default::default()).unwrap();
-//         dbg!(d4.to_string());
-
-//         //VerifyingKey::to_public_key_pem(&d3, Default::default()).unwrap()
-//     }
 // }
@@ -0,0 +1,298 @@
+//! Linked Data Signatures. RsaSignature2017.
+//!
+//! Mastodon signs the activities it may forward, like replies and deletes,
+//! so that receivers can check the original actor when another server
+//! delivers them. The signature options and the document are canonicalized
+//! with URDNA2015, and the actor's RSA key signs the SHA-256 hex digests of
+//! both, concatenated.
+//!
+//! <https://docs.joinmastodon.org/spec/security/#ld>
+//! <https://w3c-ccg.github.io/ld-signatures/>
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rsa::pkcs1v15::{Signature, SigningKey, VerifyingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::sha2::{Digest, Sha256};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+pub mod json_ld;
+pub mod rdf;
+pub mod urdna2015;
+
+/// The only supported signature type.
+pub const SIGNATURE_TYPE: &str = "RsaSignature2017";
+
+/// Context of the signature options.
+const OPTIONS_CONTEXT: &str = "https://w3id.org/identity/v1";
+
+/// Errors of Linked Data signature creation and verification.
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum LdSignatureError {
+    /// Document has no signature.
+    #[error("Signature is missing")]
+    Missing,
+    /// Signature type is not RsaSignature2017.
+    #[error("Unsupported signature type: {0}")]
+    UnsupportedType(String),
+    /// Document uses a context that is not bundled.
+    #[error("Unknown context: {0}")]
+    UnknownContext(String),
+    /// Signature or document is malformed.
+    #[error("Malformed signature: {0}")]
+    Malformed(String),
+    /// Key can not be parsed.
+    #[error("Bad key: {0}")]
+    BadKey(String),
+    /// Signature does not match.
+    #[error("Signature does not match")]
+    BadSignature,
+}
+
+/// URDNA2015 canonical N-Quads of the JSON-LD document.
+pub fn canonicalize(document: &Value) -> Result<String, LdSignatureError> {
+    Ok(urdna2015::canonicalize(&json_ld::to_rdf(document)?))
+}
+
+/// Key id that created the document's signature.
+pub fn creator(document: &Value) -> Option<String> {
+    document
+        .get("signature")?
+        .get("creator")
+        .and_then(|x| x.as_str())
+        .map(|x| x.to_string())
+}
+
+/// Document signed by the key. Existing signature is replaced.
+pub fn sign(
+    document: &Value,
+    creator: &str,
+    private_key_pem: &str,
+    created: DateTime<Utc>,
+) -> Result<Value, LdSignatureError> {
+    let mut unsigned = document.to_owned();
+    unsigned
+        .as_object_mut()
+        .ok_or(LdSignatureError::Malformed(
+            "document is not an object".to_string(),
+        ))?
+        .remove("signature");
+
+    let mut signature = Map::new();
+    signature.insert("type".to_string(), json!(SIGNATURE_TYPE));
+    signature.insert("creator".to_string(), json!(creator));
+    signature.insert(
+        "created".to_string(),
+        json!(created.to_rfc3339_opts(SecondsFormat::Secs, true)),
+    );
+
+    let private_key = RsaPrivateKey::from_pkcs8_pem(private_key_pem)
+        .map_err(|e| LdSignatureError::BadKey(e.to_string()))?;
+    let signing_key: SigningKey<Sha256> = SigningKey::new(private_key);
+    let to_be_signed = to_be_signed(&unsigned, &signature)?;
+    let signature_value = signing_key.sign(to_be_signed.as_bytes());
+    signature.insert(
+        "signatureValue".to_string(),
+        json!(general_purpose::STANDARD.encode(signature_value.to_bytes())),
+    );
+
+    let mut signed = unsigned;
+    signed["signature"] = Value::Object(signature);
+    Ok(signed)
+}
+
+/// Verify the document's signature with the creator's public key.
+pub fn verify(
+    document: &Value,
+    public_key_pem: &str,
+) -> Result<(), LdSignatureError> {
+    let signature = match document.get("signature") {
+        Some(Value::Object(signature)) => signature,
+        Some(_) => {
+            return Err(LdSignatureError::Malformed(
+                "signature is not an object".to_string(),
+            ))
+        }
+        None => return Err(LdSignatureError::Missing),
+    };
+    let kind = signature.get("type").and_then(|x| x.as_str());
+    if kind != Some(SIGNATURE_TYPE) {
+        return Err(LdSignatureError::UnsupportedType(
+            kind.unwrap_or_default().to_string(),
+        ));
+    }
+    let signature_value = signature
+        .get("signatureValue")
+        .and_then(|x| x.as_str())
+        .map(|x| x.split_whitespace().collect::<String>())
+        .and_then(|x| general_purpose::STANDARD.decode(x).ok())
+        .ok_or(LdSignatureError::Malformed(
+            "signatureValue is invalid".to_string(),
+        ))?;
+
+    let mut unsigned = document.to_owned();
+    unsigned.as_object_mut().map(|x| x.remove("signature"));
+    let to_be_verified = to_be_signed(&unsigned, signature)?;
+
+    let public_key = RsaPublicKey::from_public_key_pem(public_key_pem)
+        .map_err(|e| LdSignatureError::BadKey(e.to_string()))?;
+    let verifying_key: VerifyingKey<Sha256> = VerifyingKey::new(public_key);
+    let signature_value = Signature::try_from(signature_value.as_slice())
+        .map_err(|_| LdSignatureError::BadSignature)?;
+    verifying_key
+        .verify(to_be_verified.as_bytes(), &signature_value)
+        .map_err(|_| LdSignatureError::BadSignature)
+}
+
+/// SHA-256 hex of canonical signature options followed by SHA-256 hex of
+/// canonical document.
+fn to_be_signed(
+    unsigned: &Value,
+    signature: &Map<String, Value>,
+) -> Result<String, LdSignatureError> {
+    let mut options = signature.to_owned();
+    for key in ["type", "id", "signatureValue"] {
+        options.remove(key);
+    }
+    options.insert("@context".to_string(), json!(OPTIONS_CONTEXT));
+
+    let hash = |v: &Value| -> Result<String, LdSignatureError> {
+        Ok(hex::encode(Sha256::digest(canonicalize(v)?.as_bytes())))
+    };
+    Ok(format!(
+        "{}{}",
+        hash(&Value::Object(options))?,
+        hash(unsigned)?
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Activity as Mastodon signs it, with the expected canonical forms.
+    const FIXTURES: &str = include_str!("../tests/fixtures/ld_signature.json");
+
+    fn fixtures() -> Value {
+        serde_json::from_str(FIXTURES).unwrap()
+    }
+
+    fn fixture(key: &str) -> String {
+        fixtures()[key].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn canonicalize_document() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity.as_object_mut().unwrap().remove("signature");
+        assert_eq!(
+            canonicalize(&activity).unwrap(),
+            fixture("canonical_document")
+        );
+    }
+
+    #[test]
+    fn canonicalize_options() {
+        let options = json!({
+            "@context": OPTIONS_CONTEXT,
+            "creator": "https://mastodon.example/users/alice#main-key",
+            "created": "2024-08-20T10:00:00Z",
+        });
+        assert_eq!(
+            canonicalize(&options).unwrap(),
+            fixture("canonical_options")
+        );
+    }
+
+    #[test]
+    fn verify_fixture() {
+        let activity = fixtures()["activity"].to_owned();
+        assert_eq!(
+            creator(&activity).as_deref(),
+            Some("https://mastodon.example/users/alice#main-key")
+        );
+        assert_eq!(verify(&activity, &fixture("public_key")), Ok(()));
+        assert_eq!(
+            verify(&activity, &fixture("other_public_key")),
+            Err(LdSignatureError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn json_form_does_not_matter() {
+        let mut activity = fixtures()["activity"].to_owned();
+        // Same graph, written differently.
+        activity["to"] = json!("as:Public");
+        activity["object"]["tag"] =
+            json!(
+                [activity["object"]["tag"][1], activity["object"]["tag"][0],]
+            );
+        activity["object"]["unknownTerm"] = json!("ignored");
+        assert_eq!(verify(&activity, &fixture("public_key")), Ok(()));
+    }
+
+    #[test]
+    fn tampered_document_is_rejected() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["object"]["contentMap"]["en"] = json!("<p>Goodbye</p>");
+        assert_eq!(
+            verify(&activity, &fixture("public_key")),
+            Err(LdSignatureError::BadSignature)
+        );
+
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["signature"]["created"] = json!("2024-08-21T10:00:00Z");
+        assert_eq!(
+            verify(&activity, &fixture("public_key")),
+            Err(LdSignatureError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn sign_round_trip() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["object"]["content"] = json!("<p>Edited</p>");
+        let created = DateTime::from_timestamp(1724148000, 0).unwrap();
+        let signed = sign(
+            &activity,
+            "https://mastodon.example/users/alice#main-key",
+            &fixture("private_key"),
+            created,
+        )
+        .unwrap();
+        assert_eq!(signed["signature"]["created"], "2024-08-20T10:00:00Z");
+        assert_eq!(verify(&signed, &fixture("public_key")), Ok(()));
+    }
+
+    #[test]
+    fn unknown_context_is_rejected() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["@context"] = json!("https://example.com/context.jsonld");
+        assert_eq!(
+            verify(&activity, &fixture("public_key")),
+            Err(LdSignatureError::UnknownContext(
+                "https://example.com/context.jsonld".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn unsupported_type_and_missing_signature() {
+        let mut activity = fixtures()["activity"].to_owned();
+        activity["signature"]["type"] = json!("Ed25519Signature2018");
+        assert_eq!(
+            verify(&activity, &fixture("public_key")),
+            Err(LdSignatureError::UnsupportedType(
+                "Ed25519Signature2018".to_string()
+            ))
+        );
+        activity.as_object_mut().unwrap().remove("signature");
+        assert_eq!(
+            verify(&activity, &fixture("public_key")),
+            Err(LdSignatureError::Missing)
+        );
+    }
+}
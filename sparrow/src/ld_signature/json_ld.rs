@@ -0,0 +1,630 @@
+//! JSON-LD to RDF.
+//!
+//! Covers what activities use: term definitions, compact IRIs, `@vocab`,
+//! type coercion, `@list` and `@language` containers and value objects.
+//! Remote contexts are never fetched. Like Mastodon, only the bundled ones
+//! are known and any other context fails the conversion.
+//!
+//! <https://www.w3.org/TR/json-ld11-api/#deserialize-json-ld-to-rdf-algorithm>
+
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+use super::rdf::{
+    Quad, Term, RDF_FIRST, RDF_NIL, RDF_REST, RDF_TYPE, XSD_BOOLEAN,
+    XSD_DOUBLE, XSD_INTEGER, XSD_STRING,
+};
+use super::LdSignatureError;
+
+/// Contexts loaded from other contexts may not go deeper than this.
+const MAX_CONTEXT_DEPTH: usize = 8;
+
+/// Bundled remote contexts.
+fn bundled(iri: &str) -> Option<&'static str> {
+    match iri {
+        "https://www.w3.org/ns/activitystreams"
+        | "http://www.w3.org/ns/activitystreams"
+        | "https://www.w3.org/ns/activitystreams.jsonld" => {
+            Some(include_str!("contexts/activitystreams.jsonld"))
+        }
+        "https://w3id.org/security/v1" => {
+            Some(include_str!("contexts/security-v1.jsonld"))
+        }
+        "https://w3id.org/identity/v1" => {
+            Some(include_str!("contexts/identity-v1.jsonld"))
+        }
+        _ => None,
+    }
+}
+
+/// RDF quads of the JSON-LD document.
+pub fn to_rdf(document: &Value) -> Result<Vec<Quad>, LdSignatureError> {
+    let mut state = ToRdf::default();
+    let context = Context::default();
+    match document {
+        Value::Object(node) => {
+            state.node(&context, node)?;
+        }
+        Value::Array(nodes) => {
+            for node in nodes.iter().filter_map(|x| x.as_object()) {
+                state.node(&context, node)?;
+            }
+        }
+        _ => {
+            return Err(LdSignatureError::Malformed(
+                "document is not an object".to_string(),
+            ))
+        }
+    }
+    Ok(state.quads)
+}
+
+#[derive(Debug, Clone, Default)]
+struct Definition {
+    /// `None` when the term is defined as null and must be dropped.
+    id: Option<String>,
+    type_mapping: Option<String>,
+    container: Option<String>,
+    /// `Some(None)` when `@language` is null.
+    language: Option<Option<String>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Context {
+    terms: HashMap<String, Definition>,
+    vocab: Option<String>,
+    language: Option<String>,
+}
+
+impl Context {
+    fn process(&self, local: &Value) -> Result<Context, LdSignatureError> {
+        self.process_at(local, 0)
+    }
+
+    fn process_at(
+        &self,
+        local: &Value,
+        depth: usize,
+    ) -> Result<Context, LdSignatureError> {
+        if depth > MAX_CONTEXT_DEPTH {
+            return Err(LdSignatureError::Malformed(
+                "contexts are nested too deep".to_string(),
+            ));
+        }
+        match local {
+            Value::Null => Ok(Context::default()),
+            Value::Array(contexts) => {
+                let mut context = self.to_owned();
+                for local in contexts {
+                    context = context.process_at(local, depth)?;
+                }
+                Ok(context)
+            }
+            Value::String(iri) => {
+                let document = bundled(iri)
+                    .ok_or(LdSignatureError::UnknownContext(iri.to_owned()))?;
+                let document: Value = serde_json::from_str(document)
+                    .map_err(|e| LdSignatureError::Malformed(e.to_string()))?;
+                self.process_at(
+                    document.get("@context").unwrap_or(&Value::Null),
+                    depth + 1,
+                )
+            }
+            Value::Object(local) => {
+                let mut context = self.to_owned();
+                match local.get("@vocab") {
+                    Some(Value::String(vocab)) => {
+                        context.vocab = context.expand_iri(vocab, true)
+                    }
+                    Some(Value::Null) => context.vocab = None,
+                    _ => (),
+                }
+                match local.get("@language") {
+                    Some(Value::String(language)) => {
+                        context.language = Some(language.to_lowercase())
+                    }
+                    Some(Value::Null) => context.language = None,
+                    _ => (),
+                }
+                let mut defined = HashMap::new();
+                for term in local.keys().filter(|x| !x.starts_with('@')) {
+                    context.define(local, term, &mut defined)?;
+                }
+                Ok(context)
+            }
+            _ => Err(LdSignatureError::Malformed(
+                "@context is invalid".to_string(),
+            )),
+        }
+    }
+
+    /// Create the term definition. Terms it refers to in the same local
+    /// context are defined first.
+    fn define(
+        &mut self,
+        local: &Map<String, Value>,
+        term: &str,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<(), LdSignatureError> {
+        match defined.get(term) {
+            Some(true) => return Ok(()),
+            Some(false) => {
+                return Err(LdSignatureError::Malformed(format!(
+                    "{} is defined cyclically",
+                    term
+                )))
+            }
+            None => defined.insert(term.to_string(), false),
+        };
+
+        let value = match local.get(term) {
+            Some(Value::String(id)) => {
+                let mut value = Map::new();
+                value.insert("@id".to_string(), Value::String(id.to_owned()));
+                value
+            }
+            Some(Value::Object(value)) => value.to_owned(),
+            Some(Value::Null) | None => {
+                self.terms.insert(term.to_string(), Definition::default());
+                defined.insert(term.to_string(), true);
+                return Ok(());
+            }
+            Some(_) => {
+                return Err(LdSignatureError::Malformed(format!(
+                    "{} has an invalid definition",
+                    term
+                )))
+            }
+        };
+
+        let id = match value.get("@id") {
+            Some(Value::String(id)) => self.expand_local(id, local, defined)?,
+            Some(_) => None,
+            None => match term.split_once(':') {
+                Some(_) => self.expand_local(term, local, defined)?,
+                None => self.vocab.as_ref().map(|v| format!("{}{}", v, term)),
+            },
+        };
+        let mut definition = Definition {
+            id,
+            ..Default::default()
+        };
+        if let Some(Value::String(kind)) = value.get("@type") {
+            definition.type_mapping = match kind.as_str() {
+                "@id" | "@vocab" => Some(kind.to_owned()),
+                kind => self.expand_local(kind, local, defined)?,
+            };
+        }
+        definition.container = match value.get("@container") {
+            Some(Value::String(container)) => Some(container.to_owned()),
+            Some(Value::Array(containers)) => containers
+                .iter()
+                .filter_map(|x| x.as_str())
+                .find(|x| *x != "@set")
+                .map(|x| x.to_string()),
+            _ => None,
+        };
+        if let Some(language) = value.get("@language") {
+            definition.language =
+                Some(language.as_str().map(|x| x.to_lowercase()));
+        }
+
+        self.terms.insert(term.to_string(), definition);
+        defined.insert(term.to_string(), true);
+        Ok(())
+    }
+
+    /// Expand an IRI of a term definition.
+    fn expand_local(
+        &mut self,
+        value: &str,
+        local: &Map<String, Value>,
+        defined: &mut HashMap<String, bool>,
+    ) -> Result<Option<String>, LdSignatureError> {
+        let prefix = value.split_once(':').map(|x| x.0).unwrap_or(value);
+        for term in [value, prefix] {
+            if local.contains_key(term) && !term.starts_with('@') {
+                self.define(local, term, defined)?;
+            }
+        }
+        Ok(self.expand_iri(value, true))
+    }
+
+    /// Expand terms and compact IRIs. `vocab` for properties and types,
+    /// where terms and `@vocab` apply.
+    fn expand_iri(&self, value: &str, vocab: bool) -> Option<String> {
+        if value.starts_with('@') {
+            return Some(value.to_string());
+        }
+        if vocab {
+            if let Some(definition) = self.terms.get(value) {
+                return definition.id.to_owned();
+            }
+        }
+        if let Some((prefix, suffix)) = value.split_once(':') {
+            if prefix == "_" || suffix.starts_with("//") {
+                return Some(value.to_string());
+            }
+            return match self.terms.get(prefix).and_then(|d| d.id.as_ref()) {
+                Some(iri) => Some(format!("{}{}", iri, suffix)),
+                None => Some(value.to_string()),
+            };
+        }
+        match &self.vocab {
+            Some(v) if vocab => Some(format!("{}{}", v, value)),
+            // Relative IRI. There is no base to resolve it against.
+            _ => Some(value.to_string()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ToRdf {
+    quads: Vec<Quad>,
+    /// Labels of the document's blank nodes to generated ones.
+    blank_nodes: HashMap<String, String>,
+    counter: usize,
+}
+
+impl ToRdf {
+    fn blank(&mut self, label: Option<&str>) -> Term {
+        if let Some(generated) = label.and_then(|x| self.blank_nodes.get(x)) {
+            return Term::Blank(generated.to_owned());
+        }
+        let generated = format!("b{}", self.counter);
+        self.counter += 1;
+        if let Some(label) = label {
+            self.blank_nodes
+                .insert(label.to_string(), generated.to_owned());
+        }
+        Term::Blank(generated)
+    }
+
+    /// IRI or blank node. `None` for relative IRIs.
+    fn reference(
+        &mut self,
+        context: &Context,
+        value: &str,
+        vocab: bool,
+    ) -> Option<Term> {
+        let iri = context.expand_iri(value, vocab)?;
+        if let Some(label) = iri.strip_prefix("_:") {
+            return Some(self.blank(Some(label)));
+        }
+        is_absolute_iri(&iri).then_some(Term::Iri(iri))
+    }
+
+    /// Quads of the node object. Returns the node, or `None` when its `@id`
+    /// is relative.
+    fn node(
+        &mut self,
+        active: &Context,
+        node: &Map<String, Value>,
+    ) -> Result<Option<Term>, LdSignatureError> {
+        let context = match node.get("@context") {
+            Some(local) => active.process(local)?,
+            None => active.to_owned(),
+        };
+        let expanded: Vec<(Option<String>, &String, &Value)> = node
+            .iter()
+            .map(|(k, v)| (context.expand_iri(k, true), k, v))
+            .collect();
+
+        let id = expanded
+            .iter()
+            .find(|(p, _, _)| p.as_deref() == Some("@id"))
+            .map(|(_, _, v)| v);
+        let subject = match id {
+            Some(Value::String(id)) => {
+                match self.reference(&context, id, false) {
+                    Some(subject) => subject,
+                    None => return Ok(None),
+                }
+            }
+            Some(_) => {
+                return Err(LdSignatureError::Malformed(
+                    "@id is not a string".to_string(),
+                ))
+            }
+            None => self.blank(None),
+        };
+
+        for (property, key, value) in expanded {
+            let property = match property {
+                Some(p) if p == "@type" => {
+                    for kind in values(value).iter().filter_map(|x| x.as_str())
+                    {
+                        if let Some(object) =
+                            self.reference(&context, kind, true)
+                        {
+                            self.quads.push(Quad::new(
+                                subject.to_owned(),
+                                RDF_TYPE,
+                                object,
+                            ));
+                        }
+                    }
+                    continue;
+                }
+                // Blank node properties are not RDF.
+                Some(p) if is_absolute_iri(&p) => p,
+                _ => continue,
+            };
+
+            let definition = context.terms.get(key.as_str());
+            let objects = match (
+                definition.and_then(|d| d.container.as_deref()),
+                value,
+            ) {
+                (Some("@list"), value) => {
+                    vec![self.list(&context, definition, value)?]
+                }
+                (Some("@language"), Value::Object(map)) => map
+                    .iter()
+                    .flat_map(|(language, v)| {
+                        values(v).into_iter().filter_map(|v| {
+                            v.as_str().map(|v| {
+                                Term::language_literal(
+                                    v,
+                                    &language.to_lowercase(),
+                                )
+                            })
+                        })
+                    })
+                    .collect(),
+                (_, value) => self.objects(&context, definition, value)?,
+            };
+            for object in objects {
+                self.quads.push(Quad::new(
+                    subject.to_owned(),
+                    &property,
+                    object,
+                ));
+            }
+        }
+        Ok(Some(subject))
+    }
+
+    fn objects(
+        &mut self,
+        context: &Context,
+        definition: Option<&Definition>,
+        value: &Value,
+    ) -> Result<Vec<Term>, LdSignatureError> {
+        match value {
+            Value::Null => Ok(vec![]),
+            Value::Array(items) => {
+                let mut objects = vec![];
+                for item in items {
+                    objects.extend(self.objects(context, definition, item)?);
+                }
+                Ok(objects)
+            }
+            Value::Object(map) => {
+                let keyword = |k: &str| {
+                    map.iter()
+                        .find(|(key, _)| {
+                            context.expand_iri(key, true).as_deref() == Some(k)
+                        })
+                        .map(|(_, v)| v)
+                };
+                if let Some(v) = keyword("@value") {
+                    Ok(self
+                        .value_object(
+                            context,
+                            v,
+                            keyword("@type"),
+                            keyword("@language"),
+                        )
+                        .into_iter()
+                        .collect())
+                } else if let Some(list) = keyword("@list") {
+                    Ok(vec![self.list(context, definition, list)?])
+                } else if let Some(set) = keyword("@set") {
+                    self.objects(context, definition, set)
+                } else {
+                    Ok(self.node(context, map)?.into_iter().collect())
+                }
+            }
+            value => Ok(self
+                .literal(context, definition, value)
+                .into_iter()
+                .collect()),
+        }
+    }
+
+    /// Head of an RDF collection of the values.
+    fn list(
+        &mut self,
+        context: &Context,
+        definition: Option<&Definition>,
+        value: &Value,
+    ) -> Result<Term, LdSignatureError> {
+        let mut objects = vec![];
+        for item in values(value) {
+            objects.extend(self.objects(context, definition, item)?);
+        }
+        let mut head = Term::Iri(RDF_NIL.to_string());
+        for object in objects.into_iter().rev() {
+            let node = self.blank(None);
+            self.quads
+                .push(Quad::new(node.to_owned(), RDF_FIRST, object));
+            self.quads.push(Quad::new(node.to_owned(), RDF_REST, head));
+            head = node;
+        }
+        Ok(head)
+    }
+
+    /// Term of a string, number or boolean coerced by the definition.
+    fn literal(
+        &mut self,
+        context: &Context,
+        definition: Option<&Definition>,
+        value: &Value,
+    ) -> Option<Term> {
+        let type_mapping = definition.and_then(|d| d.type_mapping.as_deref());
+        match (value, type_mapping) {
+            (Value::String(v), Some("@id")) => {
+                self.reference(context, v, false)
+            }
+            (Value::String(v), Some("@vocab")) => {
+                self.reference(context, v, true)
+            }
+            (Value::String(v), Some(datatype)) => {
+                Some(Term::literal(v, datatype))
+            }
+            (Value::String(v), None) => {
+                let language =
+                    match definition.and_then(|d| d.language.to_owned()) {
+                        Some(language) => language,
+                        None => context.language.to_owned(),
+                    };
+                Some(match language {
+                    Some(language) => Term::language_literal(v, &language),
+                    None => Term::literal(v, XSD_STRING),
+                })
+            }
+            (value, Some("@id" | "@vocab")) | (value, None) => {
+                native(value, None)
+            }
+            (value, Some(datatype)) => native(value, Some(datatype)),
+        }
+    }
+
+    fn value_object(
+        &mut self,
+        context: &Context,
+        value: &Value,
+        kind: Option<&Value>,
+        language: Option<&Value>,
+    ) -> Option<Term> {
+        let datatype = kind
+            .and_then(|x| x.as_str())
+            .and_then(|x| context.expand_iri(x, true));
+        match (value, datatype, language.and_then(|x| x.as_str())) {
+            (Value::String(v), Some(datatype), _) => {
+                Some(Term::literal(v, &datatype))
+            }
+            (Value::String(v), None, Some(language)) => {
+                Some(Term::language_literal(v, &language.to_lowercase()))
+            }
+            (Value::String(v), None, None) => {
+                Some(Term::literal(v, XSD_STRING))
+            }
+            (value, datatype, _) => native(value, datatype.as_deref()),
+        }
+    }
+}
+
+/// Literal of a JSON number or boolean.
+fn native(value: &Value, datatype: Option<&str>) -> Option<Term> {
+    match value {
+        Value::Bool(b) => Some(Term::literal(
+            &b.to_string(),
+            datatype.unwrap_or(XSD_BOOLEAN),
+        )),
+        Value::Number(n) => Some(number(n, datatype)),
+        _ => None,
+    }
+}
+
+fn number(n: &Number, datatype: Option<&str>) -> Term {
+    let f = n.as_f64().unwrap_or_default();
+    let integral =
+        n.is_i64() || n.is_u64() || (f.fract() == 0.0 && f.abs() < 1e21);
+    if !integral || datatype == Some(XSD_DOUBLE) {
+        return Term::literal(
+            &canonical_double(f),
+            datatype.unwrap_or(XSD_DOUBLE),
+        );
+    }
+    let lexical = match (n.as_i64(), n.as_u64()) {
+        (Some(i), _) => i.to_string(),
+        (_, Some(u)) => u.to_string(),
+        _ => format!("{}", f as i64),
+    };
+    Term::literal(&lexical, datatype.unwrap_or(XSD_INTEGER))
+}
+
+/// xsd:double canonical form. e.g. `1.5E0`
+fn canonical_double(f: f64) -> String {
+    let formatted = format!("{:E}", f);
+    let (mantissa, exponent) =
+        formatted.split_once('E').unwrap_or((&formatted, "0"));
+    if mantissa.contains('.') {
+        format!("{}E{}", mantissa, exponent)
+    } else {
+        format!("{}.0E{}", mantissa, exponent)
+    }
+}
+
+fn values(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Null => vec![],
+        value => vec![value],
+    }
+}
+
+fn is_absolute_iri(value: &str) -> bool {
+    match value.split_once(':') {
+        Some((scheme, _)) => {
+            scheme != "_"
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn nquads(document: Value) -> Vec<String> {
+        let mut lines: Vec<String> = to_rdf(&document)
+            .unwrap()
+            .iter()
+            .map(|q| q.nquad())
+            .collect();
+        lines.sort();
+        lines
+    }
+
+    #[test]
+    fn literals_and_lists() {
+        let lines = nquads(json!({
+            "@context": [
+                "https://www.w3.org/ns/activitystreams",
+                {"ex": "http://example.org/", "size": {"@id": "ex:size"}}
+            ],
+            "id": "http://example.org/c",
+            "orderedItems": ["http://example.org/1"],
+            "size": [3, 1.5, true],
+            "ex:note": {"@value": "hi", "@language": "EN"},
+            "ex:when": {"@value": "2024", "@type": "xsd:gYear"},
+            "relative": "dropped",
+        }));
+        let expected = [
+            "<http://example.org/c> <http://example.org/note> \"hi\"@en .\n",
+            "<http://example.org/c> <http://example.org/size> \"1.5E0\"^^<http://www.w3.org/2001/XMLSchema#double> .\n",
+            "<http://example.org/c> <http://example.org/size> \"3\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n",
+            "<http://example.org/c> <http://example.org/size> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> .\n",
+            "<http://example.org/c> <http://example.org/when> \"2024\"^^<http://www.w3.org/2001/XMLSchema#gYear> .\n",
+            "<http://example.org/c> <https://www.w3.org/ns/activitystreams#items> _:b0 .\n",
+            "_:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> <http://example.org/1> .\n",
+            "_:b0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n",
+        ];
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn canonical_double() {
+        assert_eq!(super::canonical_double(1.5), "1.5E0");
+        assert_eq!(super::canonical_double(1234.5), "1.2345E3");
+        assert_eq!(super::canonical_double(0.001), "1.0E-3");
+    }
+}
@@ -0,0 +1,160 @@
+//! RDF terms and quads, serialized as N-Quads.
+//!
+//! <https://www.w3.org/TR/n-quads/>
+
+/// `rdf:type`
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+/// `rdf:first`
+pub const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+/// `rdf:rest`
+pub const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+/// `rdf:nil`
+pub const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+/// `xsd:string`
+pub const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+/// `xsd:boolean`
+pub const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+/// `xsd:integer`
+pub const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+/// `xsd:double`
+pub const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Subject, predicate, object or graph of a quad.
+pub enum Term {
+    /// IRI without angle brackets.
+    Iri(String),
+    /// Blank node label without `_:`.
+    Blank(String),
+    /// Literal. Language tagged ones are `rdf:langString`.
+    Literal {
+        /// Lexical form.
+        value: String,
+        /// Datatype IRI.
+        datatype: String,
+        /// Language tag.
+        language: Option<String>,
+    },
+}
+
+impl Term {
+    /// Typed literal.
+    pub fn literal(value: &str, datatype: &str) -> Term {
+        Term::Literal {
+            value: value.to_string(),
+            datatype: datatype.to_string(),
+            language: None,
+        }
+    }
+
+    /// Language tagged literal.
+    pub fn language_literal(value: &str, language: &str) -> Term {
+        Term::Literal {
+            value: value.to_string(),
+            datatype: "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString"
+                .to_string(),
+            language: Some(language.to_string()),
+        }
+    }
+
+    /// Label of the blank node.
+    pub fn blank_label(&self) -> Option<&str> {
+        match self {
+            Term::Blank(label) => Some(label),
+            _ => None,
+        }
+    }
+
+    /// N-Quads form of the term.
+    pub fn nquads(&self) -> String {
+        match self {
+            Term::Iri(iri) => format!("<{}>", iri),
+            Term::Blank(label) => format!("_:{}", label),
+            Term::Literal {
+                value,
+                datatype,
+                language,
+            } => match language {
+                Some(language) => format!("\"{}\"@{}", escape(value), language),
+                None if datatype == XSD_STRING => {
+                    format!("\"{}\"", escape(value))
+                }
+                None => format!("\"{}\"^^<{}>", escape(value), datatype),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// RDF quad. Triples are in the default graph.
+pub struct Quad {
+    /// IRI or blank node.
+    pub subject: Term,
+    /// IRI.
+    pub predicate: Term,
+    /// IRI, blank node or literal.
+    pub object: Term,
+    /// `None` for the default graph.
+    pub graph: Option<Term>,
+}
+
+impl Quad {
+    /// Quad in the default graph.
+    pub fn new(subject: Term, predicate: &str, object: Term) -> Quad {
+        Quad {
+            subject,
+            predicate: Term::Iri(predicate.to_string()),
+            object,
+            graph: None,
+        }
+    }
+
+    /// N-Quads line of the quad with the line feed.
+    pub fn nquad(&self) -> String {
+        match &self.graph {
+            Some(graph) => format!(
+                "{} {} {} {} .\n",
+                self.subject.nquads(),
+                self.predicate.nquads(),
+                self.object.nquads(),
+                graph.nquads()
+            ),
+            None => format!(
+                "{} {} {} .\n",
+                self.subject.nquads(),
+                self.predicate.nquads(),
+                self.object.nquads()
+            ),
+        }
+    }
+
+    /// Quad with blank nodes relabeled.
+    pub fn map_blank<F: Fn(&str) -> String>(&self, f: F) -> Quad {
+        let map = |t: &Term| match t {
+            Term::Blank(label) => Term::Blank(f(label)),
+            t => t.to_owned(),
+        };
+        Quad {
+            subject: map(&self.subject),
+            predicate: self.predicate.to_owned(),
+            object: map(&self.object),
+            graph: self.graph.as_ref().map(map),
+        }
+    }
+}
+
+/// Escape a literal as canonical N-Quads do.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
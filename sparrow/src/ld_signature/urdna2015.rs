@@ -0,0 +1,348 @@
+//! URDNA2015 RDF dataset canonicalization.
+//!
+//! Blank nodes are relabeled by hashing their surroundings, so the same
+//! graph serializes to the same N-Quads however it was written.
+//!
+//! <https://www.w3.org/TR/rdf-canon/>
+
+use rsa::sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::rdf::Quad;
+
+/// Canonical N-Quads of the dataset, sorted and without duplicates.
+pub fn canonicalize(quads: &[Quad]) -> String {
+    let mut seen = HashSet::new();
+    let quads: Vec<&Quad> = quads.iter().filter(|q| seen.insert(*q)).collect();
+    let mut state = State::new(&quads);
+
+    // Blank nodes with a unique first degree hash are labeled in hash order.
+    let mut hash_to_blank_nodes: BTreeMap<String, Vec<String>> =
+        BTreeMap::new();
+    for id in std::mem::take(&mut state.blank_nodes) {
+        let hash = state.hash_first_degree(&id);
+        hash_to_blank_nodes.entry(hash).or_default().push(id);
+    }
+    let mut shared = vec![];
+    for (_, ids) in hash_to_blank_nodes {
+        if ids.len() == 1 {
+            state.canonical.issue(&ids[0]);
+        } else {
+            shared.push(ids);
+        }
+    }
+
+    // The rest are told apart by their related blank nodes.
+    for ids in shared {
+        let mut results = vec![];
+        for id in ids {
+            if state.canonical.get(&id).is_some() {
+                continue;
+            }
+            let mut temporary = IdentifierIssuer::new("_:b");
+            temporary.issue(&id);
+            results.push(state.hash_n_degree(&id, temporary));
+        }
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        for (_, issuer) in results {
+            for existing in issuer.issued {
+                state.canonical.issue(&existing);
+            }
+        }
+    }
+
+    let mut lines: Vec<String> = quads
+        .iter()
+        .map(|q| {
+            q.map_blank(|label| {
+                let id = state.canonical.get(label).unwrap_or(label);
+                id.trim_start_matches("_:").to_string()
+            })
+            .nquad()
+        })
+        .collect();
+    lines.sort();
+    lines.dedup();
+    lines.concat()
+}
+
+#[derive(Debug, Clone)]
+struct IdentifierIssuer {
+    prefix: &'static str,
+    /// Existing identifiers in the order they were issued.
+    issued: Vec<String>,
+    map: HashMap<String, String>,
+}
+
+impl IdentifierIssuer {
+    fn new(prefix: &'static str) -> IdentifierIssuer {
+        IdentifierIssuer {
+            prefix,
+            issued: vec![],
+            map: HashMap::new(),
+        }
+    }
+
+    fn issue(&mut self, existing: &str) -> String {
+        if let Some(id) = self.map.get(existing) {
+            return id.to_owned();
+        }
+        let id = format!("{}{}", self.prefix, self.issued.len());
+        self.issued.push(existing.to_string());
+        self.map.insert(existing.to_string(), id.to_owned());
+        id
+    }
+
+    fn get(&self, existing: &str) -> Option<&str> {
+        self.map.get(existing).map(|x| x.as_str())
+    }
+}
+
+struct State<'a> {
+    /// Blank node labels in the order they appear.
+    blank_nodes: Vec<String>,
+    blank_node_quads: HashMap<String, Vec<&'a Quad>>,
+    canonical: IdentifierIssuer,
+    first_degree: HashMap<String, String>,
+}
+
+impl<'a> State<'a> {
+    fn new(quads: &[&'a Quad]) -> State<'a> {
+        let mut blank_nodes = vec![];
+        let mut blank_node_quads: HashMap<String, Vec<&Quad>> = HashMap::new();
+        for quad in quads {
+            let terms =
+                [Some(&quad.subject), Some(&quad.object), quad.graph.as_ref()];
+            for label in terms.iter().flatten().filter_map(|t| t.blank_label())
+            {
+                let entry =
+                    blank_node_quads.entry(label.to_string()).or_default();
+                if entry.is_empty() {
+                    blank_nodes.push(label.to_string());
+                }
+                if !entry.iter().any(|q| std::ptr::eq(*q, *quad)) {
+                    entry.push(quad);
+                }
+            }
+        }
+        State {
+            blank_nodes,
+            blank_node_quads,
+            canonical: IdentifierIssuer::new("_:c14n"),
+            first_degree: HashMap::new(),
+        }
+    }
+
+    /// Hash of the quads mentioning the blank node, where it is `_:a` and
+    /// every other blank node is `_:z`.
+    fn hash_first_degree(&mut self, id: &str) -> String {
+        if let Some(hash) = self.first_degree.get(id) {
+            return hash.to_owned();
+        }
+        let mut nquads: Vec<String> = self
+            .blank_node_quads
+            .get(id)
+            .map(|quads| {
+                quads
+                    .iter()
+                    .map(|q| {
+                        q.map_blank(|label| {
+                            if label == id { "a" } else { "z" }.to_string()
+                        })
+                        .nquad()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        nquads.sort();
+        let hash = sha256_hex(&nquads.concat());
+        self.first_degree.insert(id.to_string(), hash.to_owned());
+        hash
+    }
+
+    fn hash_related(
+        &mut self,
+        related: &str,
+        quad: &Quad,
+        issuer: &IdentifierIssuer,
+        position: &str,
+    ) -> String {
+        let id = match self.canonical.get(related).or(issuer.get(related)) {
+            Some(id) => id.to_string(),
+            None => self.hash_first_degree(related),
+        };
+        let mut input = position.to_string();
+        if position != "g" {
+            input.push_str(&quad.predicate.nquads());
+        }
+        input.push_str(&id);
+        sha256_hex(&input)
+    }
+
+    fn hash_n_degree(
+        &mut self,
+        id: &str,
+        mut issuer: IdentifierIssuer,
+    ) -> (String, IdentifierIssuer) {
+        let mut hash_to_related: BTreeMap<String, Vec<String>> =
+            BTreeMap::new();
+        let quads = self.blank_node_quads.get(id).cloned().unwrap_or_default();
+        for quad in quads {
+            let terms = [
+                (Some(&quad.subject), "s"),
+                (Some(&quad.object), "o"),
+                (quad.graph.as_ref(), "g"),
+            ];
+            for (term, position) in terms {
+                let related = match term.and_then(|t| t.blank_label()) {
+                    Some(label) if label != id => label,
+                    _ => continue,
+                };
+                let hash = self.hash_related(related, quad, &issuer, position);
+                hash_to_related
+                    .entry(hash)
+                    .or_default()
+                    .push(related.to_string());
+            }
+        }
+
+        let mut data = String::new();
+        for (related_hash, blank_nodes) in hash_to_related {
+            data.push_str(&related_hash);
+            let mut chosen_path = String::new();
+            let mut chosen_issuer = None;
+            'permutation: for permutation in permutations(&blank_nodes) {
+                let mut issuer_copy = issuer.to_owned();
+                let mut path = String::new();
+                let mut recursion = vec![];
+                let longer = |path: &str, chosen: &str| {
+                    !chosen.is_empty()
+                        && path.len() >= chosen.len()
+                        && path > chosen
+                };
+                for related in permutation {
+                    match self.canonical.get(&related) {
+                        Some(canonical) => path.push_str(canonical),
+                        None => {
+                            if issuer_copy.get(&related).is_none() {
+                                recursion.push(related.to_owned());
+                            }
+                            path.push_str(&issuer_copy.issue(&related));
+                        }
+                    }
+                    if longer(&path, &chosen_path) {
+                        continue 'permutation;
+                    }
+                }
+                for related in recursion {
+                    let (hash, result_issuer) =
+                        self.hash_n_degree(&related, issuer_copy.to_owned());
+                    path.push_str(&issuer_copy.issue(&related));
+                    path.push_str(&format!("<{}>", hash));
+                    issuer_copy = result_issuer;
+                    if longer(&path, &chosen_path) {
+                        continue 'permutation;
+                    }
+                }
+                if chosen_path.is_empty() || path < chosen_path {
+                    chosen_path = path;
+                    chosen_issuer = Some(issuer_copy);
+                }
+            }
+            data.push_str(&chosen_path);
+            if let Some(chosen) = chosen_issuer {
+                issuer = chosen;
+            }
+        }
+        (sha256_hex(&data), issuer)
+    }
+}
+
+fn permutations(items: &[String]) -> Vec<Vec<String>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+    let mut result = vec![];
+    for i in 0..items.len() {
+        let mut rest = items.to_vec();
+        let first = rest.remove(i);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, first.to_owned());
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+fn sha256_hex(data: &str) -> String {
+    hex::encode(Sha256::digest(data.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ld_signature::rdf::Term;
+
+    fn quad(s: &str, p: &str, o: &str) -> Quad {
+        let term = |t: &str| match t.strip_prefix("_:") {
+            Some(label) => Term::Blank(label.to_string()),
+            None => Term::Iri(t.to_string()),
+        };
+        Quad::new(term(s), p, term(o))
+    }
+
+    #[test]
+    fn symmetric_blank_nodes() {
+        let p = "http://example.org/p";
+        let expected = "_:c14n0 <http://example.org/p> _:c14n1 .\n\
+                        _:c14n1 <http://example.org/p> _:c14n0 .\n";
+        let quads = [quad("_:x", p, "_:y"), quad("_:y", p, "_:x")];
+        assert_eq!(canonicalize(&quads), expected);
+    }
+
+    #[test]
+    fn labels_and_order_do_not_matter() {
+        let (p, q) = ("http://example.org/p", "http://example.org/q");
+        let a = [
+            quad("_:a", p, "_:b"),
+            quad("_:b", p, "_:c"),
+            quad("_:c", p, "_:a"),
+            quad("_:a", q, "_:d"),
+            quad("_:d", q, "http://example.org/x"),
+            quad("_:e", p, "_:d"),
+        ];
+        let b = [
+            quad("_:n5", p, "_:n3"),
+            quad("_:n3", q, "http://example.org/x"),
+            quad("_:n2", p, "_:n0"),
+            quad("_:n1", p, "_:n2"),
+            quad("_:n0", p, "_:n1"),
+            quad("_:n0", q, "_:n3"),
+            quad("_:n0", q, "_:n3"),
+        ];
+        let canonical = canonicalize(&a);
+        assert_eq!(canonical, canonicalize(&b));
+        assert_eq!(canonical.lines().count(), 6);
+        assert!(!canonical.contains("_:n"));
+    }
+
+    /// Example published with the canonicalization algorithm.
+    const FIXTURES: &str = include_str!("../../tests/fixtures/urdna2015.json");
+
+    #[test]
+    fn shared_hashes_test_vector() {
+        let f: serde_json::Value = serde_json::from_str(FIXTURES).unwrap();
+        let iri =
+            |t: &str| t.trim_matches(|c| c == '<' || c == '>').to_string();
+        let quads: Vec<Quad> = f["input"]
+            .as_str()
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let t: Vec<&str> = line.split(' ').collect();
+                quad(&iri(t[0]), &iri(t[1]), &iri(t[2]))
+            })
+            .collect();
+        assert_eq!(canonicalize(&quads), f["expected"].as_str().unwrap());
+    }
+}
@@ -4,12 +4,19 @@
 //pub mod auth;
 
 //pub mod db;
-//pub mod keys;
+/// Actor keys
+pub mod keys;
 
 pub mod cache;
 
 // pub mod follow_request;
 pub mod http_response;
+/// HTTP Signatures verification
+pub mod http_signature;
+/// Object integrity proofs
+pub mod integrity_proof;
+/// Linked Data Signatures verification
+pub mod ld_signature;
 
 pub mod activitystream;
 pub mod mastodon;
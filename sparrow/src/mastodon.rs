@@ -6,62 +6,172 @@ use anyhow::{Error, Result};
 use base64::{engine::general_purpose, Engine as _};
 use bincode::{config as bincode_config, Decode, Encode};
 use once_cell::sync::OnceCell;
-use rsa::pkcs1v15::VerifyingKey;
 use rsa::pkcs1v15::{Signature, SigningKey};
 use rsa::pkcs8::DecodePrivateKey;
-use rsa::pkcs8::DecodePublicKey;
 use rsa::sha2::{Digest, Sha256};
 use rsa::signature::SignatureEncoding;
 use rsa::signature::Signer;
-use rsa::signature::Verifier;
-use rsa::{RsaPrivateKey, RsaPublicKey};
+use rsa::RsaPrivateKey;
 use serde::Serialize;
 use serde_json::Value;
 use spin_sdk::http::{self, Method, Request, RequestBuilder, Response};
 use spin_sdk::key_value::Store;
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::string::ToString;
+use std::sync::RwLock;
 use url::Url;
 
 use crate::activitystream::activity::Activity;
 use crate::activitystream::activity::ActivityType;
 use crate::activitystream::Execute;
+use crate::http_signature::{self, Scheme, SignatureError};
+use crate::http_signature::{RequestSignature, SignedRequest};
+use crate::integrity_proof::DATA_INTEGRITY_CONTEXT;
+use crate::ld_signature::{self, LdSignatureError};
 use crate::mastodon::account::actor_url::ActorUrl;
 use crate::mastodon::account::Account as MAccount;
 use crate::mastodon::account::Get as _;
+use crate::mastodon::account_key::AccountKey;
+use crate::mastodon::audience::Audience;
+use crate::mastodon::delivery::Delivery;
+use crate::mastodon::domain_block::DomainBlock;
+use crate::mastodon::fetch_error::FetchError;
+use crate::mastodon::inbox_error::InboxError;
+use crate::mastodon::recipient::Recipient;
 use crate::mastodon::setting::Setting;
+use crate::mastodon::signature_scheme::SignatureScheme;
 use crate::table::account::Account as TAccount;
 use crate::table::activity_log::ActivityLog;
 use crate::utils::get_current_time_in_rfc_1123;
 
 pub mod account;
+pub mod account_key;
 pub mod activity_log;
 pub mod application;
+pub mod audience;
+pub mod block;
 pub mod custom_emoji;
+pub mod delivery;
+pub mod domain_block;
+pub mod fetch_error;
 pub mod filter;
 pub mod filter_keyword;
 pub mod filter_result;
 pub mod filter_status;
 pub mod follow;
+pub mod inbox_activity;
+pub mod inbox_error;
+pub mod job;
 pub mod instance;
 pub mod list;
 pub mod media_attachment;
 pub mod notification;
 pub mod poll;
 pub mod preview_card;
+pub mod recipient;
 pub mod relationship;
 pub mod relationship_severance_event;
 pub mod report;
 pub mod setting;
+pub mod signature_scheme;
 pub mod status;
+pub mod status_edit;
+pub mod status_pin;
+pub mod status_source;
 pub mod tag;
+pub mod unavailable_domain;
 pub mod token;
 pub mod user;
 pub mod user_role;
 
 pub static ME_ACCOUNT: OnceCell<MAccount> = OnceCell::new();
-pub static ACTOR_ACCOUNT: OnceCell<MAccount> = OnceCell::new();
+pub static ACTOR_ACCOUNT: AccountCell = AccountCell::new();
+pub static RECIPIENTS: RecipientCell = RecipientCell::new();
+
+/// Account slot that can be replaced.
+/// Worker processes activities of many actors in one invocation.
+pub struct AccountCell(RwLock<Option<MAccount>>);
+
+impl AccountCell {
+    pub const fn new() -> Self {
+        AccountCell(RwLock::new(None))
+    }
+
+    pub fn get(&self) -> Option<MAccount> {
+        self.0.read().ok().and_then(|x| x.to_owned())
+    }
+
+    pub fn set(&self, account: MAccount) {
+        if let Ok(mut x) = self.0.write() {
+            *x = Some(account);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut x) = self.0.write() {
+            *x = None;
+        }
+    }
+}
+
+/// Local recipients of the activity being executed.
+/// Shared inbox activity can address several local accounts.
+pub struct RecipientCell(RwLock<Vec<Recipient>>);
+
+impl RecipientCell {
+    pub const fn new() -> Self {
+        RecipientCell(RwLock::new(Vec::new()))
+    }
+
+    pub fn get(&self) -> Vec<Recipient> {
+        self.0.read().map(|x| x.to_owned()).unwrap_or_default()
+    }
+
+    pub fn set(&self, recipients: Vec<Recipient>) {
+        if let Ok(mut x) = self.0.write() {
+            *x = recipients;
+        }
+    }
+
+    pub fn clear(&self) {
+        self.set(Vec::new());
+    }
+}
+
+/// Default local account. It signs fetches made while executing activities.
+pub fn me_account() -> Result<MAccount> {
+    ME_ACCOUNT
+        .get()
+        .cloned()
+        .ok_or(Error::msg("ME_ACCOUNT is not loaded"))
+}
+
+/// Actor of the activity being executed.
+pub fn actor_account() -> Result<MAccount> {
+    ACTOR_ACCOUNT
+        .get()
+        .ok_or(Error::msg("ACTOR_ACCOUNT is not loaded"))
+}
+
+/// Local accounts the activity being executed is addressed to.
+pub fn recipients() -> Vec<MAccount> {
+    RECIPIENTS.get().into_iter().map(|x| x.account).collect()
+}
+
+/// Addressed local account with the actor url.
+pub fn recipient_with_url(actor_url: &str) -> Result<MAccount> {
+    recipients()
+        .into_iter()
+        .find(|x| x.actor_url.to_string() == actor_url)
+        .ok_or(Error::msg(format!("{} is not a local recipient", actor_url)))
+}
+
+/// Addressed local account with the uid, e.g. author of a status.
+pub fn recipient_with_uid(account_uid: &str) -> Option<MAccount> {
+    recipients()
+        .into_iter()
+        .find(|x| x.uid.to_string() == account_uid)
+}
 
 // https://github.com/RustCrypto/RSA/issues/341
 
@@ -76,6 +186,10 @@ pub enum ValidationResult {
 ///
 /// This adds new actor to Account.  
 /// This adds incoming request to activityLog table.
+/// Both draft-cavage and RFC 9421 signatures are accepted.
+/// See [`http_signature`]. Activities forwarded by another server are
+/// accepted when the forwarding actor's HTTP signature is valid and the
+/// activity carries the actor's integrity proof or Linked Data signature.
 /// https://docs.joinmastodon.org/spec/security/#http-verify
 /// https://github.com/mastodon/mastodon/sender_actor_url_stringblob/main/app/controllers/concerns/signature_verification.rb
 pub async fn validate_signature(
@@ -92,49 +206,50 @@ pub async fn validate_signature(
     //     }
     // };
 
-    let sig_header = req.header("Signature").unwrap().as_str().unwrap();
-    let hostname = req
-        .header("Host")
-        .unwrap_or(req.header("x-forwarded-host").unwrap())
-        .as_str()
-        .unwrap();
-    let date = req.header("Date").unwrap().as_str().unwrap();
-    let digest = req.header("Digest").unwrap().as_str().unwrap();
-    let content_type = req.header("Content-Type").unwrap().as_str().unwrap();
-    let requested_uri = Url::parse(req.uri())?;
-    let request_path = requested_uri.path();
-    let request_method = req.method().to_string();
-    let body = String::from_utf8(req.body().to_vec())?;
+    let sig_header = header(req, "Signature")?;
+    let hostname = match req.header("Host") {
+        Some(_) => header(req, "Host")?,
+        None => header(req, "x-forwarded-host")?,
+    };
+    let signed_request = SignedRequest::from_request(req);
+    let signature = RequestSignature::parse(&signed_request)
+        .map_err(|e| InboxError::SignatureFailure(e.to_string()))?;
+    let body = String::from_utf8(req.body().to_vec())
+        .map_err(|e| InboxError::BadJson(e.to_string()))?;
     //let body = String::from_utf8_lossy(req.body()).to_string();
 
     // tracing::debug!("sig_header: {sig_header}");
     // tracing::debug!("hostname: {hostname}");
-    // tracing::debug!("date: {date}");
-    // tracing::debug!("digest: {digest}");
-    // tracing::debug!("content-type: {content_type}");
-    // tracing::debug!("request_path: {request_path}");
-    // tracing::debug!("request_method: {request_method}");
     // tracing::debug!("request_body: {body}");
 
     //let obj: Activity<Value> = serde_json::from_str(&body)?;
-    let body_value: Value = serde_json::from_str(&body)?;
-    let activity_type = body_value.get("type").unwrap().as_str().unwrap();
-    let actor_url_str = body_value.get("actor").unwrap().as_str().unwrap();
-
-    if body_value.get("object").unwrap().is_string() {
-        tracing::debug!("{}", body_value.get("object").unwrap());
+    let body_value: Value = serde_json::from_str(&body)
+        .map_err(|e| InboxError::BadJson(e.to_string()))?;
+    let actor_url_str = body_value
+        .get("actor")
+        .and_then(|x| x.as_str())
+        .ok_or(InboxError::BadJson("actor is missing".to_string()))?;
+
+    let sender_actor_url = ActorUrl::new(actor_url_str.to_string())
+        .map_err(|_| InboxError::BadJson("actor is not a url".to_string()))?;
+    let sender_domain = sender_actor_url
+        .0
+        .as_ref()
+        .and_then(|x| x.host_str())
+        .unwrap_or_default()
+        .to_string();
+
+    if DomainBlock::is_suspended(sender_domain.as_str()).await? {
+        return Err(InboxError::BlockedDomain(sender_domain).into());
     }
 
-    fn self_delete(body_value: Value, actor_url_str: String) -> bool {
-        if body_value.get("object").unwrap().is_string() {
-            if body_value.get("object").unwrap().to_string() == actor_url_str {
-                return true;
-            };
-        };
-        false
+    fn self_delete(body_value: &Value, actor_url_str: &str) -> bool {
+        body_value.get("type").and_then(|x| x.as_str()) == Some("Delete")
+            && body_value.get("object").and_then(|x| x.as_str())
+                == Some(actor_url_str)
     }
 
-    if self_delete(body_value.clone(), actor_url_str.to_string()) {
+    if self_delete(&body_value, actor_url_str) {
         tracing::trace!("Delete Self Signal");
         ActivityLog::put(
             sig_header.to_string(),
@@ -143,121 +258,232 @@ pub async fn validate_signature(
             body,
             None,
         )
-        .await
-        .unwrap();
+        .await?;
 
         return Ok(ValidationResult::DeleteSelf);
     }
 
-    let sender_actor_url = ActorUrl::new(actor_url_str.to_string()).unwrap();
+    let sender_account =
+        signing_account(&sender_actor_url, &sender_domain).await?;
+
+    // Activity proven by its actor is authentic wherever it comes from,
+    // e.g. relayed by another server. FEP-8b32
+    // Mastodon forwards replies and deletes with the actor's Linked Data
+    // signature. The HTTP signature is then by the forwarding actor and is
+    // still verified, with the forwarding actor's key.
+    let forwarder_url = signature
+        .key_id()
+        .filter(|x| !x.starts_with(actor_url_str))
+        .and_then(|x| ActorUrl::new(x.split('#').next()?.to_string()).ok());
+    let proven = match forwarder_url {
+        Some(_) => {
+            proven_by_actor(&body_value, &sender_account, &sender_actor_url)
+                .await
+        }
+        None => false,
+    };
 
-    // If this sender_actor_url is already exist,
-    // SQL CALL. Keep eyes on it. SQL call is expensive as of August 2024.
-    // Todo: Reduce this sql call if possible.
-    let sender_account = match MAccount::is_actor_exist(
-        sender_actor_url.to_owned().to_string(),
-    )
-    .await?
-    {
-        Some(maccount) => maccount,
-        None => {
-            let sender_actor = sender_actor_url.actor().await?.clone();
-            let site_domain = Setting::domain().await;
-            let sender_domain = sender_actor_url
-                .clone()
+    let (signer_account, signer_url) = match (proven, forwarder_url) {
+        (true, Some(url)) => {
+            let domain = url
                 .0
-                .unwrap()
-                .host()
-                .unwrap()
+                .as_ref()
+                .and_then(|x| x.host_str())
+                .unwrap_or_default()
                 .to_string();
-            if sender_domain != site_domain {
-                tracing::trace!("SQL CALL to store actor");
-                sender_actor.store().await?;
+            if DomainBlock::is_suspended(domain.as_str()).await? {
+                return Err(InboxError::BlockedDomain(domain).into());
             }
-
-            MAccount::get(sender_actor_url).await?
+            (signing_account(&url, &domain).await?, url)
         }
+        _ => (sender_account.to_owned(), sender_actor_url),
+    };
+    let signer_account = match verify_http_signature(
+        &signature,
+        &signed_request,
+        signer_account,
+        signer_url,
+    )
+    .await?
+    {
+        Some(account) => account,
+        None => return Ok(ValidationResult::Invalid),
+    };
+    let sender_account = match proven {
+        true => sender_account,
+        false => signer_account,
     };
 
-    let public_key_string = sender_account.public_key.as_str();
+    ACTOR_ACCOUNT.set(sender_account.to_owned());
+    tracing::trace!("ACTOR_ACCOUNT loaded at global space");
+    ActivityLog::put(
+        sig_header.to_string(),
+        hostname.to_string(),
+        Some(req.method().to_owned()),
+        body,
+        None,
+    )
+    .await?;
+    Ok(ValidationResult::Valid(sender_account))
+}
 
-    fn parse_sig_header(query: &str) -> HashMap<String, String> {
-        fn rem_first_and_last(value: &str) -> &str {
-            let mut chars = value.chars();
-            chars.next();
-            chars.next_back();
-            chars.as_str()
+/// Account of the actor signing the request. Unknown remote actor is fetched
+/// and stored.
+async fn signing_account(
+    actor_url: &ActorUrl,
+    domain: &str,
+) -> Result<MAccount> {
+    // If this actor_url is already exist,
+    // SQL CALL. Keep eyes on it. SQL call is expensive as of August 2024.
+    // Todo: Reduce this sql call if possible.
+    if let Some(maccount) =
+        MAccount::is_actor_exist(actor_url.to_string()).await?
+    {
+        return Ok(maccount);
+    }
+    let actor = match actor_url.actor().await {
+        Ok(actor) => actor,
+        Err(e) => {
+            let reason = format!("actor can not be fetched: {e}");
+            return Err(InboxError::SignatureFailure(reason).into());
         }
-        query
-            .split(',')
-            .filter_map(|s| {
-                s.split_once('=').and_then(|t| {
-                    Some((t.0.to_owned(), rem_first_and_last(t.1).to_owned()))
-                })
-            })
-            .collect()
+    };
+    let site_domain = Setting::domain().await;
+    if domain != site_domain {
+        tracing::trace!("SQL CALL to store actor");
+        actor.store().await?;
     }
-    let sig_header_map = parse_sig_header(sig_header);
-    let _key_id = sig_header_map.get("keyId").unwrap();
-    let signature = sig_header_map.get("signature").unwrap();
-    let _headers = sig_header_map.get("headers").unwrap();
-    // TODO: Check algorithm
-    let _algorithm = sig_header_map.get("algorithm").unwrap();
-
-    let decoded_signature =
-        general_purpose::STANDARD.decode(signature).unwrap();
-
-    // TODO: Generate signature string based on actual headers info got from sig_headers
-    // See this: https://blog.joinmastodon.org/2018/07/how-to-make-friends-and-verify-requests/
-    let signature_string = format!(
-        "(request-target): {} {}\nhost: {}\ndate: {}\ndigest: {}\ncontent-type: {}",
-        request_method.to_lowercase(),
-        request_path,
-        hostname,
-        date,
-        digest,
-        content_type,
+    MAccount::get(actor_url.to_owned()).await
+}
+
+/// Sender account when the request is signed by its key.
+/// None when the signature does not match.
+async fn verify_http_signature(
+    signature: &RequestSignature,
+    signed_request: &SignedRequest,
+    sender_account: MAccount,
+    sender_actor_url: ActorUrl,
+) -> Result<Option<MAccount>> {
+    let now = chrono::Utc::now().timestamp();
+    let mut verified = signature.verify(
+        signed_request,
+        sender_account.public_key.as_str(),
+        now,
     );
 
-    // tracing::debug!("--> {signature_string}");
-
-    let public_key = RsaPublicKey::from_public_key_pem(public_key_string)
-        .expect("RsaPublicKey creation failed");
-    let verifying_key_openssl: VerifyingKey<Sha256> =
-        VerifyingKey::new(public_key.clone());
-    let t = Signature::try_from(decoded_signature.as_slice()).unwrap();
-    let valid_key = verifying_key_openssl
-        .verify(signature_string.as_bytes(), &t)
-        .is_ok();
-    // TODO: Check the signed request was made within the past 12 hours
-    // https://docs.joinmastodon.org/spec/security/#http-verify
-    let valid_date = true;
-
-    if valid_key && valid_date {
-        match ACTOR_ACCOUNT.set(sender_account.to_owned()) {
-            Ok(_) => {
-                tracing::trace!("ACTOR_ACCOUNT loaded at global space")
-            }
-            Err(e) => {
-                tracing::error!("ACTOR_ACCOUNT sentting error {e:?}")
-            }
-        };
-        ActivityLog::put(
-            sig_header.to_string(),
-            hostname.to_string(),
-            Some(req.method().to_owned()),
-            body,
-            None,
-        )
+    // Actor may have rotated its key. Refetch the actor and try once more.
+    let sender_account = if verified == Err(SignatureError::BadSignature)
+        && !sender_account.local()
+    {
+        tracing::debug!("Refetching actor {} for its key", sender_actor_url);
+        let refreshed_account = refetched_account(&sender_actor_url).await?;
+        verified = signature.verify(
+            signed_request,
+            refreshed_account.public_key.as_str(),
+            now,
+        );
+        refreshed_account
+    } else {
+        sender_account
+    };
+
+    match verified {
+        Ok(()) => Ok(Some(sender_account)),
+        Err(SignatureError::BadSignature) => Ok(None),
+        Err(e) => {
+            tracing::debug!("Signature verification failed: {e}");
+            Err(InboxError::SignatureFailure(e.to_string()).into())
+        }
+    }
+}
+
+/// Whether the forwarded document carries the sender's integrity proof or
+/// Linked Data signature. Failures to verify them, e.g. the sender's server
+/// being down, leave the request to its HTTP signature.
+async fn proven_by_actor(
+    document: &Value,
+    sender_account: &MAccount,
+    sender_actor_url: &ActorUrl,
+) -> bool {
+    if document.get("proof").is_some() {
+        let actor_url = sender_actor_url.to_string();
+        match AccountKey::verify_proof(document, &actor_url).await {
+            Ok(()) => return true,
+            Err(e) => tracing::debug!("Integrity proof is not valid: {e}"),
+        }
+    }
+    if document.get("signature").is_none() {
+        return false;
+    }
+    match verify_ld_signature(document, sender_account, sender_actor_url)
         .await
-        .unwrap();
-        return Ok(ValidationResult::Valid(sender_account));
+    {
+        Ok(verified) => verified,
+        Err(e) => {
+            tracing::debug!("Linked Data signature is not verified: {e}");
+            false
+        }
     }
+}
 
-    Ok(ValidationResult::Invalid)
+/// Whether the document carries a Linked Data signature by the sender's
+/// key. See [`ld_signature`].
+async fn verify_ld_signature(
+    document: &Value,
+    sender_account: &MAccount,
+    sender_actor_url: &ActorUrl,
+) -> Result<bool, InboxError> {
+    let creator = ld_signature::creator(document).unwrap_or_default();
+    let owner = creator.split('#').next().unwrap_or_default();
+    if owner != sender_actor_url.to_string() {
+        tracing::debug!("{creator} is not a key of {sender_actor_url}");
+        return Ok(false);
+    }
+    let mut verified =
+        ld_signature::verify(document, sender_account.public_key.as_str());
+
+    // Actor may have rotated its key. Refetch the actor and try once more.
+    if verified == Err(LdSignatureError::BadSignature)
+        && !sender_account.local()
+    {
+        tracing::debug!("Refetching actor {} for its key", sender_actor_url);
+        let refreshed_account =
+            refetched_account(sender_actor_url).await.map_err(|e| {
+                let reason = format!("actor can not be refetched: {e}");
+                InboxError::SignatureFailure(reason)
+            })?;
+        verified = ld_signature::verify(
+            document,
+            refreshed_account.public_key.as_str(),
+        );
+    }
+
+    match verified {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            tracing::debug!("Linked Data signature is not valid: {e}");
+            Ok(false)
+        }
+    }
+}
+
+/// Account of the remote actor refetched for its current keys.
+async fn refetched_account(actor_url: &ActorUrl) -> Result<MAccount> {
+    actor_url.actor().await?.store().await?;
+    MAccount::get(actor_url.to_owned()).await
+}
+
+/// Value of the request header.
+fn header<'a>(req: &'a Request, name: &str) -> Result<&'a str, InboxError> {
+    req.header(name)
+        .and_then(|x| x.as_str())
+        .ok_or(InboxError::MissingHeader(name.to_string()))
 }
 
 /// Send ActivityPub Object/Message
+///
+/// Activity is queued and the worker delivers it.
+/// Returns 202 once it is queued.
 pub async fn post_activity<T>(
     actor: MAccount,
     activity: Activity<T>,
@@ -270,124 +496,202 @@ where
     let sender_actor_url_string = actor.actor_url.to_string();
 
     let recipient_actor_url_string = match activity.activity_type {
-        ActivityType::Follow => activity.activity_object.to_string(),
-        //ActivityType::Undo => {}
-        ActivityType::Accept => {
+        ActivityType::Follow | ActivityType::Block => {
+            activity.activity_object.to_string()
+        }
+        ActivityType::Undo => {
             let a = activity.activity_object.to_string();
-            let b: Value = serde_json::from_str(a.as_str()).unwrap();
-            let c = b.get("actor").unwrap().as_str().unwrap();
-            c.to_string()
+            let b: Value = serde_json::from_str(a.as_str())?;
+            b.get("object")
+                .and_then(|x| x.as_str())
+                .ok_or(Error::msg("Undone activity has no object"))?
+                .to_string()
         }
-        ob_type => {
-            return Err(anyhow::Error::msg(format!(
-                "UNKOWN ObjectType {:?}",
-                ob_type
-            )))
+        ActivityType::Accept | ActivityType::Reject => {
+            let a = activity.activity_object.to_string();
+            let b: Value = serde_json::from_str(a.as_str())?;
+            b.get("actor")
+                .and_then(|x| x.as_str())
+                .ok_or(Error::msg("Answered activity has no actor"))?
+                .to_string()
+        }
+        // Addressed by to and cc.
+        _ => {
+            post_activity_to_audience(actor, activity).await?;
+            return Ok(202u16);
         }
     };
 
     tracing::debug!(sender_actor_url_string);
     tracing::debug!(recipient_actor_url_string);
 
-    let sender_actor_url = actor.actor_url;
-    let sender_private_key_pem = actor.private_key.clone().unwrap();
-
-    let recipient_actor_url =
-        ActorUrl::new(recipient_actor_url_string).unwrap();
+    let recipient_actor_url = ActorUrl::new(recipient_actor_url_string)?;
     let recipient_account = MAccount::get(recipient_actor_url).await?;
-    //let date = get_current_time_for_signing();
-    let date = get_current_time_in_rfc_1123();
-    let accept_content_type =
-        "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"".to_string();
-    let content_type = "application/activity+json";
 
-    let request_body = serde_json::to_string(&activity).unwrap();
+    queue_delivery(&actor, recipient_account, &activity).await?;
+    Ok(202u16)
+}
 
-    // tracing::trace!("me -> {me}");
-    // tracing::trace!("my_actor -> {my_actor}");
-    // tracing::trace!("recipient_actor -> {recipient_actor}");
-    // tracing::trace!("recipient_server -> {recipient_server}");
-    // tracing::trace!("private_key_pem -> {private_key_pem}");
-    // tracing::trace!("date -> {date}");
-    // tracing::trace!("content_type -> {content_type}");
+/// Send ActivityPub Object/Message to its audience.
+///
+/// `to` and `cc` are expanded to inbox urls. See [`Audience`].
+/// Returns the number of queued deliveries.
+pub async fn post_activity_to_audience<T>(
+    actor: MAccount,
+    activity: Activity<T>,
+) -> Result<usize>
+where
+    T: Debug + Serialize + ToString + Execute,
+{
+    tracing::debug!("<========= POSTING ACTIVITY TO AUDIENCE =========>");
+
+    let activity_val = serde_json::to_value(&activity)?;
+    let inboxes = Audience::inboxes(&actor, &activity_val).await?;
+    let request_body = activity_body(&actor, activity_val).await?;
+
+    for inbox_url in inboxes.iter() {
+        Delivery::enqueue(
+            &actor,
+            inbox_url.to_owned(),
+            activity.id.to_owned(),
+            request_body.to_owned(),
+        )
+        .await?;
+    }
 
-    let mut hasher = Sha256::new();
-    hasher.update(request_body.clone());
-    let digest = format!(
-        "SHA-256={}",
-        general_purpose::STANDARD.encode(hasher.finalize())
-    );
+    Ok(inboxes.len())
+}
 
-    let site_domain = Setting::domain().await;
+/// Queue activity to recipient's inbox.
+pub async fn queue_delivery<T>(
+    actor: &MAccount,
+    recipient_account: MAccount,
+    activity: &Activity<T>,
+) -> Result<()>
+where
+    T: Debug + Serialize + ToString + Execute,
+{
+    let inbox_url = recipient_account.inbox_url.ok_or(Error::msg(format!(
+        "{} has no inbox",
+        recipient_account.actor_url
+    )))?;
+    let request_body =
+        activity_body(actor, serde_json::to_value(activity)?).await?;
+
+    Delivery::enqueue(actor, inbox_url, activity.id.to_owned(), request_body)
+        .await
+}
 
-    let hostname = recipient_account.account_uri.domain.unwrap_or(site_domain);
-    let inbox_url = recipient_account.inbox_url.unwrap();
-    let inbox_path_url = url::Url::parse(inbox_url.as_str()).unwrap();
-    let inbox_path = inbox_path_url.path();
-    let signature_string = format!(
-        "(request-target): post {}\nhost: {}\ndate: {}\ndigest: {}\ncontent-type: {}",
-        inbox_path, hostname, date, digest, content_type
-    );
+/// Activity json with an integrity proof by the actor. FEP-8b32
+async fn activity_body(
+    actor: &MAccount,
+    mut activity: Value,
+) -> Result<String> {
+    // Proof needs the data integrity context.
+    let context = match activity.get("@context") {
+        Some(Value::Array(a)) => a.to_owned(),
+        Some(Value::String(c)) => vec![Value::String(c.to_owned())],
+        _ => vec![Value::String(
+            "https://www.w3.org/ns/activitystreams".to_string(),
+        )],
+    };
+    let di_context = Value::String(DATA_INTEGRITY_CONTEXT.to_string());
+    if !context.contains(&di_context) {
+        activity["@context"] =
+            Value::Array([context, vec![di_context]].concat());
+    }
 
-    // The signature string is constructed using the values of the HTTP headers defined in headers, joined by newlines. Typically, you will want to include the request target, as well as the host and the date. Mastodon assumes Date: header if none are provided. For the above GET request, to generate a Signature: with headers="(request-target) host date"
-    // https://github.com/RustCrypto/RSA/issues/341
-    let private_key = RsaPrivateKey::from_pkcs8_pem(&sender_private_key_pem)
-        .expect("RsaPrivateKey creation failed");
-    let signing_key: SigningKey<Sha256> = SigningKey::new(private_key);
-    let signature = <SigningKey<Sha256> as Signer<Signature>>::sign(
-        &signing_key,
-        signature_string.as_bytes(),
-    );
-    let encoded_signature =
-        general_purpose::STANDARD.encode(signature.to_bytes().as_ref());
+    let signed = AccountKey::prove(actor, &activity).await?;
+    Ok(serde_json::to_string(&signed)?)
+}
 
-    let sig_header = format!(
-        r#"keyId="{}#main-key",algorithm="rsa-sha256",headers="(request-target) host date digest content-type",signature="{}""#,
-        sender_actor_url_string, encoded_signature
-    );
+/// Sign and post activity json to the inbox.
+///
+/// Double-knocks: RFC 9421 first, draft-cavage on 401.
+/// See [`SignatureScheme`].
+/// Each attempt is recorded in activity_log with its response status.
+pub async fn deliver(
+    actor: &MAccount,
+    inbox_url: &str,
+    request_body: String,
+) -> Result<u16> {
+    let sender_private_key_pem = actor
+        .private_key
+        .clone()
+        .ok_or(Error::msg("Sender has no private key"))?;
+    let accept_content_type =
+        "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"".to_string();
+    let content_type = "application/activity+json";
 
-    tracing::debug!("!!!!!!!!!!!!!!!!!!!!!!!");
-    tracing::debug!(signature_string);
-    tracing::debug!(sig_header);
-
-    let request = RequestBuilder::new(Method::Post, inbox_url)
-        .header("Date", date)
-        .header("Signature", sig_header.clone())
-        .header("Digest", digest)
-        .header("Content-Type", content_type)
-        .header("Accept", &accept_content_type)
-        .body(request_body.to_string())
-        .build();
-
-    let response: Response = http::send(request).await?;
-    let status = response.status();
-
-    match status {
-        200u16 | 202u16 => {
-            tracing::debug!("Activity posted({})", 200)
+    let inbox_path_url = url::Url::parse(inbox_url)?;
+    let hostname = inbox_path_url.host_str().unwrap_or_default().to_string();
+
+    let knocks = SignatureScheme::knocks(hostname.as_str()).await?;
+    for (i, scheme) in knocks.iter().enumerate() {
+        let signature_headers = signature_headers(
+            actor.actor_url.to_string().as_str(),
+            &sender_private_key_pem,
+            &inbox_path_url,
+            Some((&request_body, content_type)),
+            *scheme,
+        )?;
+        let sig_header = signature_headers
+            .iter()
+            .find(|(k, _)| k == "Signature")
+            .map(|(_, v)| v.to_owned())
+            .unwrap_or_default();
+
+        let mut request_builder = RequestBuilder::new(Method::Post, inbox_url);
+        for (key, value) in signature_headers.iter() {
+            request_builder.header(key, value);
         }
-        s => {
-            tracing::error!("Activity posted but something went wrong({})", s);
-            tracing::error!("activity: {:?}", activity);
-            tracing::error!("request_body: {:?}", request_body.to_string());
+        let request = request_builder
+            .header("Content-Type", content_type)
+            .header("Accept", &accept_content_type)
+            .body(request_body.to_string())
+            .build();
+
+        let result: Result<Response, _> = http::send(request).await;
+        let status = match result.as_ref() {
+            Ok(response) => response.status().to_string(),
+            Err(e) => format!("error: {e}"),
+        };
+
+        ActivityLog::put(
+            sig_header.to_string(),
+            hostname.to_string(),
+            Some(Method::Post),
+            request_body.to_owned(),
+            Some(status.to_owned()),
+        )
+        .await?;
+
+        match result.as_ref().map(|x| *x.status()) {
+            Ok(401u16) if i + 1 < knocks.len() => {
+                tracing::debug!("{} rejected {} signature", hostname, scheme);
+                continue;
+            }
+            Ok(200u16..=299u16) => {
+                tracing::debug!("Activity posted({})", status);
+                SignatureScheme::accepted(hostname.as_str(), *scheme).await?;
+            }
+            _ => {
+                tracing::error!(
+                    "Activity posted but something went wrong({})",
+                    status
+                );
+                tracing::error!("request_body: {:?}", request_body.to_string());
+            }
         }
-    }
 
-    //if status == 202u16 { // Only 202? or
-    ActivityLog::put(
-        sig_header.to_string(),
-        hostname.to_string(),
-        Some(Method::Post),
-        request_body,
-        Some(status.to_string()),
-    )
-    .await
-    .unwrap();
-    //}ActivityLog
+        return Ok(*result?.status());
+    }
 
-    Ok(*status)
+    Err(Error::msg(format!("No signature scheme to post to {}", hostname)))
 }
 
+/// Signed GET request to the url. Response other than 200 is an error,
+/// 410 Gone being [`FetchError::Gone`].
 pub async fn get_fediverse(
     request_url: Url,
     sender: MAccount,
@@ -397,36 +701,62 @@ pub async fn get_fediverse(
         request_url.to_string()
     );
 
-    let sender_priv_key = sender.private_key.unwrap();
-    //let date = get_current_time_for_signing();
-    let date = get_current_time_in_rfc_1123();
+    let sender_priv_key = sender
+        .private_key
+        .clone()
+        .ok_or(Error::msg("Sender has no private key"))?;
 
     let content_type = "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"";
 
-    let signature = create_get_signrature(
-        sender.actor_url.to_string().as_str(), // Todo: Polish this
-        &sender_priv_key,
-        &request_url.to_string(),
-        &date,
-    );
-
-    let request = Request::builder()
-        .method(Method::Get)
-        .uri(request_url.to_owned())
-        .header("Date", date)
-        .header("Signature", &signature)
-        .header("Accept", content_type)
-        .build();
+    let hostname = request_url.host_str().unwrap_or_default().to_string();
+    let knocks = SignatureScheme::knocks(hostname.as_str()).await?;
+    let mut signature = String::new();
+    let mut response: Option<Response> = None;
+    for (i, scheme) in knocks.iter().enumerate() {
+        let signature_headers = signature_headers(
+            sender.actor_url.to_string().as_str(), // Todo: Polish this
+            &sender_priv_key,
+            &request_url,
+            None,
+            *scheme,
+        )?;
+        signature = signature_headers
+            .iter()
+            .find(|(k, _)| k == "Signature")
+            .map(|(_, v)| v.to_owned())
+            .unwrap_or_default();
+
+        let mut request_builder = Request::builder();
+        request_builder.method(Method::Get).uri(request_url.to_owned());
+        for (key, value) in signature_headers.iter() {
+            request_builder.header(key, value);
+        }
+        let request = request_builder.header("Accept", content_type).build();
 
-    let response: Response = spin_sdk::http::send(request).await.unwrap();
+        let r: Response = spin_sdk::http::send(request).await?;
+        match *r.status() {
+            401 if i + 1 < knocks.len() => {
+                tracing::debug!("{} rejected {} signature", hostname, scheme);
+                continue;
+            }
+            200 => {
+                SignatureScheme::accepted(hostname.as_str(), *scheme).await?;
+            }
+            _ => {}
+        }
+        response = Some(r);
+        break;
+    }
+    let response = response.ok_or(Error::msg(format!(
+        "No signature scheme to get from {}",
+        hostname
+    )))?;
 
     match response.status() {
         200 => {}
-        410 =>
-        // 401 is Gone
-        {
-            tracing::trace!("get_fediverse request gets 401.");
-            tracing::trace!("Todo: if this request goes to actor, remove/delete this actor from account table.");
+        410 => {
+            tracing::debug!("{} is gone", request_url);
+            return Err(FetchError::Gone(request_url.to_string()).into());
         }
         r_code => {
             tracing::error!(
@@ -446,16 +776,78 @@ pub async fn get_fediverse(
     Ok(response)
 }
 
+/// Headers signing the request to url with the scheme.
+///
+/// `body` is the body and its content type of Post request. Get request
+/// goes without it.
+pub fn signature_headers(
+    sender_actor_url: &str,
+    sender_private_key_pem: &str,
+    url: &Url,
+    body: Option<(&String, &str)>,
+    scheme: Scheme,
+) -> Result<Vec<(String, String)>> {
+    let date = get_current_time_in_rfc_1123();
+    let mut headers = vec![("Date".to_string(), date.to_owned())];
+
+    match (scheme, body) {
+        (Scheme::Cavage, Some((message, content_type))) => {
+            let (signature, digest) = create_post_signrature(
+                sender_actor_url,
+                sender_private_key_pem,
+                url.as_str(),
+                message,
+                &date,
+                content_type,
+            );
+            headers.push(("Digest".to_string(), digest));
+            headers.push(("Signature".to_string(), signature));
+        }
+        (Scheme::Cavage, None) => {
+            let signature = create_get_signrature(
+                sender_actor_url,
+                sender_private_key_pem,
+                url.as_str(),
+                &date,
+            );
+            headers.push(("Signature".to_string(), signature));
+        }
+        (Scheme::Rfc9421, Some((message, _))) => {
+            let (signature_input, signature, digest) =
+                create_post_message_signature(
+                    sender_actor_url,
+                    sender_private_key_pem,
+                    url,
+                    message,
+                )?;
+            headers.push(("Content-Digest".to_string(), digest));
+            headers.push(("Signature-Input".to_string(), signature_input));
+            headers.push(("Signature".to_string(), signature));
+        }
+        (Scheme::Rfc9421, None) => {
+            let (signature_input, signature) = create_get_message_signature(
+                sender_actor_url,
+                sender_private_key_pem,
+                url,
+            )?;
+            headers.push(("Signature-Input".to_string(), signature_input));
+            headers.push(("Signature".to_string(), signature));
+        }
+    }
+
+    Ok(headers)
+}
+
 /// Signature string required for Post request.  
 pub fn create_post_signrature(
-    sender_inbox_url: &str,
+    sender_actor_url: &str,
     sender_private_key_pem: &str,
     recipient_inbox_url: &str,
     message: &String,
     date_in_rfc_1123: &String,
     content_type: &str,
 ) -> (String, String) {
-    let sender = Url::parse(sender_inbox_url).unwrap();
+    let sender = Url::parse(sender_actor_url).unwrap();
     let recipient = Url::parse(recipient_inbox_url).unwrap();
     let private_key = RsaPrivateKey::from_pkcs8_pem(&sender_private_key_pem)
         .expect("RsaPrivateKey creation failed");
@@ -469,8 +861,8 @@ pub fn create_post_signrature(
 
     let signature_string = format!(
         "(request-target): post {}\nhost: {}\ndate: {}\ndigest: {}\ncontent-type: {}",
-        sender.path(),
-        sender.domain().unwrap(),
+        recipient.path(),
+        recipient.host_str().unwrap(),
         date_in_rfc_1123,
         digest,
         content_type
@@ -529,3 +921,44 @@ pub fn create_get_signrature(
 
     sig_header
 }
+
+/// Signature-Input, Signature and Content-Digest of RFC 9421 for Post
+/// request.
+pub fn create_post_message_signature(
+    sender_actor_url: &str,
+    sender_private_key_pem: &str,
+    recipient_inbox_url: &Url,
+    message: &String,
+) -> Result<(String, String, String)> {
+    let digest = http_signature::content_digest(message.as_bytes());
+    let request = SignedRequest::from_url("POST", recipient_inbox_url)
+        .header("content-digest", &digest)
+        .body(message.as_bytes());
+    let (signature_input, signature) = http_signature::sign_message(
+        &request,
+        &["@method", "@target-uri", "content-digest"],
+        format!("{}#main-key", sender_actor_url).as_str(),
+        sender_private_key_pem,
+        chrono::Utc::now().timestamp(),
+    )?;
+
+    Ok((signature_input, signature, digest))
+}
+
+/// Signature-Input and Signature of RFC 9421 for Get request.
+pub fn create_get_message_signature(
+    sender_actor_url: &str,
+    sender_private_key_pem: &str,
+    request_url: &Url,
+) -> Result<(String, String)> {
+    let request = SignedRequest::from_url("GET", request_url);
+    let signature = http_signature::sign_message(
+        &request,
+        &["@method", "@target-uri"],
+        format!("{}#main-key", sender_actor_url).as_str(),
+        sender_private_key_pem,
+        chrono::Utc::now().timestamp(),
+    )?;
+
+    Ok(signature)
+}
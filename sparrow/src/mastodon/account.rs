@@ -144,8 +144,17 @@ pub struct Account {
     /// followers_url of Account. Not serializable
     #[serde(skip_serializing, skip_deserializing)]
     pub followers_url: Option<String>,
+    /// featured_collection_url of Account. Not serializable
+    #[serde(skip_serializing, skip_deserializing)]
+    pub featured_collection_url: Option<String>,
     #[serde(skip_serializing, skip_deserializing)]
     pub indexable: Option<bool>,
+    /// alsoKnownAs(actor urls) of Account. Not serializable
+    #[serde(skip_serializing, skip_deserializing)]
+    pub also_known_as: Vec<String>,
+    /// rowid of the account this account has moved to. Not serializable
+    #[serde(skip_serializing, skip_deserializing)]
+    pub moved_to_account_id: Option<i64>,
     /// Only for credential account
     /// CredentialAccount entity attributes
     /// An extra attribute that contains source values to be used with API methods that verify credentials and update credentials.
@@ -190,6 +199,37 @@ impl Account {
         self.account_uri.domain.is_none()
     }
 
+    /// Replace alsoKnownAs(aliases) of this account.  
+    pub async fn set_aliases(&self, aliases: Vec<String>) -> Result<()> {
+        let also_known_as = match aliases.is_empty() {
+            true => None,
+            false => Some(serde_json::to_string(&aliases)?),
+        };
+        TAccount::set_also_known_as(self.uid.to_string(), also_known_as).await
+    }
+
+    /// Mark this account as moved to target account.  
+    pub async fn move_to(&self, target: &MAccount) -> Result<()> {
+        let target_taccount = TAccount::get(("uid".to_string(), target.uid.to_string())).await?;
+        let rowid = match target_taccount.last() {
+            Some(t) => t.rowid,
+            None => return Err(Error::msg(format!("Unknown account {}", target.uid))),
+        };
+        TAccount::set_moved_to(self.uid.to_string(), Some(rowid)).await
+    }
+
+    /// Account this account has moved to.  
+    pub async fn moved_to(&self) -> Result<Option<MAccount>> {
+        let rowid = match self.moved_to_account_id {
+            Some(r) => r,
+            None => return Ok(None),
+        };
+        match TAccount::fr_rowid(rowid).await? {
+            Some(t) => Ok(Some(MAccount::get(t).await?)),
+            None => Ok(None),
+        }
+    }
+
     /// Searching account.  
     /// Mastodon doc: <https://docs.joinmastodon.org/entities/Search/#accounts>
     pub async fn search(st: &String) -> Result<Vec<MAccount>> {
@@ -334,6 +374,18 @@ impl Account {
         }
        
     }
+
+    /// Return MAccount with ActorUrl. If the actor is not in account table yet, fetch and store it first.
+    pub async fn fetch(actor_url: ActorUrl) -> Result<Account> {
+        match Self::is_actor_exist(actor_url.to_string()).await? {
+            Some(a) => Ok(a),
+            None => {
+                let actor = actor_url.actor().await?;
+                actor.store().await?;
+                MAccount::get(actor_url).await
+            }
+        }
+    }
 }
 
 impl TryInto<String> for Account {
@@ -396,6 +448,7 @@ impl Get<TAccount> for Account {
             account_uri,
             display_name: acct_tbl.display_name,
             locked: convert_to_bool(acct_tbl.locked.unwrap_or_default()),
+            fields: acct_tbl.fields.to_owned().and_then(|x| serde_json::from_str(&x).ok()).unwrap_or_default(),
             bot: bot,
             discoverable: convert_to_bool(acct_tbl.discoverable.unwrap_or_default()),
             created_at: DateTime::from_timestamp(
@@ -423,7 +476,12 @@ impl Get<TAccount> for Account {
             shared_inbox_url: acct_tbl.shared_inbox_url,
             following_url: acct_tbl.following_url,
             followers_url: acct_tbl.followers_url,
+            featured_collection_url: acct_tbl.featured_collection_url,
             indexable: Some(convert_to_bool(acct_tbl.indexable.unwrap())),
+            also_known_as: acct_tbl.also_known_as.to_owned().and_then(|x| serde_json::from_str(&x).ok()).unwrap_or_default(),
+            moved: acct_tbl.moved_to_account_id.map(|_| true),
+            moved_to_account_id: acct_tbl.moved_to_account_id,
+            suspended: acct_tbl.suspended_at.map(|_| true),
             ..Default::default()
         };
         
@@ -12,8 +12,9 @@ use url::Url;
 use crate::activitystream::actor::person::Person;
 use crate::mastodon;
 use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::uri::Uri as AccountUri;
 use crate::mastodon::account::Get as _;
-use crate::mastodon::ME_ACCOUNT;
+use crate::mastodon::inbox_error::InboxError;
 use crate::table::account::Account as TAccount;
 use crate::table::actor_json::ActorJson;
 
@@ -34,53 +35,68 @@ impl ActorUrl {
 
     pub async fn actor(&self) -> Result<Person> {
         let ct = "application/activity+json";
-        let actor_url = self.0.as_ref().unwrap().to_owned();
+        let actor_url =
+            self.0.to_owned().ok_or(Error::msg("ActorUrl is empty"))?;
 
-        let me_account = ME_ACCOUNT.get().unwrap().to_owned();
+        let me_account = mastodon::me_account()?;
         let taccount = TAccount::default(); // Todo: Remove this sql call.
-        let response = mastodon::get_fediverse(actor_url, me_account).await?;
+        let response =
+            mastodon::get_fediverse(actor_url.to_owned(), me_account).await?;
 
         let body = response.body();
-        let actor = str::from_utf8(body).unwrap();
+        let actor = str::from_utf8(body)
+            .map_err(|e| InboxError::BadJson(e.to_string()))?;
 
         match response.status().to_owned() {
             200 => {}
-            410 => {
-                // Http response 410 is Gone
-                return Err(anyhow::Error::msg(format!(
-                    "Actor {:?} is gone",
-                    self.0.as_ref().unwrap().to_string()
-                )));
-                // TODO: need to delete from db table?
-            } // Gone.
             404 => {
                 // Actor not found
                 return Err(anyhow::Error::msg(format!(
                     "Actor {:?} NOT FOUND",
-                    self.0.as_ref().unwrap().to_string()
+                    actor_url.to_string()
                 )));
             }
             r => {
-                tracing::debug!(
-                    "Actor {:?} response is {}",
-                    self.0.as_ref().unwrap(),
-                    r
-                );
+                tracing::debug!("Actor {:?} response is {}", actor_url, r);
             }
         }
 
         let headers = response.headers();
-        let actor_value: Value = serde_json::from_str(actor).unwrap();
+        let actor_value: Value = serde_json::from_str(actor)
+            .map_err(|e| InboxError::BadJson(e.to_string()))?;
+
+        // Document of another actor must not be taken for this one.
+        let id = actor_value.get("id").and_then(|x| x.as_str());
+        if id.and_then(|x| x.parse::<Url>().ok()) != Some(actor_url.to_owned())
+        {
+            return Err(InboxError::BadJson(format!(
+                "{:?} is not the id of {}",
+                id, actor_url
+            ))
+            .into());
+        }
 
         // This saves acor to actor_json table
-        ActorJson::put(serde_json::from_str(actor).unwrap()).await?;
+        ActorJson::put(actor_value.to_owned()).await?;
 
         // Convert this to ActivityPub Actor
-        let actor = Person::try_from(actor_value).unwrap();
+        let actor = serde_json::from_value::<Person>(actor_value)
+            .map_err(|e| InboxError::BadJson(e.to_string()))?;
 
         Ok(actor)
     }
 
+    /// ActorUrl from either actor url or account uri(user@domain).
+    pub async fn resolve(handle: &str) -> Result<Self> {
+        if handle.starts_with("https://") || handle.starts_with("http://") {
+            return Self::new(handle.to_string());
+        }
+        let account_uri =
+            AccountUri::try_from(handle.trim_start_matches('@').to_string())
+                .map_err(Error::msg)?;
+        account_uri.actor_url().await
+    }
+
     pub async fn remove(&self) {
         todo!()
     }
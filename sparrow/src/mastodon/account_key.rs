@@ -0,0 +1,212 @@
+//! Ed25519 keys of accounts.
+//!
+//! Local accounts get a key on first use. It signs integrity proofs of
+//! outgoing activities and is published as Multikey in the actor's
+//! `assertionMethod`. Remote actors' Multikeys are stored with the actor and
+//! verify the proofs of incoming activities. See [`integrity_proof`].
+
+use anyhow::{Error, Result};
+use chrono::Utc;
+use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
+use ed25519_dalek::pkcs8::{DecodePrivateKey, EncodePrivateKey};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand::rngs::OsRng;
+use serde_json::Value;
+use url::Url;
+
+use crate::activitystream::actor::person::{Multikey, Person};
+use crate::integrity_proof;
+use crate::keys::{multikey_decode, multikey_encode};
+use crate::mastodon::account::actor_url::ActorUrl;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::setting::Setting;
+use crate::table::account_key::AccountKey as TAccountKey;
+use crate::table::account_key::Get as _;
+
+/// Ed25519 keys of local and remote accounts.
+pub struct AccountKey;
+
+impl AccountKey {
+    /// Ed25519 key id of the actor.
+    pub fn key_id(actor_url: &str) -> String {
+        format!("{}#ed25519-key", actor_url)
+    }
+
+    /// Ed25519 key id and signing key of the local account.
+    /// Generated and stored on first use.
+    pub async fn signing_key(
+        account: &MAccount,
+    ) -> Result<(String, SigningKey)> {
+        let actor_url = account.actor_url.to_string();
+        let key_id = Self::key_id(&actor_url);
+        let stored =
+            TAccountKey::get(("key_id".to_string(), key_id.to_owned()))
+                .await?
+                .into_iter()
+                .find_map(|x| x.private_key);
+        if let Some(pem) = stored {
+            return Ok((key_id, SigningKey::from_pkcs8_pem(&pem)?));
+        }
+        if !account.local() {
+            return Err(Error::msg(format!("{} is not local", actor_url)));
+        }
+
+        tracing::debug!("Generating Ed25519 key of {}", actor_url);
+        let signing_key = SigningKey::generate(&mut OsRng);
+        TAccountKey::new(
+            key_id.to_owned(),
+            actor_url,
+            multikey_encode(&signing_key.verifying_key()),
+            Some(signing_key.to_pkcs8_pem(LineEnding::LF)?.to_string()),
+        )
+        .await?;
+        Ok((key_id, signing_key))
+    }
+
+    /// Multikey of the local account to publish.
+    pub async fn multikey(account: &MAccount) -> Result<Multikey> {
+        let (key_id, signing_key) = Self::signing_key(account).await?;
+        Ok(Multikey::new(
+            key_id,
+            account.actor_url.to_string(),
+            multikey_encode(&signing_key.verifying_key()),
+        ))
+    }
+
+    /// Store Multikeys the remote actor publishes. The actor must be fetched
+    /// from its id. Keys must be on the actor's server and controlled by it.
+    /// Keys of local accounts are never replaced.
+    pub async fn store(person: &Person) -> Result<()> {
+        let local_domain = Setting::domain().await;
+        for key in person.multikeys() {
+            if !storable(&key, &person.id, &local_domain) {
+                tracing::debug!("Ignoring key {} of {}", key.id, person.id);
+                continue;
+            }
+            TAccountKey::new(
+                key.id,
+                key.controller,
+                key.public_key_multibase,
+                None,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Controller and verifying key of the key id. Unknown key's controller
+    /// is fetched.
+    pub async fn verifying_key(
+        key_id: &str,
+    ) -> Result<Option<(String, VerifyingKey)>> {
+        if let Some(key) = Self::stored(key_id).await? {
+            return Ok(Some(key));
+        }
+        let controller = key_id.split('#').next().unwrap_or_default();
+        let person = ActorUrl::new(controller.to_string())?.actor().await?;
+        if person.id != controller {
+            return Err(Error::msg(format!(
+                "{} is not the controller of {}",
+                person.id, key_id
+            )));
+        }
+        person.store().await?;
+        Self::stored(key_id).await
+    }
+
+    /// Document with an integrity proof by the local account.
+    pub async fn prove(account: &MAccount, document: &Value) -> Result<Value> {
+        let (key_id, signing_key) = Self::signing_key(account).await?;
+        let signed =
+            integrity_proof::sign(document, &key_id, &signing_key, Utc::now())?;
+        Ok(signed)
+    }
+
+    /// Verify the integrity proof of the document signed by the actor.
+    pub async fn verify_proof(document: &Value, actor_url: &str) -> Result<()> {
+        let key_id = integrity_proof::verification_method(document)
+            .ok_or(Error::msg("verificationMethod is missing"))?;
+        let (controller, key) = Self::verifying_key(&key_id)
+            .await?
+            .ok_or(Error::msg(format!("{} is not found", key_id)))?;
+        if controller != actor_url {
+            return Err(Error::msg(format!(
+                "{} is not a key of {}",
+                key_id, actor_url
+            )));
+        }
+        integrity_proof::verify(document, &key)?;
+        Ok(())
+    }
+
+    async fn stored(key_id: &str) -> Result<Option<(String, VerifyingKey)>> {
+        let record =
+            TAccountKey::get(("key_id".to_string(), key_id.to_string()))
+                .await?
+                .into_iter()
+                .next();
+        match record {
+            Some(r) => {
+                Ok(Some((r.controller, multikey_decode(&r.public_key)?)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Whether the key published by the actor document with the id may be
+/// stored. The key must be controlled by the document's actor on the same
+/// remote server.
+fn storable(key: &Multikey, person_id: &str, local_domain: &str) -> bool {
+    let host = |x: &str| {
+        Url::parse(x)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+    };
+    let person_host = host(person_id);
+    key.controller == person_id
+        && person_host.is_some()
+        && host(&key.id) == person_host
+        && person_host.as_deref() != Some(local_domain)
+        && multikey_decode(&key.public_key_multibase).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(id: &str, controller: &str) -> Multikey {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        Multikey::new(
+            id.to_string(),
+            controller.to_string(),
+            multikey_encode(&signing_key.verifying_key()),
+        )
+    }
+
+    #[test]
+    fn stores_own_key() {
+        let bob = "https://remote.example/users/bob";
+        let k = key("https://remote.example/users/bob#ed25519-key", bob);
+        assert!(storable(&k, bob, "local.example"));
+    }
+
+    #[test]
+    fn ignores_key_of_another_actor() {
+        let mallory = "https://evil.example/users/mallory";
+        let bob = "https://remote.example/users/bob";
+        let bob_key = "https://remote.example/users/bob#ed25519-key";
+        assert!(!storable(&key(bob_key, bob), mallory, "local.example"));
+        assert!(!storable(&key(bob_key, mallory), mallory, "local.example"));
+
+        let same_host = "https://remote.example/users/mallory";
+        assert!(!storable(&key(bob_key, bob), same_host, "local.example"));
+    }
+
+    #[test]
+    fn ignores_key_of_local_account() {
+        let alice = "https://local.example/users/alice";
+        let k = key("https://local.example/users/alice#ed25519-key", alice);
+        assert!(!storable(&k, alice, "local.example"));
+    }
+}
@@ -0,0 +1,116 @@
+//! Remote inboxes an outgoing activity is delivered to.
+//!
+//! `to`, `cc`, `bto`, `bcc` and `audience` of the activity and its object
+//! are expanded. Public and the actor's followers collection become the
+//! actor's followers, other addresses are actors (e.g. mentioned ones).
+//! Each remote server receives one copy through its shared inbox when it has
+//! one. Suspended accounts, blocked and unavailable domains are skipped.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::activitystream::PUBLIC;
+use crate::mastodon::account::actor_url::ActorUrl;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::domain_block::DomainBlock;
+use crate::mastodon::follow::Follow as MFollow;
+use crate::mastodon::recipient::addresses;
+use crate::mastodon::unavailable_domain::UnavailableDomain;
+
+pub struct Audience;
+
+impl Audience {
+    /// Inbox urls of the activity sent by actor. Without any address, it
+    /// goes to actor's followers.
+    pub async fn inboxes(
+        actor: &MAccount,
+        activity: &Value,
+    ) -> Result<Vec<String>> {
+        let addresses = addresses(activity);
+        let followers_url = actor
+            .followers_url
+            .to_owned()
+            .unwrap_or(format!("{}/followers", actor.actor_url));
+
+        let mut accounts: Vec<MAccount> = Vec::new();
+        let to_followers = addresses.is_empty()
+            || addresses.contains(&followers_url)
+            || addresses.contains(&PUBLIC.to_string());
+        if to_followers {
+            accounts.extend(MFollow::get_follows(actor.uid.to_string()).await?);
+        }
+
+        for address in addresses.iter() {
+            if address == PUBLIC
+                || *address == followers_url
+                || *address == actor.actor_url.to_string()
+            {
+                continue;
+            }
+            if let Some(account) = account_of(address).await? {
+                accounts.push(account);
+            }
+        }
+
+        let mut skipped_domains: HashMap<String, bool> = HashMap::new();
+        let mut inboxes: Vec<String> = Vec::new();
+        for account in accounts.into_iter().filter(|x| !x.local()) {
+            if account.suspended == Some(true) {
+                continue;
+            }
+            let domain = match account.account_uri.domain.to_owned() {
+                Some(d) => d,
+                None => continue,
+            };
+            let skipped = match skipped_domains.get(&domain) {
+                Some(s) => *s,
+                None => {
+                    let s = DomainBlock::is_suspended(domain.as_str()).await?
+                        || UnavailableDomain::is_unavailable(domain.as_str())
+                            .await?;
+                    skipped_domains.insert(domain, s);
+                    s
+                }
+            };
+            if skipped {
+                continue;
+            }
+
+            let inbox = account
+                .shared_inbox_url
+                .filter(|x| !x.is_empty())
+                .or(account.inbox_url);
+            match inbox {
+                Some(i) if !inboxes.contains(&i) => inboxes.push(i),
+                Some(_) => (),
+                None => tracing::debug!("{} has no inbox", account.actor_url),
+            }
+        }
+
+        Ok(inboxes)
+    }
+}
+
+/// Account of the addressed actor. Unknown actor is fetched and stored.
+/// None when the address is not an actor (e.g. other's collection).
+async fn account_of(url: &str) -> Result<Option<MAccount>> {
+    if let Some(account) = MAccount::is_actor_exist(url.to_string()).await? {
+        return Ok(Some(account));
+    }
+    let actor_url = match ActorUrl::new(url.to_string()) {
+        Ok(u) => u,
+        Err(_) => return Ok(None),
+    };
+    match actor_url.actor().await {
+        Ok(actor) => {
+            actor.store().await?;
+            Ok(Some(MAccount::get(actor_url).await?))
+        }
+        Err(e) => {
+            tracing::debug!("{} is not an actor: {e:?}", url);
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Block between two accounts.  
+
+use anyhow::Result;
+
+use crate::mastodon::account::uid::Uid as AccountUid;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::follow::Follow as MFollow;
+use crate::table::block::Block as TBlock;
+
+/// Block
+pub struct Block;
+
+impl Block {
+    /// a blocks b.  
+    /// Follows between a and b are removed in both directions.
+    pub async fn new(uri: String, a: &MAccount, b: &MAccount) -> Result<()> {
+        TBlock::new(uri, a.uid.to_string(), b.uid.to_string()).await?;
+        MFollow::remove(a, b).await?;
+        MFollow::remove(b, a).await
+    }
+
+    /// a unblocks b.
+    pub async fn undo(a: &MAccount, b: &MAccount) -> Result<()> {
+        TBlock::remove(a.uid.to_string(), b.uid.to_string()).await
+    }
+
+    /// Does a block b?
+    pub async fn is_blocking(a: &MAccount, b: &MAccount) -> Result<bool> {
+        let record =
+            TBlock::record(a.uid.to_string(), b.uid.to_string()).await?;
+        Ok(record.is_some())
+    }
+
+    /// Block activity's id from a to b.
+    pub async fn uri(a: &MAccount, b: &MAccount) -> Result<Option<String>> {
+        let record =
+            TBlock::record(a.uid.to_string(), b.uid.to_string()).await?;
+        Ok(record.and_then(|x| x.uri))
+    }
+
+    /// Accounts blocked by given account.
+    pub async fn blocks(a: &MAccount) -> Result<Vec<MAccount>> {
+        let mut maccounts: Vec<MAccount> = Vec::new();
+        for b in TBlock::blocks(a.uid.to_string()).await? {
+            let account =
+                MAccount::get(AccountUid(b.target_account_uid)).await?;
+            maccounts.push(account);
+        }
+        Ok(maccounts)
+    }
+}
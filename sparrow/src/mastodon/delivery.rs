@@ -0,0 +1,163 @@
+//! Outbound delivery queue.
+//!
+//! Activities are queued per recipient inbox and sent by the worker.
+//! Failed deliveries are retried with exponential backoff until the
+//! `delivery_retry_horizon` passes. Each attempt is recorded in
+//! activity_log. Deliveries to unavailable domains are put off with the
+//! longest delay, so they are sent if the domain comes back before the
+//! horizon passes.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::mastodon::account::uid::Uid as AccountUid;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::setting::Setting;
+use crate::mastodon::unavailable_domain::UnavailableDomain;
+use crate::table::delivery::Delivery as TDelivery;
+use crate::utils::get_current_epoch;
+
+/// Retry horizon in seconds when `delivery_retry_horizon` is not set. 2 days.
+const DEFAULT_RETRY_HORIZON: i64 = 172800;
+
+/// Delay before the first retry in seconds. Doubled on each attempt.
+const RETRY_DELAY: i64 = 60;
+
+/// Longest delay between attempts. 6 hours.
+const MAX_RETRY_DELAY: i64 = 21600;
+
+/// Deliveries sent in one worker invocation.
+const BATCH_SIZE: i64 = 50;
+
+/// Delivered and failed deliveries are kept for a week.
+const FINISHED_DELIVERY_AGE: i64 = 604800;
+
+pub struct Delivery;
+
+impl Delivery {
+    /// Queue the activity json to the inbox, signed by actor when sent.
+    pub async fn enqueue(
+        actor: &MAccount,
+        inbox_url: String,
+        activity_id: String,
+        body: String,
+    ) -> Result<()> {
+        TDelivery::new(inbox_url, actor.uid.to_string(), activity_id, body)
+            .await
+    }
+
+    /// Send due deliveries. Returns the number of delivered ones.
+    pub async fn drain() -> Result<usize> {
+        let horizon = Self::horizon().await;
+        let mut senders: HashMap<String, MAccount> = HashMap::new();
+        // Inbox failed in this run. Its later deliveries wait.
+        let mut failed_inboxes: Vec<String> = Vec::new();
+        let mut unavailable: HashMap<String, bool> = HashMap::new();
+        let mut delivered = 0;
+
+        for tdelivery in TDelivery::due(BATCH_SIZE).await? {
+            if failed_inboxes.contains(&tdelivery.inbox_url) {
+                continue;
+            }
+
+            let domain = url::Url::parse(tdelivery.inbox_url.as_str())
+                .ok()
+                .and_then(|x| x.host_str().map(String::from))
+                .unwrap_or_default();
+            let is_unavailable = match unavailable.get(&domain) {
+                Some(u) => *u,
+                None => {
+                    let u = UnavailableDomain::is_unavailable(&domain).await?;
+                    unavailable.insert(domain.to_owned(), u);
+                    u
+                }
+            };
+            if is_unavailable {
+                let status = "unavailable domain".to_string();
+                let expired = get_current_epoch() + MAX_RETRY_DELAY
+                    > tdelivery.created_at + horizon;
+                if expired {
+                    TDelivery::fail(tdelivery.uid, status).await?;
+                } else {
+                    TDelivery::retry(tdelivery.uid, MAX_RETRY_DELAY, status)
+                        .await?;
+                }
+                continue;
+            }
+
+            if !TDelivery::lock(tdelivery.uid.to_owned()).await? {
+                continue;
+            }
+            let attempts = tdelivery.attempts + 1;
+
+            let sender = match senders.get(&tdelivery.account_uid) {
+                Some(a) => a.to_owned(),
+                None => {
+                    let uid = AccountUid(tdelivery.account_uid.to_owned());
+                    let a = MAccount::get(uid).await?;
+                    senders.insert(a.uid.to_string(), a.clone());
+                    a
+                }
+            };
+
+            let result = crate::mastodon::deliver(
+                &sender,
+                tdelivery.inbox_url.as_str(),
+                tdelivery.body,
+            )
+            .await;
+
+            let (status, retryable, down) = match result {
+                Ok(s) if (200..300).contains(&s) => {
+                    TDelivery::delivered(tdelivery.uid, s.to_string()).await?;
+                    UnavailableDomain::delivered(&domain).await?;
+                    delivered += 1;
+                    continue;
+                }
+                // Client errors won't be fixed by retrying.
+                Ok(s) => (
+                    s.to_string(),
+                    !(400..500).contains(&s) || [401, 408, 429].contains(&s),
+                    s >= 500,
+                ),
+                // Connection error.
+                Err(e) => (format!("{e}"), true, true),
+            };
+
+            tracing::error!(
+                "Delivery {} to {} failed({}) at attempt {}",
+                tdelivery.activity_id,
+                tdelivery.inbox_url,
+                status,
+                attempts
+            );
+            failed_inboxes.push(tdelivery.inbox_url);
+
+            if down {
+                let dead = UnavailableDomain::failed(&domain).await?;
+                unavailable.insert(domain, dead);
+            }
+
+            let delay =
+                (RETRY_DELAY << (attempts - 1).min(16)).min(MAX_RETRY_DELAY);
+            let expired =
+                get_current_epoch() + delay > tdelivery.created_at + horizon;
+            if !retryable || expired {
+                TDelivery::fail(tdelivery.uid, status).await?;
+            } else {
+                TDelivery::retry(tdelivery.uid, delay, status).await?;
+            }
+        }
+
+        TDelivery::purge(FINISHED_DELIVERY_AGE).await?;
+        Ok(delivered)
+    }
+
+    async fn horizon() -> i64 {
+        Setting::get("delivery_retry_horizon")
+            .await
+            .and_then(|x| x.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_RETRY_HORIZON)
+    }
+}
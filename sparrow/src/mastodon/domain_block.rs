@@ -0,0 +1,22 @@
+//! Domains blocked by the server moderators.
+//!
+//! Mastodon doc: <https://docs.joinmastodon.org/entities/DomainBlock/>
+
+use anyhow::Result;
+
+use crate::table::domain_block::DomainBlock as TDomainBlock;
+
+/// Severity code of suspended domain.
+const SEVERITY_SUSPEND: i64 = 1;
+
+/// DomainBlock
+pub struct DomainBlock;
+
+impl DomainBlock {
+    /// Is the domain (or its parent domain) suspended?
+    /// Nothing is received from or delivered to suspended domains.
+    pub async fn is_suspended(domain: &str) -> Result<bool> {
+        let block = TDomainBlock::of_domain(domain.to_lowercase()).await?;
+        Ok(block.is_some_and(|x| x.severity == Some(SEVERITY_SUSPEND)))
+    }
+}
@@ -0,0 +1,10 @@
+//! Errors of fetching objects from remote servers.
+
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum FetchError {
+    /// Object is deleted for good. HTTP 410.
+    #[error("Gone: {0}")]
+    Gone(String),
+}
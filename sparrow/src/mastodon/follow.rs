@@ -23,6 +23,7 @@ pub struct Follow {
     pub uri: Option<String>,
     pub notify: Option<bool>,
     pub languages: Option<String>,
+    pub pending: Option<bool>,
 }
 
 /// Relation status between two accounts.  
@@ -41,12 +42,53 @@ impl Follow {
         sub: AccountUid,
         obj: AccountUid,
     ) -> Result<()> {
-        match Self::is_exist(sub.to_owned(), obj.to_owned()).await? {
-            true => TFollow::update(uri, sub, obj).await,
-            false => TFollow::new(uri, sub, obj).await,
+        // Accepted follow request is promoted.
+        match TFollow::record(sub.to_string(), obj.to_string()).await? {
+            Some(_) => TFollow::update(uri, sub, obj, false).await,
+            None => TFollow::new(uri, sub, obj, false).await,
         }
     }
 
+    /// Follow request waiting for Accept.
+    pub async fn request(
+        uri: String,
+        sub: AccountUid,
+        obj: AccountUid,
+    ) -> Result<()> {
+        match TFollow::record(sub.to_string(), obj.to_string()).await? {
+            // Already following. Nothing to wait for.
+            Some(f) if f.pending != Some(true) => Ok(()),
+            Some(_) => TFollow::update(uri, sub, obj, true).await,
+            None => TFollow::new(uri, sub, obj, true).await,
+        }
+    }
+
+    /// Accepted follow from a to b goes back to a pending request.
+    pub async fn revoke(uri: String, a: &MAccount, b: &MAccount) -> Result<()> {
+        match TFollow::record(a.uid.to_string(), b.uid.to_string()).await? {
+            Some(f) if f.uri.as_deref() == Some(uri.as_str()) => {
+                TFollow::update(uri, a.uid.to_owned(), b.uid.to_owned(), true)
+                    .await
+            }
+            _ => {
+                tracing::debug!("No follow to revoke: {}", uri);
+                Ok(())
+            }
+        }
+    }
+
+    /// Remove follow (or follow request) from a to b.
+    pub async fn remove(a: &MAccount, b: &MAccount) -> Result<()> {
+        TFollow::remove(a.uid.to_string(), b.uid.to_string()).await
+    }
+
+    /// Is there a pending follow request from a to b?
+    pub async fn is_pending(a: &MAccount, b: &MAccount) -> Result<bool> {
+        let record =
+            TFollow::record(a.uid.to_string(), b.uid.to_string()).await?;
+        Ok(record.map(|x| x.pending == Some(true)).unwrap_or(false))
+    }
+
     pub async fn follower_count(taccount: TAccount) -> Result<u64> {
         TFollow::follower_count(taccount).await
     }
@@ -95,11 +137,11 @@ impl Follow {
 
         let followers =
             TFollow::get(("target_account_uid".to_string(), id)).await?;
-        for f in followers.iter() {
-            let account_id = f.to_owned().account_uid.unwrap();
-            let account_uid = AccountUid(account_id);
-            let account = MAccount::get(account_uid).await?;
-            maccounts.push(account);
+        for f in followers.iter().filter(|x| x.pending != Some(true)) {
+            if let Some(account_id) = f.account_uid.to_owned() {
+                let account = MAccount::get(AccountUid(account_id)).await?;
+                maccounts.push(account);
+            }
         }
         Ok(maccounts)
     }
@@ -108,11 +150,11 @@ impl Follow {
         let mut maccounts: Vec<MAccount> = Vec::new();
 
         let following = TFollow::get(("account_uid".to_string(), id)).await?;
-        for f in following.iter() {
-            let target_account_id = f.to_owned().target_account_uid.unwrap();
-            let target_account_uid = AccountUid(target_account_id);
-            let account = MAccount::get(target_account_uid).await?;
-            maccounts.push(account);
+        for f in following.iter().filter(|x| x.pending != Some(true)) {
+            if let Some(target_id) = f.target_account_uid.to_owned() {
+                let account = MAccount::get(AccountUid(target_id)).await?;
+                maccounts.push(account);
+            }
         }
         Ok(maccounts)
     }
@@ -143,11 +185,12 @@ impl TryFrom<TFollow> for Follow {
             created_at: tf.created_at,
             updated_at: tf.updated_at,
             account_uid: tf.account_uid,
-            target_account_uid: Some(tf.target_account_uid.unwrap()),
+            target_account_uid: tf.target_account_uid,
             show_reblogs: tf.show_reblogs,
             uri: tf.uri,
             notify: tf.notify,
             languages: tf.languages,
+            pending: tf.pending,
         })
     }
 }
@@ -0,0 +1,57 @@
+//! Inbound activities already processed.
+//!
+//! Remote servers retry deliveries and shared inbox receives the same
+//! activity more than once. Replays within the dedupe window are
+//! acknowledged without side effects.
+
+use anyhow::Result;
+use url::Url;
+
+use crate::mastodon::setting::Setting;
+use crate::table::inbox_activity::InboxActivity as TInboxActivity;
+
+/// Dedupe window in seconds when `inbox_dedupe_window` is not set. 7 days.
+const DEFAULT_DEDUPE_WINDOW: i64 = 604800;
+
+pub struct InboxActivity {}
+
+impl InboxActivity {
+    /// Claim the activity for processing.
+    /// Returns false when it is a replay.
+    pub async fn claim(id: &str, actor: Option<&str>) -> Result<bool> {
+        let window = Self::window().await;
+        let claimed = TInboxActivity::claim(
+            id.to_string(),
+            actor.map(String::from),
+            window,
+        )
+        .await?;
+        if claimed {
+            TInboxActivity::purge(window).await?;
+        }
+        Ok(claimed)
+    }
+
+    /// Is the activity id on the actor's host? Id of another server would
+    /// take its dedupe slot and shadow the real activity.
+    pub fn same_origin(id: &str, actor: &str) -> bool {
+        let host = |x: &str| {
+            Url::parse(x)
+                .ok()
+                .and_then(|u| u.host_str().map(String::from))
+        };
+        host(id).is_some() && host(id) == host(actor)
+    }
+
+    /// Failed activity is released so that the sender can retry it.
+    pub async fn release(id: &str) -> Result<()> {
+        TInboxActivity::release(id.to_string()).await
+    }
+
+    async fn window() -> i64 {
+        Setting::get("inbox_dedupe_window")
+            .await
+            .and_then(|x| x.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_DEDUPE_WINDOW)
+    }
+}
@@ -0,0 +1,55 @@
+//! Errors of inbound activity handling.
+//!
+//! Each error is answered with its HTTP status and a JSON error body.
+
+use serde_json::json;
+use spin_sdk::http::Response;
+use thiserror::Error;
+
+use crate::REQUEST_UID;
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum InboxError {
+    /// Header is absent or not a visible ASCII string.
+    #[error("Missing header: {0}")]
+    MissingHeader(String),
+    /// Body is not a JSON object or a required field is malformed.
+    #[error("Bad JSON: {0}")]
+    BadJson(String),
+    /// Activity type is not handled.
+    #[error("Unknown type: {0}")]
+    UnknownType(String),
+    /// Signature can not be verified with the actor's key.
+    #[error("Signature verification failed: {0}")]
+    SignatureFailure(String),
+    /// Sender's domain is suspended.
+    #[error("Blocked domain: {0}")]
+    BlockedDomain(String),
+}
+
+impl InboxError {
+    /// HTTP status code of the error.
+    pub fn status(&self) -> u16 {
+        match self {
+            InboxError::MissingHeader(_) => 400,
+            InboxError::BadJson(_) => 400,
+            InboxError::UnknownType(_) => 501,
+            InboxError::SignatureFailure(_) => 401,
+            InboxError::BlockedDomain(_) => 403,
+        }
+    }
+
+    /// Response of the error. The reason is logged with the request uid.
+    pub fn response(&self) -> Response {
+        let request_uid =
+            REQUEST_UID.get().map(|x| x.to_string()).unwrap_or_default();
+        tracing::warn!("[{}] {} ({})", request_uid, self, self.status());
+
+        let body = json!({ "error": self.to_string() }).to_string();
+        Response::builder()
+            .status(self.status())
+            .header("Content-Type", "application/json")
+            .body(body)
+            .build()
+    }
+}
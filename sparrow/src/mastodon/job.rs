@@ -0,0 +1,229 @@
+//! Background jobs.
+//!
+//! Inbox verifies the signature, queues the activity and answers 202 right
+//! away. Worker drains the queue later, so slow remote servers (actor
+//! fetches, outbound `Accept`) don't hold the sender's request. Failed jobs
+//! are retried with backoff and their failure reason is recorded.
+
+use anyhow::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::activitystream::activity::accept::Accept;
+use crate::activitystream::activity::add::Add;
+use crate::activitystream::activity::announce::Announce;
+use crate::activitystream::activity::block::Block;
+use crate::activitystream::activity::create::Create;
+use crate::activitystream::activity::delete::Delete;
+use crate::activitystream::activity::flag::Flag;
+use crate::activitystream::activity::follow::Follow;
+use crate::activitystream::activity::like::Like;
+use crate::activitystream::activity::r#move::Move;
+use crate::activitystream::activity::reject::Reject;
+use crate::activitystream::activity::remove::Remove;
+use crate::activitystream::activity::undo::Undo;
+use crate::activitystream::activity::update::Update;
+use crate::activitystream::activity::Activity;
+use crate::activitystream::activity::ActivityType;
+use crate::activitystream::Execute;
+use crate::mastodon::account::actor_url::ActorUrl;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::inbox_error::InboxError;
+use crate::mastodon::recipient::Recipient;
+use crate::mastodon::{ACTOR_ACCOUNT, RECIPIENTS};
+use crate::table::job::Job as TJob;
+
+/// Queue of inbound activities.
+pub const INBOX_QUEUE: &str = "inbox";
+
+/// Attempts before a job gives up.
+const MAX_ATTEMPTS: i64 = 8;
+
+/// Delay before the first retry in seconds. Doubled on each attempt.
+const RETRY_DELAY: i64 = 30;
+
+/// Jobs run in one worker invocation.
+const BATCH_SIZE: i64 = 20;
+
+/// Finished jobs are kept for a day.
+const FINISHED_JOB_AGE: i64 = 86400;
+
+/// Inbound activity and the actor whose signature was verified.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct InboxPayload {
+    activity: Value,
+    /// Actor url. None when actor deletes itself.
+    actor: Option<String>,
+}
+
+pub struct Job;
+
+impl Job {
+    /// Activity types the inbox processes.
+    pub fn accepts(activity_type: &ActivityType) -> bool {
+        matches!(
+            activity_type,
+            ActivityType::Accept
+                | ActivityType::Add
+                | ActivityType::Announce
+                | ActivityType::Block
+                | ActivityType::Create
+                | ActivityType::Delete
+                | ActivityType::Flag
+                | ActivityType::Follow
+                | ActivityType::Like
+                | ActivityType::Move
+                | ActivityType::Reject
+                | ActivityType::Remove
+                | ActivityType::Undo
+                | ActivityType::Update
+        )
+    }
+
+    /// Queue an inbound activity sent by actor. Returns job's uid.
+    pub async fn enqueue_inbox(
+        activity: Value,
+        actor: Option<&MAccount>,
+    ) -> Result<String> {
+        let payload = InboxPayload {
+            activity,
+            actor: actor.map(|x| x.actor_url.to_string()),
+        };
+        TJob::new(
+            INBOX_QUEUE.to_string(),
+            serde_json::to_string(&payload)?,
+            MAX_ATTEMPTS,
+        )
+        .await
+    }
+
+    /// Run due jobs of the queue. Returns the number of finished jobs.
+    pub async fn drain(queue: &str, me: &MAccount) -> Result<usize> {
+        let mut finished = 0;
+        TJob::exhaust(queue.to_string()).await?;
+        for tjob in TJob::due(queue.to_string(), BATCH_SIZE).await? {
+            // Overlapping worker got it first.
+            if !TJob::lock(tjob.uid.to_owned()).await? {
+                continue;
+            }
+            let attempts = tjob.attempts + 1;
+
+            let result = match tjob.queue.as_str() {
+                INBOX_QUEUE => run_inbox(tjob.payload.as_str(), me).await,
+                q => Err(Error::msg(format!("Unknown queue '{}'", q))),
+            };
+
+            match result {
+                Ok(_) => {
+                    TJob::done(tjob.uid).await?;
+                    finished += 1;
+                }
+                Err(e) => {
+                    let reason = format!("{e:#}");
+                    tracing::error!("Job {} failed: {}", tjob.uid, reason);
+                    // Malformed activity never succeeds.
+                    let permanent = e.downcast_ref::<InboxError>().is_some();
+                    if permanent || attempts >= tjob.max_attempts {
+                        TJob::fail(tjob.uid, reason).await?;
+                    } else {
+                        let delay = RETRY_DELAY << (attempts - 1).min(16);
+                        TJob::retry(tjob.uid, delay, reason).await?;
+                    }
+                }
+            }
+        }
+
+        TJob::purge(FINISHED_JOB_AGE).await?;
+        Ok(finished)
+    }
+}
+
+/// Execute queued inbound activity.
+async fn run_inbox(payload: &str, me: &MAccount) -> Result<()> {
+    let payload: InboxPayload = serde_json::from_str(payload)
+        .map_err(|e| InboxError::BadJson(e.to_string()))?;
+    let body = payload.activity;
+
+    let actor_account = match payload.actor {
+        Some(url) => {
+            let account = MAccount::get(ActorUrl::new(url)?).await?;
+            ACTOR_ACCOUNT.set(account.to_owned());
+            Some(account)
+        }
+        None => {
+            ACTOR_ACCOUNT.clear();
+            None
+        }
+    };
+
+    let v_type = body
+        .get("type")
+        .and_then(|x| x.as_str())
+        .unwrap_or_default();
+    let activity_type = ActivityType::from_str(v_type)
+        .map_err(|_| InboxError::UnknownType(v_type.to_string()))?;
+
+    // Handlers act for each addressed local account.
+    let recipients = match actor_account.as_ref() {
+        Some(actor) => Recipient::resolve(&body, actor).await?,
+        None => Vec::new(),
+    };
+    RECIPIENTS.set(recipients.to_owned());
+
+    let me = me.to_owned();
+    let a = actor_account.to_owned();
+    match activity_type.to_owned() {
+        ActivityType::Accept => execute::<Accept>(&body, me, a).await?,
+        ActivityType::Add => execute::<Add>(&body, me, a).await?,
+        ActivityType::Announce => execute::<Announce>(&body, me, a).await?,
+        ActivityType::Block => execute::<Block>(&body, me, a).await?,
+        ActivityType::Create => execute::<Create>(&body, me, a).await?,
+        ActivityType::Delete => execute::<Delete>(&body, me, a).await?,
+        ActivityType::Flag => execute::<Flag>(&body, me, a).await?,
+        ActivityType::Follow => execute::<Follow>(&body, me, a).await?,
+        ActivityType::Like => execute::<Like>(&body, me, a).await?,
+        ActivityType::Move => execute::<Move>(&body, me, a).await?,
+        ActivityType::Reject => execute::<Reject>(&body, me, a).await?,
+        ActivityType::Remove => execute::<Remove>(&body, me, a).await?,
+        ActivityType::Undo => execute::<Undo>(&body, me, a).await?,
+        ActivityType::Update => execute::<Update>(&body, me, a).await?,
+        action => {
+            return Err(InboxError::UnknownType(format!("{:?}", action)).into())
+        }
+    };
+
+    // Directly addressed local accounts are notified of the new status.
+    if activity_type == ActivityType::Create {
+        if let Some(actor) = actor_account {
+            Recipient::fan_out(&recipients, &body, &actor).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse and execute the activity sent by actor.
+async fn execute<T>(
+    body: &Value,
+    me: MAccount,
+    actor_account: Option<MAccount>,
+) -> Result<()>
+where
+    T: DeserializeOwned
+        + Debug
+        + Serialize
+        + ToString
+        + PartialEq
+        + Eq
+        + Clone
+        + Default
+        + Execute,
+{
+    let activity = serde_json::from_value::<Activity<T>>(body.to_owned())
+        .map_err(|e| InboxError::BadJson(e.to_string()))?;
+    activity.execute(me, actor_account).await
+}
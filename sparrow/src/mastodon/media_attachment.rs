@@ -6,6 +6,10 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::activitystream::object::document::Document;
+use crate::mastodon::account::Account as MAccount;
+use crate::table::media_attachment::MediaAttachment as TMediaAttachment;
+
 /// MediaAttachement: Represents a file or media attachment that can be added to a status.
 /// <https://docs.joinmastodon.org/entities/MediaAttachment/>
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
@@ -13,7 +17,7 @@ pub struct MediaAttachment {
     /// rowid from sqlite
     pub rowid: i64,
     /// uid: uuid v7
-    #[serde(rename(serialize = "type", deserialize = "type"))]
+    #[serde(rename(serialize = "id", deserialize = "id"))]
     pub uid: String,
     /// The type of the attachment.
     /// String (Enumerable, oneOf)
@@ -22,21 +26,22 @@ pub struct MediaAttachment {
     /// gifv = Looping, soundless animation
     /// video = Video clip
     /// audio = Audio track
+    #[serde(rename(serialize = "type", deserialize = "type"))]
     pub media_type: String,
     /// he location of the original full-size attachment.
     pub url: String,
     /// The location of a scaled-down preview of the attachment.
-    pub preview_url: String,
+    pub preview_url: Option<String>,
     /// The location of the full-size original attachment on the remote website.
-    pub remote_url: String,
+    pub remote_url: Option<String>,
     /// Metadata returned by Paperclip.
     pub meta: HashMap<String, String>,
     /// Alternate text that describes what is in the media attachment, to be used for the visually impaired or when media attachments do not load.
-    pub description: String,
+    pub description: Option<String>,
     /// A hash computed by the BlurHash algorithm, for generating colorful preview thumbnails when media has not been downloaded yet.
-    pub blurhash: String,
+    pub blurhash: Option<String>,
     /// A shorter URL for the attachment.
-    pub text_url: String,
+    pub text_url: Option<String>,
 }
 
 impl MediaAttachment {
@@ -45,4 +50,72 @@ impl MediaAttachment {
     pub async fn get(media_ia: String) -> Result<Vec<MediaAttachment>> {
         Ok(Vec::new())
     }
+
+    /// Keep remote media attached to a's status. Media is not downloaded.
+    pub async fn save_remote(
+        a: &MAccount,
+        status_uid: String,
+        document: &Document,
+    ) -> Result<()> {
+        let url = match document.url() {
+            Some(u) => u,
+            None => return Ok(()),
+        };
+
+        let media_type = match document.media_kind() {
+            "image" => 0,
+            "gifv" => 1,
+            "video" => 2,
+            "audio" => 4,
+            _ => 3,
+        };
+
+        TMediaAttachment::new(
+            a.uid.to_string(),
+            status_uid,
+            url,
+            media_type,
+            document.media_type.to_owned(),
+            document.name.to_owned(),
+            document.blurhash.to_owned(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Media attached to the status.
+    pub async fn of_status(status_uid: String) -> Result<Vec<MediaAttachment>> {
+        let medias = TMediaAttachment::of_status(status_uid)
+            .await?
+            .into_iter()
+            .map(Self::fr_tmedia_attachment)
+            .collect();
+        Ok(medias)
+    }
+
+    fn fr_tmedia_attachment(t: TMediaAttachment) -> MediaAttachment {
+        let media_type = match t.media_type {
+            0 => "image",
+            1 => "gifv",
+            2 => "video",
+            4 => "audio",
+            _ => "unknown",
+        };
+
+        MediaAttachment {
+            rowid: t.rowid.unwrap_or_default(),
+            uid: t.uid,
+            media_type: media_type.to_string(),
+            // Remote media is served from its origin.
+            url: t.remote_url.to_owned(),
+            preview_url: t
+                .thumbnail_remote_url
+                .or(Some(t.remote_url.to_owned())),
+            remote_url: Some(t.remote_url),
+            meta: HashMap::new(),
+            description: t.description,
+            blurhash: t.blurhash,
+            text_url: None,
+        }
+    }
 }
@@ -2,6 +2,7 @@
 //!
 //! Mastodon doc: <https://docs.joinmastodon.org/entities/Notification/>
 
+use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +10,7 @@ use crate::mastodon::account::Account as MAccount;
 use crate::mastodon::relationship_severance_event::RelationshipSeveranceEvent;
 use crate::mastodon::report::Report;
 use crate::mastodon::status::Status;
+use crate::table::notification::Notification as TNotification;
 
 /// Represents a notification of an event relevant to the user.
 /// Mastodon doc: <https://docs.joinmastodon.org/entities/Notification/>
@@ -48,3 +50,32 @@ pub struct Notification {
     /// Attached when type of the notification is severed_relationships.
     pub relationship_severance_event: Option<RelationshipSeveranceEvent>,
 }
+
+impl Notification {
+    /// Notify `account` about what `from_account` did.  
+    /// `activity_id` and `activity_type` point the row that caused the notification. (e.g. status uid and "Status")
+    pub async fn new(
+        notification_type: &str,
+        account: &MAccount,
+        from_account: &MAccount,
+        activity_id: String,
+        activity_type: &str,
+    ) -> Result<()> {
+        TNotification::new(
+            activity_id,
+            activity_type.to_string(),
+            account.uid.to_string(),
+            from_account.uid.to_string(),
+            notification_type.to_string(),
+        )
+        .await
+    }
+
+    /// Remove notifications caused by given row.
+    pub async fn remove(
+        activity_id: String,
+        activity_type: &str,
+    ) -> Result<()> {
+        TNotification::remove(activity_id, activity_type.to_string()).await
+    }
+}
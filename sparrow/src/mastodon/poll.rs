@@ -1,27 +1,273 @@
-// https://docs.joinmastodon.org/entities/Poll/
+//! Represents a poll attached to a status.
+//!
+//! Mastodon doc: <https://docs.joinmastodon.org/entities/Poll/>
 
+use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::activitystream::activity::update::Update;
+use crate::activitystream::object::question::Question;
+use crate::mastodon::account::uid::Uid as AccountUid;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
 use crate::mastodon::custom_emoji::CustomEmoji;
+use crate::mastodon::ME_ACCOUNT;
+use crate::table::poll::Get as _;
+use crate::table::poll::Poll as TPoll;
+use crate::table::poll_vote::PollVote as TPollVote;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+use crate::utils::get_current_epoch;
 
+/// Represents a poll attached to a status.
+/// Mastodon doc: <https://docs.joinmastodon.org/entities/Poll/>
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
 pub struct Poll {
+    /// The ID(uuid v7) of the poll in the database.
     #[serde(rename(serialize = "id", deserialize = "id"))]
     pub uid: String,
+    /// When the poll ends.
     pub expires_at: Option<DateTime<Utc>>,
+    /// Is the poll currently expired?
     pub expired: bool,
+    /// Does the poll allow multiple-choice answers?
     pub multiple: bool,
+    /// How many votes have been received.
     pub votes_count: i64,
+    /// How many unique accounts have voted on a multiple-choice poll.
     pub voters_count: Option<i64>,
+    /// Possible answers for the poll.
     pub options: Vec<PollOption>,
+    /// Custom emoji to be used for rendering poll options.
     pub emojis: Vec<CustomEmoji>,
+    /// When called with a user token, has the authorized user voted?
     pub voted: Option<bool>,
+    /// When called with a user token, which options has the authorized user chosen?
     pub own_votes: Option<Vec<i64>>,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
 pub struct PollOption {
+    /// The text value of the poll option.
     pub title: String,
+    /// The total number of received votes for this option.
     pub votes_count: Option<i64>,
 }
+
+impl Poll {
+    /// a creates a poll on its status. Returns the poll's uid.
+    pub async fn new(
+        a: &MAccount,
+        status_uid: String,
+        options: Vec<String>,
+        expires_at: i64,
+        multiple: bool,
+    ) -> Result<String> {
+        let tallies = vec![0; options.len()];
+        let uid = TPoll::new(
+            a.uid.to_string(),
+            status_uid.to_owned(),
+            Some(expires_at),
+            options,
+            tallies,
+            multiple,
+            Some(0),
+        )
+        .await?;
+        TStatus::set_poll_id(status_uid, uid.to_owned()).await?;
+        Ok(uid)
+    }
+
+    /// Store the poll of remote Question. Returns the poll's uid.
+    pub async fn save_question(
+        question: &Question,
+        author: &MAccount,
+        status_uid: String,
+    ) -> Result<String> {
+        let expires_at = question
+            .end_time
+            .to_owned()
+            .or(question
+                .closed
+                .to_owned()
+                .and_then(|x| x.as_str().map(String::from)))
+            .and_then(|x| DateTime::parse_from_rfc3339(x.as_str()).ok())
+            .map(|x| x.timestamp());
+
+        let uid = TPoll::new(
+            author.uid.to_string(),
+            status_uid.to_owned(),
+            expires_at,
+            question.titles(),
+            question.tallies(),
+            question.multiple(),
+            question.voters_count,
+        )
+        .await?;
+        TStatus::set_poll_id(status_uid, uid.to_owned()).await?;
+        Ok(uid)
+    }
+
+    /// Apply votes count of updated Question.
+    pub async fn refresh(tpoll: &TPoll, question: &Question) -> Result<()> {
+        TPoll::update_tallies(
+            tpoll.uid.to_owned(),
+            question.tallies(),
+            question.voters_count,
+        )
+        .await
+    }
+
+    /// Poll with uid.
+    pub async fn find(uid: String) -> Result<Option<Poll>> {
+        match TPoll::get(("uid".to_string(), uid)).await?.last() {
+            Some(t) => Ok(Some(Self::fr_tpoll(t.to_owned()).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Is the poll expired?
+    pub fn is_expired(tpoll: &TPoll) -> bool {
+        tpoll.expires_at.is_some_and(|x| x <= get_current_epoch())
+    }
+
+    /// Options a has chosen on the poll.
+    pub async fn choices(a: &MAccount, tpoll: &TPoll) -> Result<Vec<i64>> {
+        TPollVote::choices(a.uid.to_string(), tpoll.uid.to_owned()).await
+    }
+
+    /// a votes on the poll.
+    /// Votes on our poll are counted from poll_vote table.
+    /// Votes on remote poll are added to its cached tallies until next update.
+    pub async fn vote(
+        a: &MAccount,
+        tpoll: &TPoll,
+        choices: Vec<i64>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        let voted_before = !Self::choices(a, tpoll).await?.is_empty();
+
+        for choice in choices.iter() {
+            TPollVote::new(
+                a.uid.to_string(),
+                tpoll.uid.to_owned(),
+                *choice,
+                uri.to_owned(),
+            )
+            .await?;
+        }
+
+        let author =
+            MAccount::get(AccountUid(tpoll.account_uid.to_owned())).await?;
+        if author.local() {
+            return Self::recount(tpoll).await;
+        }
+
+        let mut tallies: Vec<i64> =
+            serde_json::from_str(&tpoll.cached_tallies).unwrap_or_default();
+        for choice in choices {
+            if let Some(t) = tallies.get_mut(choice as usize) {
+                *t += 1;
+            }
+        }
+        let voters_count = match voted_before {
+            true => tpoll.voters_count,
+            false => tpoll.voters_count.map(|x| x + 1),
+        };
+        TPoll::update_tallies(tpoll.uid.to_owned(), tallies, voters_count).await
+    }
+
+    /// Count votes of our poll.
+    async fn recount(tpoll: &TPoll) -> Result<()> {
+        let options: Vec<String> = serde_json::from_str(&tpoll.options)?;
+        let mut tallies = vec![0; options.len()];
+        for (choice, cnt) in TPollVote::tallies(tpoll.uid.to_owned()).await? {
+            if let Some(t) = tallies.get_mut(choice as usize) {
+                *t = cnt;
+            }
+        }
+        let voters_count =
+            TPollVote::voters_count(tpoll.uid.to_owned()).await?;
+        TPoll::update_tallies(tpoll.uid.to_owned(), tallies, Some(voters_count))
+            .await
+    }
+
+    /// Send final result of a's expired polls to followers.
+    pub async fn close_expired(a: &MAccount) -> Result<()> {
+        for tpoll in TPoll::expired_unclosed(a.uid.to_string()).await? {
+            let tstatus = match TStatus::get((
+                "uid".to_string(),
+                tpoll.status_uid.to_owned(),
+            ))
+            .await?
+            .last()
+            {
+                Some(t) => t.to_owned(),
+                None => {
+                    TPoll::close(tpoll.uid).await?;
+                    continue;
+                }
+            };
+
+            if tstatus.deleted_at.is_none() {
+                let question = Question::fr_tstatus(tstatus).await?;
+                let mut update_activity = Update::new(
+                    a.actor_url.to_string(),
+                    serde_json::to_value(&question)?,
+                )
+                .await;
+                update_activity.to = question.note.to.to_owned();
+                update_activity.cc = question.note.cc.to_owned();
+
+                crate::mastodon::post_activity_to_audience(
+                    a.to_owned(),
+                    update_activity,
+                )
+                .await?;
+            }
+
+            TPoll::close(tpoll.uid).await?;
+        }
+        Ok(())
+    }
+
+    async fn fr_tpoll(tpoll: TPoll) -> Result<Poll> {
+        let titles: Vec<String> = serde_json::from_str(&tpoll.options)?;
+        let tallies: Vec<i64> =
+            serde_json::from_str(&tpoll.cached_tallies).unwrap_or_default();
+
+        let options = titles
+            .into_iter()
+            .enumerate()
+            .map(|(i, title)| PollOption {
+                title,
+                votes_count: match tpoll.hide_totals {
+                    true => None,
+                    false => Some(tallies.get(i).copied().unwrap_or_default()),
+                },
+            })
+            .collect();
+
+        // Have I voted on this poll?
+        let own_votes = match ME_ACCOUNT.get() {
+            Some(me) => Some(Self::choices(me, &tpoll).await?),
+            None => None,
+        };
+
+        Ok(Poll {
+            uid: tpoll.uid.to_owned(),
+            expires_at: tpoll
+                .expires_at
+                .and_then(|x| DateTime::from_timestamp(x, 0)),
+            expired: Self::is_expired(&tpoll),
+            multiple: tpoll.multiple,
+            votes_count: tpoll.votes_count,
+            voters_count: tpoll.voters_count,
+            options,
+            emojis: Vec::new(),
+            voted: own_votes.as_ref().map(|x| !x.is_empty()),
+            own_votes,
+        })
+    }
+}
@@ -0,0 +1,196 @@
+//! Local accounts an inbound activity is delivered to.
+//!
+//! Shared inbox gets an activity once even when it addresses several local
+//! accounts. Recipients are resolved from `to`, `cc`, `bto`, `bcc` and
+//! `audience` of the activity and its object, plus follower collections.
+//! Worker keeps them in `RECIPIENTS` while it executes the activity, so
+//! handlers act for each addressed local account.
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::activitystream::PUBLIC;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::activity_log::ActivityLog;
+use crate::mastodon::notification::Notification as MNotification;
+use crate::table::account::Account as TAccount;
+use crate::table::follow::Follow as TFollow;
+use crate::table::follow::Get as _;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+
+const ADDRESSING: [&str; 5] = ["to", "cc", "bto", "bcc", "audience"];
+
+/// Local account addressed by an inbound activity.
+#[derive(Debug, Clone)]
+pub struct Recipient {
+    pub account: MAccount,
+    /// Addressed by its actor url or its status, not through a collection.
+    pub direct: bool,
+}
+
+impl Recipient {
+    /// Local recipients of the activity sent by actor.
+    pub async fn resolve(
+        activity: &Value,
+        actor: &MAccount,
+    ) -> Result<Vec<Recipient>> {
+        let addresses = addresses(activity);
+        let mut referred = referred(activity);
+
+        // Object given only by its id. Logged activity tells whom it is about.
+        let activity_type = activity.get("type").and_then(|x| x.as_str());
+        let object_id = activity.get("object").and_then(|x| x.as_str());
+        if let (Some("Accept" | "Reject" | "Undo"), Some(id)) =
+            (activity_type, object_id)
+        {
+            if let Some(logged) = ActivityLog::get_with_id(id).await? {
+                referred.extend(embedded(&logged));
+            }
+        }
+
+        let followers_url = actor
+            .followers_url
+            .to_owned()
+            .unwrap_or(format!("{}/followers", actor.actor_url));
+        // Move is sent to the followers without addressing them.
+        let to_followers = addresses.contains(&followers_url)
+            || addresses.contains(&PUBLIC.to_string())
+            || activity_type == Some("Move");
+
+        // Authors of the statuses the activity is about.
+        let mut owners: Vec<String> = Vec::new();
+        for uri in referred.iter() {
+            let tstatus = TStatus::get(("uri".to_string(), uri.to_owned()))
+                .await?
+                .last()
+                .cloned();
+            if let Some(t) = tstatus.filter(|x| x.local == Some(true)) {
+                owners.push(t.account_id);
+            }
+        }
+
+        // Followers of the actor. Pending requests are not counted.
+        let followers: Vec<String> = match to_followers {
+            true => TFollow::get((
+                "target_account_uid".to_string(),
+                actor.uid.to_string(),
+            ))
+            .await?
+            .into_iter()
+            .filter(|x| x.pending != Some(true))
+            .filter_map(|x| x.account_uid)
+            .collect(),
+            false => Vec::new(),
+        };
+
+        let mut recipients = Vec::new();
+        for taccount in TAccount::locals().await? {
+            let direct = addresses.contains(&taccount.uri)
+                || referred.contains(&taccount.uri)
+                || owners.contains(&taccount.uid);
+            if direct || followers.contains(&taccount.uid) {
+                let account = MAccount::get(taccount).await?;
+                recipients.push(Recipient { account, direct });
+            }
+        }
+
+        Ok(recipients)
+    }
+
+    /// Directly addressed recipients are notified of the created status.
+    pub async fn fan_out(
+        recipients: &[Recipient],
+        activity: &Value,
+        actor: &MAccount,
+    ) -> Result<()> {
+        let object = match activity.get("object") {
+            Some(o) if o.is_object() => o,
+            _ => return Ok(()),
+        };
+
+        // Votes on our polls are not mentions.
+        if object.get("name").is_some_and(|x| x.is_string())
+            && object.get("type").and_then(|x| x.as_str()) == Some("Note")
+        {
+            return Ok(());
+        }
+
+        let id = object
+            .get("id")
+            .and_then(|x| x.as_str())
+            .unwrap_or_default();
+        let tstatus = match TStatus::get(("uri".to_string(), id.to_string()))
+            .await?
+            .last()
+        {
+            Some(t) => t.to_owned(),
+            None => return Ok(()),
+        };
+
+        for recipient in recipients.iter().filter(|x| x.direct) {
+            MNotification::new(
+                "mention",
+                &recipient.account,
+                actor,
+                tstatus.uid.to_owned(),
+                "Status",
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Addresses of the activity and its object.
+pub(crate) fn addresses(activity: &Value) -> Vec<String> {
+    let mut addresses = Vec::new();
+    let object = activity.get("object").filter(|x| x.is_object());
+    for v in [Some(activity), object].into_iter().flatten() {
+        for field in ADDRESSING {
+            match v.get(field) {
+                Some(Value::String(s)) => addresses.push(s.to_owned()),
+                Some(Value::Array(a)) => addresses.extend(
+                    a.iter().filter_map(|x| x.as_str().map(String::from)),
+                ),
+                _ => (),
+            }
+        }
+    }
+    addresses
+}
+
+/// Urls the activity is about. Its object or objects, the move target, or
+/// what the embedded object refers to.
+fn referred(activity: &Value) -> Vec<String> {
+    let mut referred = Vec::new();
+    if let Some(s) = activity.get("target").and_then(|x| x.as_str()) {
+        referred.push(s.to_string());
+    }
+    match activity.get("object") {
+        Some(Value::String(s)) => referred.push(s.to_owned()),
+        Some(Value::Array(a)) => referred
+            .extend(a.iter().filter_map(|x| x.as_str().map(String::from))),
+        Some(o @ Value::Object(_)) => referred.extend(embedded(o)),
+        _ => (),
+    }
+    referred
+}
+
+/// Id, replied status, actor and object of an embedded object. Nested
+/// activities are followed, e.g. `Undo` of `Accept` of our `Follow`.
+fn embedded(object: &Value) -> Vec<String> {
+    let mut referred = Vec::new();
+    for field in ["id", "inReplyTo", "actor", "object"] {
+        match object.get(field) {
+            Some(Value::String(s)) => referred.push(s.to_owned()),
+            Some(o @ Value::Object(_)) if field == "object" => {
+                referred.extend(embedded(o))
+            }
+            _ => (),
+        }
+    }
+    referred
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::mastodon::account::uid::Uid as AccountUid;
 use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::block::Block;
 use crate::mastodon::follow::Follow;
 use crate::mastodon::follow::FollowRelation;
 
@@ -73,6 +74,11 @@ impl Relationship {
             }
         }
 
+        let requested = Follow::is_pending(a, b).await?;
+        let requested_by = Follow::is_pending(b, a).await?;
+        let blocking = Block::is_blocking(a, b).await?;
+        let blocked_by = Block::is_blocking(b, a).await?;
+
         // Todo:
         // `follow` table has information about Relationship.
         Ok(Relationship {
@@ -82,12 +88,12 @@ impl Relationship {
             notifying: true,
             languages: Vec::new(),
             followed_by,
-            blocking: false,
-            blocked_by: false,
+            blocking,
+            blocked_by,
             muting: false,
             muting_notifications: false,
-            requested: false,
-            requested_by: false,
+            requested,
+            requested_by,
             domain_blocking: false,
             endorsed: false,
             note: b.to_owned().note,
@@ -2,21 +2,28 @@
 //!
 //! Mastodon doc: <https://docs.joinmastodon.org/entities/Report/>
 
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::mastodon::account::uid::Uid as AccountUid;
 use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::table::report::Get as _;
+use crate::table::report::Report as TReport;
+use crate::utils::convert_epoch_to_iso_8601;
 
 /// Reports filed against users and/or statuses, to be taken action on by moderators.   
 /// Mastodon doc: <https://docs.joinmastodon.org/entities/Report/>
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
 pub struct Report {
     /// The ID(uuid v7) of the report in the database.
+    #[serde(rename(serialize = "id", deserialize = "id"))]
     pub uid: String,
     /// Whether an action was taken yet.
     pub action_taken: bool,
     /// When an action was taken against the report.
     /// Nullable, String (ISO 8601 Datetime) or null
-    pub action_taken_at: String,
+    pub action_taken_at: Option<String>,
     /// the generic reason for the report.
     /// String (Enumerable oneOf)
     /// `spam` = Unwanted or repetitive content
@@ -32,10 +39,80 @@ pub struct Report {
     pub created_at: String,
     /// IDs of statuses that have been attached to this report for additional context.
     /// Nullable, Array of String (cast from integer), or null
-    pub status_ids: String,
+    pub status_ids: Option<Vec<String>>,
     /// IDs of the rules that have been cited as a violation by this report.
     /// Nullable, Array of String (cast from integer), or null
-    pub rule_ids: String,
+    pub rule_ids: Option<Vec<String>>,
     /// The account that was reported.
     pub target_account: MAccount,
+    /// The account which filed the report. Only for Admin::Report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<MAccount>,
+}
+
+impl Report {
+    /// `account` reports `target_account` and its statuses.
+    /// Returns the report's uid.
+    pub async fn new(
+        uri: String,
+        account: &MAccount,
+        target_account: &MAccount,
+        status_uids: Vec<String>,
+        comment: String,
+    ) -> Result<String> {
+        TReport::new(
+            uri,
+            account.uid.to_string(),
+            target_account.uid.to_string(),
+            status_uids,
+            comment,
+        )
+        .await
+    }
+
+    /// All reports. Newest first.
+    pub async fn all() -> Result<Vec<Report>> {
+        let mut reports = Vec::new();
+        for treport in TReport::all().await? {
+            reports.push(Self::fr_treport(treport).await?);
+        }
+        Ok(reports)
+    }
+
+    /// Report with uid.
+    pub async fn find(uid: String) -> Result<Option<Report>> {
+        match TReport::get(("uid".to_string(), uid)).await?.last() {
+            Some(t) => Ok(Some(Self::fr_treport(t.to_owned()).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Mark report as resolved by `account`.
+    pub async fn resolve(uid: String, account: &MAccount) -> Result<()> {
+        TReport::resolve(uid, account.uid.to_string()).await
+    }
+
+    async fn fr_treport(treport: TReport) -> Result<Report> {
+        let account = MAccount::get(AccountUid(treport.account_uid)).await?;
+        let target_account =
+            MAccount::get(AccountUid(treport.target_account_uid)).await?;
+
+        Ok(Report {
+            uid: treport.uid,
+            action_taken: treport.action_taken_at.is_some(),
+            action_taken_at: treport
+                .action_taken_at
+                .map(convert_epoch_to_iso_8601),
+            category: treport.category,
+            comment: treport.comment,
+            forwarded: treport.forwarded.unwrap_or_default(),
+            created_at: convert_epoch_to_iso_8601(treport.created_at),
+            status_ids: serde_json::from_str(&treport.status_uids).ok(),
+            rule_ids: treport
+                .rule_ids
+                .and_then(|x| serde_json::from_str(&x).ok()),
+            target_account,
+            account: Some(account),
+        })
+    }
 }
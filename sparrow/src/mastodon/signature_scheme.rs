@@ -0,0 +1,70 @@
+//! HTTP signature scheme of each domain.
+//!
+//! Outgoing requests are double-knocked: signed with RFC 9421 first, and
+//! signed again with draft-cavage when the server answers 401. The scheme
+//! that worked is remembered per domain. Domains on draft-cavage are knocked
+//! with RFC 9421 again after `signature_scheme_recheck` seconds, since they
+//! may have upgraded.
+
+use anyhow::Result;
+
+use crate::http_signature::Scheme;
+use crate::mastodon::setting::Setting;
+use crate::table::signature_scheme::Get as _;
+use crate::table::signature_scheme::SignatureScheme as TSignatureScheme;
+
+/// Seconds until a draft-cavage domain is knocked with RFC 9421 again when
+/// `signature_scheme_recheck` is not set. 30 days.
+const DEFAULT_RECHECK: i64 = 2592000;
+
+/// Remembered signature scheme of remote domains.
+pub struct SignatureScheme;
+
+impl SignatureScheme {
+    /// Schemes to sign requests to the domain with, in order.
+    pub async fn knocks(domain: &str) -> Result<Vec<Scheme>> {
+        let knocks = match Self::remembered(domain).await? {
+            Some((Scheme::Cavage, fresh)) if fresh => vec![Scheme::Cavage],
+            _ => vec![Scheme::Rfc9421, Scheme::Cavage],
+        };
+        Ok(knocks)
+    }
+
+    /// The domain accepted a request signed with the scheme.
+    pub async fn accepted(domain: &str, scheme: Scheme) -> Result<()> {
+        match Self::remembered(domain).await? {
+            Some((s, fresh)) if s == scheme && fresh => Ok(()),
+            _ => {
+                tracing::debug!("{} accepts {} signatures", domain, scheme);
+                TSignatureScheme::put(domain.to_lowercase(), scheme.to_string())
+                    .await
+            }
+        }
+    }
+
+    /// Remembered scheme of the domain and whether it is still fresh.
+    async fn remembered(domain: &str) -> Result<Option<(Scheme, bool)>> {
+        let record = TSignatureScheme::get((
+            "domain".to_string(),
+            domain.to_lowercase(),
+        ))
+        .await?
+        .into_iter()
+        .next();
+        let record = match record {
+            Some(r) => r,
+            None => return Ok(None),
+        };
+        let scheme = match record.scheme.parse::<Scheme>() {
+            Ok(s) => s,
+            Err(_) => return Ok(None),
+        };
+
+        let recheck = Setting::get("signature_scheme_recheck")
+            .await
+            .and_then(|x| x.parse::<i64>().ok())
+            .unwrap_or(DEFAULT_RECHECK);
+        let now = chrono::Utc::now().timestamp();
+        Ok(Some((scheme, now - record.updated_at < recheck)))
+    }
+}
@@ -3,7 +3,7 @@
 //! Mastodon doc: <https://docs.joinmastodon.org/entities/Status/>
 
 use anyhow::Result;
-use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use struct_iterable::Iterable;
@@ -11,13 +11,18 @@ use uuid::Uuid;
 
 use crate::activitystream::object::note::Note as NoteObject;
 use crate::mastodon::{
-    account::actor_url::ActorUrl, account::uri::Uri as AccountUri,
-    account::Account as MAccount, account::Get, custom_emoji::CustomEmoji,
-    filter_result::FilterResult, media_attachment::MediaAttachment,
-    poll::Poll, preview_card::PreviewCard, ACTOR_ACCOUNT, ME_ACCOUNT,
+    account::actor_url::ActorUrl, account::uid::Uid as AccountUid,
+    account::uri::Uri as AccountUri, account::Account as MAccount,
+    account::Get, custom_emoji::CustomEmoji, filter_result::FilterResult,
+    media_attachment::MediaAttachment, poll::Poll, preview_card::PreviewCard,
+    setting::Setting, ACTOR_ACCOUNT, ME_ACCOUNT,
 };
 use crate::table::account::Account as TAccount;
+use crate::table::favourite::Favourite as TFavourite;
+use crate::table::status::Get as _;
 use crate::table::status::Status as TStatus;
+use crate::table::status_edit::StatusEdit as TStatusEdit;
+use crate::table::status_pin::StatusPin as TStatusPin;
 use crate::table::New;
 
 /// Represents a status posted by an account.  
@@ -94,7 +99,7 @@ pub struct Status {
     pub text: String,
     /// Timestamp of when the status was last edited.
     /// Nullable String (ISO 8601 Datetime)
-    pub edited_at: String,
+    pub edited_at: Option<String>,
     /// If the current token has an authorized user: Have you favourited this status?
     pub favourited: Option<bool>,
     /// If the current token has an authorized user: Have you boosted this status?
@@ -135,7 +140,7 @@ impl Status {
     pub async fn get(_a: MAccount) {}
 
     pub async fn count(taccount: TAccount) -> Result<u64> {
-        Ok(TStatus::count(taccount).await.unwrap() as u64)
+        Ok(TStatus::count(taccount).await? as u64)
     }
 }
 
@@ -174,13 +179,14 @@ pub struct Application {
 
 impl Status {
     pub async fn new(note: NoteObject, actor_account: MAccount) -> Result<()> {
-        let note_published_at = note.published.unwrap();
-        let created_at = NaiveDateTime::parse_from_str(
-            &note_published_at.as_str(),
-            "%Y-%m-%dT%H:%M:%SZ",
-        )
-        .map(|d| DateTime::<Utc>::from_naive_utc_and_offset(d, Utc))
-        .unwrap();
+        // Fractional seconds and offsets are used by some servers.
+        let created_at = note
+            .published
+            .to_owned()
+            .and_then(|x| DateTime::parse_from_rfc3339(x.as_str()).ok())
+            .map(|x| x.with_timezone(&Utc))
+            .unwrap_or(Utc::now());
+        let url = note.url.to_owned().unwrap_or(note.id.to_owned());
 
         let status = Status {
             uid: Uuid::now_v7().to_string(),
@@ -189,7 +195,7 @@ impl Status {
             account: actor_account,
             //content,
             //visibility: note,
-            sensitive: note.sensitivity.unwrap_or_default(),
+            sensitive: note.sensitive.unwrap_or_default(),
             // spoiler_text,
             media_attachments: vec![],
             // application,
@@ -199,14 +205,14 @@ impl Status {
             // reblogs_count,
             // favourites_count,
             // replies_count,
-            url: Some(note.url.unwrap()),
+            url: Some(url),
             in_reply_to_id: None,
             in_reply_to_account_id: None,
             // reblog,
             // poll,
             // card,
             language: "en".to_string(),
-            text: note.content.unwrap(),
+            text: note.content.unwrap_or_default(),
             // edited_at,
             // favourited,
             // reblogged,
@@ -220,8 +226,179 @@ impl Status {
         status.save().await
     }
 
+    /// Post a status of local account. Returns the stored status row.
+    pub async fn post(
+        account: &MAccount,
+        content: String,
+        spoiler_text: String,
+        visibility: &str,
+        sensitive: bool,
+        language: Option<String>,
+    ) -> Result<TStatus> {
+        let uid = Uuid::now_v7().to_string();
+        let uri =
+            format!("https://{}/statuses/{}", Setting::domain().await, uid);
+        let now = Utc::now().timestamp();
+
+        let tstatus = TStatus {
+            rowid: None,
+            uid: uid.to_owned(),
+            uri: Some(uri.to_owned()),
+            text: content,
+            created_at: now,
+            updated_at: now,
+            url: Some(uri),
+            sensitive: sensitive as i64,
+            visibility: match visibility {
+                "unlisted" => 1,
+                "private" => 2,
+                "direct" => 3,
+                _ => 0,
+            },
+            spoiler_text,
+            language,
+            local: Some(true),
+            account_id: account.uid.to_string(),
+            ..Default::default()
+        };
+        tstatus.new().await?;
+
+        Ok(tstatus)
+    }
+
     pub async fn save(&self) -> Result<()> {
-        let a = TStatus::try_from(self.to_owned()).unwrap();
+        let a = TStatus::try_from(self.to_owned())?;
         a.new().await
     }
+
+    /// Apply edited Note to the status.  
+    /// Previous versions are kept in status_edit table.
+    pub async fn edit(tstatus: TStatus, note: NoteObject) -> Result<()> {
+        let edited_at = note
+            .updated
+            .to_owned()
+            .and_then(|x| DateTime::parse_from_rfc3339(x.as_str()).ok())
+            .map(|x| x.timestamp())
+            .unwrap_or(Utc::now().timestamp());
+
+        // Updates can arrive out of order. Keep the newest one.
+        if tstatus.edited_at.unwrap_or_default() >= edited_at {
+            tracing::debug!("Status {} has newer edit already", tstatus.uid);
+            return Ok(());
+        }
+
+        // First edit. Keep the original version too.
+        if TStatusEdit::history(tstatus.uid.to_owned())
+            .await?
+            .is_empty()
+        {
+            TStatusEdit::new(&tstatus, tstatus.created_at).await?;
+        }
+
+        TStatus::edit(
+            tstatus.uid.to_owned(),
+            note.content.unwrap_or_default(),
+            note.summary.unwrap_or_default(),
+            note.sensitive.unwrap_or_default(),
+            edited_at,
+        )
+        .await?;
+
+        match TStatus::get(("uid".to_string(), tstatus.uid)).await?.last() {
+            Some(edited) => TStatusEdit::new(edited, edited_at).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Build Status from status table row.  
+    /// A boost row carries the boosted status in `reblog`.
+    pub async fn fr_tstatus(tstatus: TStatus) -> Result<Self> {
+        let account =
+            MAccount::get(AccountUid(tstatus.account_id.to_owned())).await?;
+
+        let reblog = match tstatus.reblog_of_id.to_owned() {
+            Some(uid) => {
+                match TStatus::get(("uid".to_string(), uid)).await?.last() {
+                    Some(t) => Some(Box::new(
+                        Box::pin(Self::fr_tstatus(t.to_owned())).await?,
+                    )),
+                    None => None,
+                }
+            }
+            None => None,
+        };
+
+        // Have I favourited this status?
+        let favourited = match ME_ACCOUNT.get() {
+            Some(me) => Some(
+                TFavourite::is_exist(
+                    me.uid.to_string(),
+                    tstatus.uid.to_owned(),
+                )
+                .await?,
+            ),
+            None => None,
+        };
+
+        // Have I pinned this status? Only my own status is pinnable.
+        let pinned = match ME_ACCOUNT.get() {
+            Some(me) if me.uid.to_string() == tstatus.account_id => Some(
+                TStatusPin::is_exist(
+                    me.uid.to_string(),
+                    tstatus.uid.to_owned(),
+                )
+                .await?,
+            ),
+            _ => None,
+        };
+
+        let media_attachments =
+            MediaAttachment::of_status(tstatus.uid.to_owned()).await?;
+
+        let poll = match tstatus.poll_id.to_owned() {
+            Some(uid) => Poll::find(uid).await?,
+            None => None,
+        };
+
+        let visibility = match tstatus.visibility {
+            1 => "unlisted",
+            2 => "private",
+            3 => "direct",
+            _ => "public",
+        };
+
+        let status = Status {
+            rowid: tstatus.rowid,
+            uid: tstatus.uid.to_owned(),
+            uri: tstatus.uri,
+            created_at: DateTime::from_timestamp(tstatus.created_at, 0)
+                .unwrap_or_default(),
+            account,
+            content: tstatus.text,
+            visibility: visibility.to_string(),
+            sensitive: tstatus.sensitive != 0,
+            spoiler_text: tstatus.spoiler_text,
+            media_attachments,
+            reblogs_count: TStatus::reblogs_count(tstatus.uid.to_owned())
+                .await?,
+            favourites_count: TFavourite::count(tstatus.uid.to_owned()).await?,
+            replies_count: TStatus::replies_count(tstatus.uid.to_owned())
+                .await?,
+            favourited,
+            pinned,
+            url: tstatus.url,
+            in_reply_to_id: tstatus.in_reply_to_id,
+            in_reply_to_account_id: tstatus.in_reply_to_account_id,
+            reblog,
+            poll,
+            language: tstatus.language.unwrap_or_default(),
+            edited_at: tstatus
+                .edited_at
+                .and_then(|x| DateTime::from_timestamp(x, 0))
+                .map(|x| x.to_rfc3339()),
+            ..Default::default()
+        };
+
+        Ok(status)
+    }
 }
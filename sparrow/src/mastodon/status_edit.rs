@@ -0,0 +1,72 @@
+//! Represents a revision of a status that has been edited.  
+//!
+//! Mastodon doc: <https://docs.joinmastodon.org/entities/StatusEdit/>
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::mastodon::account::uid::Uid as AccountUid;
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::custom_emoji::CustomEmoji;
+use crate::mastodon::media_attachment::MediaAttachment;
+use crate::table::status::Status as TStatus;
+use crate::table::status_edit::StatusEdit as TStatusEdit;
+
+/// Represents a revision of a status that has been edited.  
+/// Mastodon doc: <https://docs.joinmastodon.org/entities/StatusEdit/>
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+pub struct StatusEdit {
+    /// The content of the status at this revision.
+    pub content: String,
+    /// The content of the subject or content warning at this revision.
+    pub spoiler_text: String,
+    /// Whether the status was marked sensitive at this revision.
+    pub sensitive: bool,
+    /// The timestamp of when the revision was published.
+    pub created_at: DateTime<Utc>,
+    /// The account that published this revision.
+    pub account: MAccount,
+    /// The current state of the media attachments at this revision.
+    pub media_attachments: Vec<MediaAttachment>,
+    /// Any custom emoji that are used in the current revision.
+    pub emojis: Vec<CustomEmoji>,
+}
+
+impl StatusEdit {
+    /// Revisions of the status. Oldest first.  
+    /// Not edited status has only one revision, itself.
+    pub async fn history(tstatus: TStatus) -> Result<Vec<StatusEdit>> {
+        let account =
+            MAccount::get(AccountUid(tstatus.account_id.to_owned())).await?;
+
+        let edits = TStatusEdit::history(tstatus.uid.to_owned()).await?;
+        if edits.is_empty() {
+            return Ok(vec![StatusEdit {
+                content: tstatus.text,
+                spoiler_text: tstatus.spoiler_text,
+                sensitive: tstatus.sensitive != 0,
+                created_at: DateTime::from_timestamp(tstatus.created_at, 0)
+                    .unwrap_or_default(),
+                account,
+                ..Default::default()
+            }]);
+        }
+
+        let history = edits
+            .into_iter()
+            .map(|e| StatusEdit {
+                content: e.text,
+                spoiler_text: e.spoiler_text,
+                sensitive: e.sensitive.unwrap_or_default(),
+                created_at: DateTime::from_timestamp(e.created_at, 0)
+                    .unwrap_or_default(),
+                account: account.to_owned(),
+                ..Default::default()
+            })
+            .collect();
+
+        Ok(history)
+    }
+}
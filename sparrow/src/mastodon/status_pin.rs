@@ -0,0 +1,44 @@
+//! Statuses pinned to account's profile. (aka. featured)
+
+use anyhow::Result;
+
+use crate::mastodon::account::Account as MAccount;
+use crate::table::status::Get as _;
+use crate::table::status::Status as TStatus;
+use crate::table::status_pin::StatusPin as TStatusPin;
+
+/// StatusPin
+pub struct StatusPin;
+
+impl StatusPin {
+    /// a pins the status.
+    pub async fn new(a: &MAccount, tstatus: &TStatus) -> Result<()> {
+        TStatusPin::new(a.uid.to_string(), tstatus.uid.to_owned()).await
+    }
+
+    /// a unpins the status.
+    pub async fn undo(a: &MAccount, tstatus: &TStatus) -> Result<()> {
+        TStatusPin::remove(a.uid.to_string(), tstatus.uid.to_owned()).await
+    }
+
+    /// Is the status pinned by a?
+    pub async fn is_pinned(a: &MAccount, tstatus: &TStatus) -> Result<bool> {
+        TStatusPin::is_exist(a.uid.to_string(), tstatus.uid.to_owned()).await
+    }
+
+    /// Statuses pinned by a. Newest pin first. Deleted statuses are skipped.
+    pub async fn pins(a: &MAccount) -> Result<Vec<TStatus>> {
+        let mut statuses = Vec::new();
+        for pin in TStatusPin::pins(a.uid.to_string()).await? {
+            if let Some(s) = TStatus::get(("uid".to_string(), pin.status_uid))
+                .await?
+                .last()
+            {
+                if s.deleted_at.is_none() {
+                    statuses.push(s.to_owned());
+                }
+            }
+        }
+        Ok(statuses)
+    }
+}
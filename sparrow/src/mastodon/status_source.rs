@@ -0,0 +1,29 @@
+//! Represents a status's source as plain text.  
+//!
+//! Mastodon doc: <https://docs.joinmastodon.org/entities/StatusSource/>
+
+use serde::{Deserialize, Serialize};
+
+use crate::table::status::Status as TStatus;
+
+/// Represents a status's source as plain text.  
+/// Mastodon doc: <https://docs.joinmastodon.org/entities/StatusSource/>
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
+pub struct StatusSource {
+    /// ID(uuid v7) of the status in the database.
+    pub id: String,
+    /// The plain text used to compose the status.
+    pub text: String,
+    /// The plain text used to compose the status’s subject or content warning.
+    pub spoiler_text: String,
+}
+
+impl From<TStatus> for StatusSource {
+    fn from(tstatus: TStatus) -> Self {
+        StatusSource {
+            id: tstatus.uid,
+            text: tstatus.text,
+            spoiler_text: tstatus.spoiler_text,
+        }
+    }
+}
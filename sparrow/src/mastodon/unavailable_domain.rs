@@ -0,0 +1,84 @@
+//! Domains of dead servers.
+//!
+//! Delivery failures (connection errors and 5xx) are counted per domain.
+//! After `unavailable_domain_threshold` consecutive failures spanning
+//! `unavailable_domain_window` seconds, the domain is marked unavailable and
+//! nothing is delivered to it. A successful delivery resets the count, and
+//! the next verified inbound request from the domain makes it available
+//! again.
+
+use anyhow::Result;
+
+use crate::mastodon::setting::Setting;
+use crate::table::delivery_failure::DeliveryFailure as TDeliveryFailure;
+use crate::table::unavailable_domain::Get as _;
+use crate::table::unavailable_domain::UnavailableDomain as TUnavailableDomain;
+
+/// Failures before a domain is marked unavailable when
+/// `unavailable_domain_threshold` is not set.
+const DEFAULT_THRESHOLD: i64 = 10;
+
+/// Seconds the failures must span when `unavailable_domain_window` is not
+/// set. 1 day.
+const DEFAULT_WINDOW: i64 = 86400;
+
+pub struct UnavailableDomain;
+
+impl UnavailableDomain {
+    /// Is the domain marked unavailable?
+    pub async fn is_unavailable(domain: &str) -> Result<bool> {
+        let domains = TUnavailableDomain::get((
+            "domain".to_string(),
+            domain.to_lowercase(),
+        ))
+        .await?;
+        Ok(!domains.is_empty())
+    }
+
+    /// Count a delivery failure. Returns true when the domain became
+    /// unavailable.
+    pub async fn failed(domain: &str) -> Result<bool> {
+        let domain = domain.to_lowercase();
+        let record = TDeliveryFailure::increment(domain.to_owned()).await?;
+
+        let threshold = setting("unavailable_domain_threshold")
+            .await
+            .unwrap_or(DEFAULT_THRESHOLD);
+        let window = setting("unavailable_domain_window")
+            .await
+            .unwrap_or(DEFAULT_WINDOW);
+
+        if record.failures >= threshold
+            && record.last_failed_at - record.first_failed_at >= window
+        {
+            tracing::warn!(
+                "{} is unavailable after {} failures",
+                domain,
+                record.failures
+            );
+            TUnavailableDomain::new(domain).await?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Delivered to the domain. Reset its failures and make it available.
+    pub async fn delivered(domain: &str) -> Result<()> {
+        let domain = domain.to_lowercase();
+        TDeliveryFailure::reset(domain.to_owned()).await?;
+        TUnavailableDomain::remove(domain).await
+    }
+
+    /// Domain is reachable, e.g. it sent a verified request. Make it
+    /// available when it is marked unavailable.
+    pub async fn available(domain: &str) -> Result<()> {
+        if !Self::is_unavailable(domain).await? {
+            return Ok(());
+        }
+        Self::delivered(domain).await
+    }
+}
+
+async fn setting(key: &str) -> Option<i64> {
+    Setting::get(key).await.and_then(|x| x.parse::<i64>().ok())
+}
@@ -6,7 +6,11 @@ use anyhow::Result;
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
+use crate::mastodon::account::Account as MAccount;
+use crate::mastodon::account::Get as _;
+use crate::mastodon::user::Get as _;
 use crate::mastodon::user::User;
+use crate::table::account::Account as TAccount;
 use crate::table::user_role::Get;
 
 /// Represents a custom user role that grants permissions.  
@@ -61,6 +65,43 @@ pub struct UserRole {
 }
 
 impl UserRole {
+    /// Administrator. Bypasses all permissions.
+    pub const ADMINISTRATOR: i64 = 0x1;
+    /// Manage Reports.
+    pub const MANAGE_REPORTS: i64 = 0x10;
+
+    /// Whether the role has the permission.
+    pub fn has(&self, permission: i64) -> bool {
+        self.permissions & (Self::ADMINISTRATOR | permission) != 0
+    }
+
+    /// Whether the local account's user has the permission.
+    pub async fn permitted(
+        account: &MAccount,
+        permission: i64,
+    ) -> Result<bool> {
+        let user = match User::get(account.to_owned()).await? {
+            Some(u) if u.role_id.is_some() => u,
+            _ => return Ok(false),
+        };
+        Ok(Self::get(user)
+            .await?
+            .map(|x| x.has(permission))
+            .unwrap_or_default())
+    }
+
+    /// Local accounts whose users have the permission.
+    pub async fn accounts_with(permission: i64) -> Result<Vec<MAccount>> {
+        let mut accounts = Vec::new();
+        for taccount in TAccount::locals().await? {
+            let account = MAccount::get(taccount).await?;
+            if Self::permitted(&account, permission).await? {
+                accounts.push(account);
+            }
+        }
+        Ok(accounts)
+    }
+
     pub async fn get(user: User) -> Result<Option<UserRole>> {
         let role_id = user.role_id.unwrap();
 
@@ -14,17 +14,34 @@
 //!
 
 pub mod account;
+pub mod account_key;
 pub mod activity_log;
 pub mod actor_json;
+pub mod block;
 pub mod conversation;
 pub mod conversation_mute;
+pub mod delivery;
+pub mod delivery_failure;
+pub mod domain_block;
+pub mod favourite;
 pub mod follow;
+pub mod inbox_activity;
+pub mod job;
+pub mod media_attachment;
 pub mod mute;
+pub mod notification;
 pub mod oauth_access_grant;
 pub mod oauth_access_token;
 pub mod oauth_application;
+pub mod poll;
+pub mod poll_vote;
+pub mod report;
 pub mod setting;
+pub mod signature_scheme;
 pub mod status;
+pub mod status_edit;
+pub mod status_pin;
+pub mod unavailable_domain;
 pub mod user;
 pub mod user_role;
 
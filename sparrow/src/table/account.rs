@@ -13,6 +13,7 @@ use url::Url;
 
 use crate::activitystream::actor::person::Person as PersonActor;
 use crate::mastodon::account::actor_url::ActorUrl;
+use crate::mastodon::account::field::Field;
 use crate::mastodon::account::uri::Uri as AccountUri;
 use crate::table::FieldType;
 
@@ -118,6 +119,17 @@ impl Account {
         Ok(accounts)
     }
 
+    /// returns Account rows of this server
+    pub async fn locals() -> Result<Vec<Account>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let accounts: Vec<Account> = sqlx::query_as(
+            "SELECT rowid, * FROM account WHERE domain IS NULL",
+        )
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(accounts)
+    }
+
     /// To get federation_id
     pub async fn federation_id(self: &Self) -> Result<String> {
         let username = self.username.clone();
@@ -199,6 +211,49 @@ impl Account {
         let cnt = count.rows().next().unwrap().get::<i64>("cnt").unwrap();
         Ok(cnt > 0)
     }
+
+    /// Get Account by rowid.
+    pub async fn fr_rowid(rowid: i64) -> Result<Option<Account>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let accounts: Vec<Account> =
+            sqlx::query_as("SELECT rowid, * FROM account WHERE rowid = ?")
+                .bind(rowid)
+                .fetch_all(&sqlx_conn)
+                .await?;
+        Ok(accounts.last().map(|x| x.to_owned()))
+    }
+
+    /// Set account's alsoKnownAs. JSON array of actor urls.
+    pub async fn set_also_known_as(
+        uid: String,
+        also_known_as: Option<String>,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE account SET also_known_as = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(also_known_as)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark account as moved to the other account(rowid).
+    pub async fn set_moved_to(
+        uid: String,
+        moved_to_account_id: Option<i64>,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE account SET moved_to_account_id = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(moved_to_account_id)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
 }
 
 /// I am a trait Get<T>
@@ -275,9 +330,9 @@ impl New<PersonActor> for Account {
         Ok(())
     }
 
-    async fn update(_actor: PersonActor) -> Result<()> {
-        // Todo: Here
-        Ok(())
+    async fn update(actor: PersonActor) -> Result<()> {
+        let account = Self::try_from(actor)?;
+        Self::update(account).await
     }
 }
 
@@ -481,6 +536,30 @@ impl TryFrom<PersonActor> for Account {
             .map(|x| x.to_owned().media_type)
             .unwrap_or_default();
 
+        // PropertyValue attachments are profile fields.
+        let fields: Vec<Field> = actor
+            .attachment
+            .to_owned()
+            .unwrap_or_default()
+            .iter()
+            .filter(|x| {
+                x.get("type").and_then(|t| t.as_str()) == Some("PropertyValue")
+            })
+            .map(|x| Field {
+                name: x
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                value: x
+                    .get("value")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                verified_at: "".to_string(),
+            })
+            .collect();
+
         let account = Account {
             uid: uuid::Uuid::now_v7().to_string(),
             username: actor.preferred_username.to_lowercase(),
@@ -508,6 +587,14 @@ impl TryFrom<PersonActor> for Account {
             shared_inbox_url: Some(actor.endpoints.shared_inbox), // default(""), not null
             following_url: Some(actor.following),
             followers_url: Some(actor.followers), // default(""), not null
+            locked: actor.manually_approves_followers.map(|x| match x {
+                false => 0,
+                true => 1,
+            }),
+            fields: Some(serde_json::to_string(&fields)?),
+            also_known_as: actor
+                .also_known_as
+                .map(|x| serde_json::to_string(&x).unwrap()),
             memorial: actor.memorial.map(|x| match x {
                 false => 0,
                 true => 1,
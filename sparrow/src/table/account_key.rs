@@ -0,0 +1,75 @@
+//! account_key table
+//!
+//! Ed25519 keys of actors.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// account_key table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct AccountKey {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// verificationMethod id
+    pub key_id: String,
+    /// Actor url owning the key
+    pub controller: String,
+    pub key_type: String,
+    /// publicKeyMultibase
+    pub public_key: String,
+    /// PKCS#8 pem. Local accounts only.
+    pub private_key: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl AccountKey {
+    /// Store the key. Public key of known key_id is replaced unless the key
+    /// has a private key or another controller.
+    pub async fn new(
+        key_id: String,
+        controller: String,
+        public_key: String,
+        private_key: Option<String>,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT INTO account_key(uid, key_id, controller, public_key, private_key) VALUES (?, ?, ?, ?, ?) ON CONFLICT(key_id) DO UPDATE SET public_key = excluded.public_key, updated_at = unixepoch() WHERE account_key.private_key IS NULL AND account_key.controller = excluded.controller",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(key_id)
+        .bind(controller)
+        .bind(public_key)
+        .bind(private_key)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<AccountKey>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for AccountKey {
+    async fn get((key, val): (String, String)) -> Result<Vec<AccountKey>> {
+        let query_template =
+            format!("SELECT rowid, * FROM account_key WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let keys = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(keys)
+    }
+}
@@ -0,0 +1,98 @@
+//! block table
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// block table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct Block {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+    /// Account's uid who blocks.
+    pub account_uid: String,
+    /// Blocked account's uid.
+    pub target_account_uid: String,
+    /// Block activity's id.
+    pub uri: Option<String>,
+}
+
+impl Block {
+    /// Record block from a to b.
+    pub async fn new(uri: String, a: String, b: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO block(uid, account_uid, target_account_uid, uri) VALUES (?, ?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(a)
+        .bind(b)
+        .bind(uri)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete block from a to b.
+    pub async fn remove(a: String, b: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "DELETE FROM block WHERE account_uid = ? AND target_account_uid = ?",
+        )
+        .bind(a)
+        .bind(b)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Block record from a to b.
+    pub async fn record(a: String, b: String) -> Result<Option<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let record: Vec<Self> = sqlx::query_as(
+            "SELECT rowid, * FROM block WHERE account_uid = ? AND target_account_uid = ?",
+        )
+        .bind(a)
+        .bind(b)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(record.last().map(|x| x.to_owned()))
+    }
+
+    /// Accounts blocked by the account. Newest first.
+    pub async fn blocks(account_uid: String) -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let blocks: Vec<Self> = sqlx::query_as(
+            "SELECT rowid, * FROM block WHERE account_uid = ? ORDER BY created_at DESC",
+        )
+        .bind(account_uid)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(blocks)
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<Block>>;
+}
+
+#[async_trait]
+impl Get<(String, String)> for Block {
+    async fn get((key, val): (String, String)) -> Result<Vec<Block>> {
+        let query_template =
+            format!("SELECT rowid, * FROM block WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let blocks = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(blocks)
+    }
+}
@@ -0,0 +1,164 @@
+//! delivery table
+//!
+//! Outbound activities waiting for delivery, keyed by inbox url.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// Seconds after which a locked delivery is considered abandoned.
+const LOCK_TIMEOUT: i64 = 300;
+
+/// delivery table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct Delivery {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// Recipient's inbox (or shared inbox) url.
+    pub inbox_url: String,
+    /// Sender account's uid. Its key signs the request.
+    pub account_uid: String,
+    pub activity_id: String,
+    /// Activity json.
+    pub body: String,
+    pub attempts: i64,
+    /// Not sent before this time.
+    pub run_at: i64,
+    pub locked_at: Option<i64>,
+    /// Response status or error of the last attempt.
+    pub last_status: Option<String>,
+    pub delivered_at: Option<i64>,
+    /// Set when the delivery gave up.
+    pub failed_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Delivery {
+    /// Queue the activity to the inbox.
+    /// Same activity is queued once per inbox.
+    pub async fn new(
+        inbox_url: String,
+        account_uid: String,
+        activity_id: String,
+        body: String,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO delivery(uid, inbox_url, account_uid, activity_id, body) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(inbox_url)
+        .bind(account_uid)
+        .bind(activity_id)
+        .bind(body)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Deliveries ready to send. Oldest first.
+    pub async fn due(limit: i64) -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let deliveries = sqlx::query_as(
+            "SELECT rowid, * FROM delivery WHERE delivered_at IS NULL AND failed_at IS NULL AND run_at <= unixepoch() AND (locked_at IS NULL OR locked_at <= unixepoch() - ?) ORDER BY run_at, rowid LIMIT ?",
+        )
+        .bind(LOCK_TIMEOUT)
+        .bind(limit)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(deliveries)
+    }
+
+    /// Lock the delivery and count the attempt.
+    /// Returns false when another worker has it.
+    pub async fn lock(uid: String) -> Result<bool> {
+        let sqlx_conn = dbcon::open_default()?;
+        let result = sqlx::query(
+            "UPDATE delivery SET locked_at = unixepoch(), attempts = attempts + 1, updated_at = unixepoch() WHERE uid = ? AND delivered_at IS NULL AND failed_at IS NULL AND (locked_at IS NULL OR locked_at <= unixepoch() - ?)",
+        )
+        .bind(uid)
+        .bind(LOCK_TIMEOUT)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark the delivery done.
+    pub async fn delivered(uid: String, status: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE delivery SET delivered_at = unixepoch(), locked_at = NULL, last_status = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(status)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Unlock the delivery to send again after `delay` seconds.
+    pub async fn retry(uid: String, delay: i64, status: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE delivery SET run_at = unixepoch() + ?, locked_at = NULL, last_status = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(delay)
+        .bind(status)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Give up the delivery.
+    pub async fn fail(uid: String, status: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE delivery SET failed_at = unixepoch(), locked_at = NULL, last_status = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(status)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Remove delivered or failed deliveries older than `age` seconds.
+    pub async fn purge(age: i64) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "DELETE FROM delivery WHERE (delivered_at IS NOT NULL OR failed_at IS NOT NULL) AND updated_at <= unixepoch() - ?",
+        )
+        .bind(age)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<Delivery>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for Delivery {
+    async fn get((key, val): (String, String)) -> Result<Vec<Delivery>> {
+        let query_template =
+            format!("SELECT rowid, * FROM delivery WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let deliveries = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(deliveries)
+    }
+}
@@ -0,0 +1,73 @@
+//! delivery_failure table
+//!
+//! Consecutive delivery failures per domain.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+
+/// delivery_failure table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct DeliveryFailure {
+    pub rowid: Option<i64>,
+    pub domain: String,
+    pub failures: i64,
+    /// When the streak of failures started.
+    pub first_failed_at: i64,
+    pub last_failed_at: i64,
+}
+
+impl DeliveryFailure {
+    /// Count a failure of the domain. Returns the updated record.
+    pub async fn increment(domain: String) -> Result<Self> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT INTO delivery_failure(domain, failures) VALUES (?, 1) ON CONFLICT(domain) DO UPDATE SET failures = failures + 1, last_failed_at = unixepoch()",
+        )
+        .bind(domain.to_owned())
+        .execute(&sqlx_conn)
+        .await?;
+        let record = sqlx::query_as(
+            "SELECT rowid, * FROM delivery_failure WHERE domain = ?",
+        )
+        .bind(domain)
+        .fetch_one(&sqlx_conn)
+        .await?;
+        Ok(record)
+    }
+
+    /// Forget failures of the domain.
+    pub async fn reset(domain: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query("DELETE FROM delivery_failure WHERE domain = ?")
+            .bind(domain)
+            .execute(&sqlx_conn)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<DeliveryFailure>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for DeliveryFailure {
+    async fn get((key, val): (String, String)) -> Result<Vec<DeliveryFailure>> {
+        let query_template =
+            format!("SELECT rowid, * FROM delivery_failure WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let failures = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(failures)
+    }
+}
@@ -0,0 +1,62 @@
+//! domain_block table
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+
+/// domain_block table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct DomainBlock {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    pub domain: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    /// 0: silence, 1: suspend, 2: noop
+    pub severity: Option<i64>,
+    pub reject_media: bool,
+    pub reject_reports: bool,
+    pub private_comment: Option<String>,
+    pub public_comment: Option<String>,
+    pub obfuscate: bool,
+}
+
+impl DomainBlock {
+    /// Block of the domain or its parent domain.
+    pub async fn of_domain(domain: String) -> Result<Option<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let blocks: Vec<Self> = sqlx::query_as(
+            "SELECT rowid, * FROM domain_block WHERE ? = domain OR ? LIKE '%.' || domain ORDER BY length(domain) DESC",
+        )
+        .bind(domain.to_owned())
+        .bind(domain)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(blocks.first().cloned())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<DomainBlock>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for DomainBlock {
+    async fn get((key, val): (String, String)) -> Result<Vec<DomainBlock>> {
+        let query_template =
+            format!("SELECT rowid, * FROM domain_block WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let blocks = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(blocks)
+    }
+}
@@ -0,0 +1,104 @@
+//! favourite table
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// favourite table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct Favourite {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+    /// Account's uid who favourited.
+    pub account_uid: String,
+    /// Favourited status's uid.
+    pub status_uid: String,
+    /// Like activity's id.
+    pub uri: Option<String>,
+}
+
+impl Favourite {
+    /// Record favourite and return its uid.
+    /// Same account can favourite a status only once.
+    pub async fn new(
+        uri: String,
+        account_uid: String,
+        status_uid: String,
+    ) -> Result<String> {
+        let sqlx_conn = dbcon::open_default()?;
+        let uid = Uuid::now_v7().to_string();
+        sqlx::query(
+            "INSERT INTO favourite(uid, account_uid, status_uid, uri) VALUES (?, ?, ?, ?)",
+        )
+        .bind(uid.to_owned())
+        .bind(account_uid)
+        .bind(status_uid)
+        .bind(uri)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(uid)
+    }
+
+    /// Delete favourite with Like activity's id.
+    pub async fn remove(uri: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query("DELETE FROM favourite WHERE uri = ?")
+            .bind(uri)
+            .execute(&sqlx_conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Count favourites of the status.
+    pub async fn count(status_uid: String) -> Result<i64> {
+        let sqlx_conn = dbcon::open_default()?;
+        let (cnt,): (i64,) = sqlx::query_as(
+            "SELECT count(rowid) AS CNT FROM favourite WHERE status_uid = ?",
+        )
+        .bind(status_uid)
+        .fetch_one(&sqlx_conn)
+        .await?;
+        Ok(cnt)
+    }
+
+    /// Check the account favourited the status.
+    pub async fn is_exist(
+        account_uid: String,
+        status_uid: String,
+    ) -> Result<bool> {
+        let sqlx_conn = dbcon::open_default()?;
+        let (cnt,): (i64,) = sqlx::query_as(
+            "SELECT count(rowid) AS CNT FROM favourite WHERE account_uid = ? AND status_uid = ?",
+        )
+        .bind(account_uid)
+        .bind(status_uid)
+        .fetch_one(&sqlx_conn)
+        .await?;
+        Ok(cnt > 0)
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<Favourite>>;
+}
+
+#[async_trait]
+impl Get<(String, String)> for Favourite {
+    async fn get((key, val): (String, String)) -> Result<Vec<Favourite>> {
+        let query_template =
+            format!("SELECT rowid, * FROM favourite WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let favourites = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(favourites)
+    }
+}
@@ -25,6 +25,8 @@ pub struct Follow {
     pub uri: Option<String>,
     pub notify: Option<bool>,
     pub languages: Option<String>,
+    /// Follow request which is not accepted yet.
+    pub pending: Option<bool>,
 }
 
 impl Follow {
@@ -35,13 +37,14 @@ impl Follow {
         uri: String,
         sub: AccountUid,
         obj: AccountUid,
+        pending: bool,
     ) -> Result<()> {
         let sqlx_conn = dbcon::open_default()?;
 
         // if follow is already exist, do update or instert
 
         let a = sqlx::query(
-            "INSERT INTO follow(uid, account_uid, target_account_uid, uri) VALUES (?, ?, ?, ?)",
+            "INSERT INTO follow(uid, account_uid, target_account_uid, uri, pending) VALUES (?, ?, ?, ?, ?)",
         )
         .bind(Uuid::now_v7().to_string())
         // .bind(
@@ -53,6 +56,7 @@ impl Follow {
         .bind(sub.to_string())
         .bind(obj.to_string())
         .bind(uri)
+        .bind(pending)
         .execute(&sqlx_conn)
         .await;
 
@@ -70,6 +74,7 @@ impl Follow {
         uri: String,
         sub: AccountUid,
         obj: AccountUid,
+        pending: bool,
     ) -> Result<()> {
         let sqlx_conn = dbcon::open_default()?;
 
@@ -82,9 +87,10 @@ impl Follow {
             .to_string();
 
         let a =
-            sqlx::query("UPDATE follow SET updated_at = ?, uri = ? WHERE account_uid = ? AND target_account_uid = ?")
+            sqlx::query("UPDATE follow SET updated_at = ?, uri = ?, pending = ? WHERE account_uid = ? AND target_account_uid = ?")
                 .bind(updated_at)
                 .bind(uri)
+                .bind(pending)
                 .bind(sub.to_string())
                 .bind(obj.to_string())
                 .execute(&sqlx_conn)
@@ -113,7 +119,7 @@ impl Follow {
     pub async fn follower_count(taccount: TAccount) -> Result<u64> {
         let sqlx_conn = dbcon::open_default()?;
         let follows: (i64,) = sqlx::query_as(
-            "SELECT count(*) FROM follow WHERE target_account_uid = ?",
+            "SELECT count(*) FROM follow WHERE target_account_uid = ? AND pending = false",
         )
         .bind(taccount.uid)
         .fetch_one(&sqlx_conn)
@@ -124,7 +130,7 @@ impl Follow {
     pub async fn following_count(taccount: TAccount) -> Result<u64> {
         let sqlx_conn = dbcon::open_default()?;
         let followings: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) AS COUNT FROM follow WHERE account_uid = ?",
+            "SELECT COUNT(*) AS COUNT FROM follow WHERE account_uid = ? AND pending = false",
         )
         .bind(taccount.uid)
         .fetch_one(&sqlx_conn)
@@ -135,7 +141,7 @@ impl Follow {
     pub async fn followers(account_uuid: String) -> Result<Vec<Self>> {
         let sqlx_conn = dbcon::open_default()?;
         let followings: Vec<Follow> = sqlx::query_as(
-            "SELECT rowid, * FROM follow WHERE target_account_uid = ?",
+            "SELECT rowid, * FROM follow WHERE target_account_uid = ? AND pending = false",
         )
         .bind(account_uuid)
         .fetch_all(&sqlx_conn)
@@ -145,11 +151,12 @@ impl Follow {
 
     pub async fn followings(account_uuid: String) -> Result<Vec<Self>> {
         let sqlx_conn = dbcon::open_default()?;
-        let followings: Vec<Follow> =
-            sqlx::query_as("SELECT rowid, * FROM follow WHERE account_id = ?")
-                .bind(account_uuid)
-                .fetch_all(&sqlx_conn)
-                .await?;
+        let followings: Vec<Follow> = sqlx::query_as(
+            "SELECT rowid, * FROM follow WHERE account_uid = ? AND pending = false",
+        )
+        .bind(account_uuid)
+        .fetch_all(&sqlx_conn)
+        .await?;
         Ok(followings)
     }
 
@@ -182,7 +189,7 @@ impl Follow {
     pub async fn relation(a: String, b: String) -> Result<usize> {
         let sqlx_conn = dbcon::open_default()?;
         let sql_template =
-            "SELECT rowid FROM follow WHERE account_uid = ? AND target_account_uid = ?";
+            "SELECT rowid FROM follow WHERE account_uid = ? AND target_account_uid = ? AND pending = false";
         let case_a = sqlx::query(sql_template)
             .bind(a.clone())
             .bind(b.clone())
@@ -220,6 +227,31 @@ impl Follow {
         Ok(record.last().map(|x| x.to_owned()))
     }
 
+    /// Pending follow requests to the account. Oldest first.
+    pub async fn requests(account_uuid: String) -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let requests: Vec<Follow> = sqlx::query_as(
+            "SELECT rowid, * FROM follow WHERE target_account_uid = ? AND pending = true ORDER BY created_at ASC",
+        )
+        .bind(account_uuid)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(requests)
+    }
+
+    /// Delete follow row between two accounts.
+    pub async fn remove(a: String, b: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "DELETE FROM follow WHERE account_uid = ? AND target_account_uid = ?",
+        )
+        .bind(a)
+        .bind(b)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
     pub async fn unfollow(uri: String) -> Result<()> {
         let sqlx_conn = dbcon::open_default()?;
         let a = sqlx::query("DELETE FROM follow WHERE uri = ?")
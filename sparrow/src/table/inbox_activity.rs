@@ -0,0 +1,85 @@
+//! inbox_activity table
+//!
+//! Ids of inbound activities already processed.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+
+/// inbox_activity table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct InboxActivity {
+    pub rowid: Option<i64>,
+    /// Activity's id
+    pub uri: String,
+    pub actor: Option<String>,
+    pub created_at: i64,
+}
+
+impl InboxActivity {
+    /// Record the activity id.
+    /// Returns false when it was already recorded within `window` seconds.
+    pub async fn claim(
+        uri: String,
+        actor: Option<String>,
+        window: i64,
+    ) -> Result<bool> {
+        let sqlx_conn = dbcon::open_default()?;
+        let result = sqlx::query(
+            "INSERT INTO inbox_activity(uri, actor) VALUES (?, ?) ON CONFLICT(uri) DO UPDATE SET actor = excluded.actor, created_at = unixepoch() WHERE created_at <= unixepoch() - ?",
+        )
+        .bind(uri)
+        .bind(actor)
+        .bind(window)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Forget the activity id so that the sender's retry is processed.
+    pub async fn release(uri: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query("DELETE FROM inbox_activity WHERE uri = ?")
+            .bind(uri)
+            .execute(&sqlx_conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Remove ids older than `window` seconds.
+    pub async fn purge(window: i64) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "DELETE FROM inbox_activity WHERE created_at <= unixepoch() - ?",
+        )
+        .bind(window)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<InboxActivity>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for InboxActivity {
+    async fn get((key, val): (String, String)) -> Result<Vec<InboxActivity>> {
+        let query_template =
+            format!("SELECT rowid, * FROM inbox_activity WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let activities = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(activities)
+    }
+}
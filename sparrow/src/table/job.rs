@@ -0,0 +1,175 @@
+//! job table
+//!
+//! Background jobs drained by the worker.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// Seconds after which a locked job is considered abandoned.
+const LOCK_TIMEOUT: i64 = 300;
+
+/// job table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct Job {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// Queue name. e.g. "inbox"
+    pub queue: String,
+    /// Job arguments in json.
+    pub payload: String,
+    pub attempts: i64,
+    pub max_attempts: i64,
+    /// Not run before this time.
+    pub run_at: i64,
+    pub locked_at: Option<i64>,
+    /// Failure reason of the last attempt.
+    pub last_error: Option<String>,
+    pub finished_at: Option<i64>,
+    /// Set when the job gave up.
+    pub failed_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Job {
+    /// Queue a job. Returns its uid.
+    pub async fn new(
+        queue: String,
+        payload: String,
+        max_attempts: i64,
+    ) -> Result<String> {
+        let uid = Uuid::now_v7().to_string();
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT INTO job(uid, queue, payload, max_attempts) VALUES (?, ?, ?, ?)",
+        )
+        .bind(uid.to_owned())
+        .bind(queue)
+        .bind(payload)
+        .bind(max_attempts)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(uid)
+    }
+
+    /// Jobs of the queue ready to run. Oldest first.
+    pub async fn due(queue: String, limit: i64) -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let jobs = sqlx::query_as(
+            "SELECT rowid, * FROM job WHERE queue = ? AND finished_at IS NULL AND failed_at IS NULL AND attempts < max_attempts AND run_at <= unixepoch() AND (locked_at IS NULL OR locked_at <= unixepoch() - ?) ORDER BY run_at, rowid LIMIT ?",
+        )
+        .bind(queue)
+        .bind(LOCK_TIMEOUT)
+        .bind(limit)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(jobs)
+    }
+
+    /// Lock the job and count the attempt.
+    /// Returns false when another worker has it.
+    pub async fn lock(uid: String) -> Result<bool> {
+        let sqlx_conn = dbcon::open_default()?;
+        let result = sqlx::query(
+            "UPDATE job SET locked_at = unixepoch(), attempts = attempts + 1, updated_at = unixepoch() WHERE uid = ? AND finished_at IS NULL AND failed_at IS NULL AND attempts < max_attempts AND (locked_at IS NULL OR locked_at <= unixepoch() - ?)",
+        )
+        .bind(uid)
+        .bind(LOCK_TIMEOUT)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Mark the job finished.
+    pub async fn done(uid: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE job SET finished_at = unixepoch(), locked_at = NULL, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Unlock the job to run again after `delay` seconds.
+    pub async fn retry(uid: String, delay: i64, error: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE job SET run_at = unixepoch() + ?, locked_at = NULL, last_error = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(delay)
+        .bind(error)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Give up the job.
+    pub async fn fail(uid: String, error: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE job SET failed_at = unixepoch(), locked_at = NULL, last_error = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(error)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Give up jobs of the queue that used all attempts without reporting
+    /// back, e.g. the worker crashed while running them.
+    pub async fn exhaust(queue: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE job SET failed_at = unixepoch(), locked_at = NULL, last_error = COALESCE(last_error, 'attempts exhausted'), updated_at = unixepoch() WHERE queue = ? AND finished_at IS NULL AND failed_at IS NULL AND attempts >= max_attempts AND (locked_at IS NULL OR locked_at <= unixepoch() - ?)",
+        )
+        .bind(queue)
+        .bind(LOCK_TIMEOUT)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Remove finished jobs older than `age` seconds.
+    /// Failed jobs are kept for inspection.
+    pub async fn purge(age: i64) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "DELETE FROM job WHERE finished_at IS NOT NULL AND finished_at <= unixepoch() - ?",
+        )
+        .bind(age)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<Job>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for Job {
+    async fn get((key, val): (String, String)) -> Result<Vec<Job>> {
+        let query_template =
+            format!("SELECT rowid, * FROM job WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let jobs = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(jobs)
+    }
+}
@@ -1,54 +1,107 @@
-use chrono::{DateTime, Utc};
+//! media_attachments table
 
-pub struct MediaAttachement {
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// media_attachments table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct MediaAttachment {
+    pub rowid: Option<i64>,
     pub uid: String,
-    pub status_id: String,
-    pub file_file_name: String,
-    pub file_content_type: String,
-    pub file_file_size: i64,
-    pub file_updated_at: DateTime<Utc>,
+    /// Status's uid the media is attached to.
+    pub status_id: Option<String>,
+    pub file_file_name: Option<String>,
+    pub file_content_type: Option<String>,
+    pub file_file_size: Option<i64>,
+    pub file_updated_at: Option<i64>,
     pub remote_url: String,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-    pub shortcode: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub shortcode: Option<String>,
+    /// 0: image, 1: gifv, 2: video, 3: unknown, 4: audio
     #[serde(rename(serialize = "type", deserialize = "type"))]
+    #[sqlx(rename = "type")]
     pub media_type: i64,
-    pub file_meta: String,
-    pub account_id: String,
-    pub description: String,
-    pub scheduled_status_id: String,
-    pub blurhash: String,
-    pub processing: i64,
-    pub file_storage_schema_version: i64,
-    pub thumbnail_file_name: String,
-    pub thumbnail_content_type: String,
-    pub thumbnail_file_size: i64,
-    pub thumbnail_updated_at: DateTime<Utc>,
-    pub thumbnail_remote_url: String,
+    pub file_meta: Option<String>,
+    /// Account's uid who uploaded.
+    pub account_id: Option<String>,
+    pub description: Option<String>,
+    pub scheduled_status_id: Option<String>,
+    pub blurhash: Option<String>,
+    pub processing: Option<i64>,
+    pub file_storage_schema_version: Option<i64>,
+    pub thumbnail_file_name: Option<String>,
+    pub thumbnail_content_type: Option<String>,
+    pub thumbnail_file_size: Option<i64>,
+    pub thumbnail_updated_at: Option<i64>,
+    pub thumbnail_remote_url: Option<String>,
+}
+
+impl MediaAttachment {
+    /// Record remote media attached to the status and return its uid.
+    pub async fn new(
+        account_id: String,
+        status_id: String,
+        remote_url: String,
+        media_type: i64,
+        file_content_type: Option<String>,
+        description: Option<String>,
+        blurhash: Option<String>,
+    ) -> Result<String> {
+        let sqlx_conn = dbcon::open_default()?;
+        let uid = Uuid::now_v7().to_string();
+        sqlx::query(
+            "INSERT INTO media_attachments(uid, account_id, status_id, remote_url, type, file_content_type, description, blurhash) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(uid.to_owned())
+        .bind(account_id)
+        .bind(status_id)
+        .bind(remote_url)
+        .bind(media_type)
+        .bind(file_content_type)
+        .bind(description)
+        .bind(blurhash)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(uid)
+    }
+
+    /// Media attached to the status. In attached order.
+    pub async fn of_status(status_id: String) -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let medias: Vec<Self> = sqlx::query_as(
+            "SELECT rowid, * FROM media_attachments WHERE status_id = ? ORDER BY rowid",
+        )
+        .bind(status_id)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(medias)
+    }
 }
 
 #[async_trait]
 pub trait Get<T> {
-    async fn get(arg: T) -> Result<Vec<MediaAttachement>>;
+    async fn get(arg: T) -> Result<Vec<MediaAttachment>>;
 }
 
 /// Very generic table Get function
 /// Geting (key: String, val: String).
 /// This goes `SELECT * FROM some_table WHERER key = val`
 #[async_trait]
-impl Get<(String, String)> for MediaAttachement {
-    async fn get(
-        (key, val): (String, String),
-    ) -> Result<Vec<MediaAttachement>> {
-        let query_template = format!(
-            "SELECT rowid, * FROM media_attachement WHERE {} = ?",
-            key
-        );
+impl Get<(String, String)> for MediaAttachment {
+    async fn get((key, val): (String, String)) -> Result<Vec<MediaAttachment>> {
+        let query_template =
+            format!("SELECT rowid, * FROM media_attachments WHERE {} = ?", key);
         let sqlx_conn = dbcon::open_default()?;
-        let accounts = sqlx::query_as(query_template.as_str())
+        let medias = sqlx::query_as(query_template.as_str())
             .bind(val)
             .fetch_all(&sqlx_conn)
             .await?;
-        Ok(accounts)
+        Ok(medias)
     }
 }
@@ -1,21 +1,72 @@
+//! notification table
+
+use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use serde_json;
-use serde_json::Value;
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
 
+/// notification table in Database
 #[derive(
     Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
 )]
 pub struct Notification {
-    pub rowid: i64,
+    pub rowid: Option<i64>,
     pub uid: String,
+    /// uid of the row that caused this notification. (status, follow...)
     pub activity_id: String,
+    /// Type of the row that caused this notification. (Status, Follow...)
     pub activity_type: String,
-    pub created_at: Timestamp,
-    pub updated_at: Timestamp,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+    /// Recipient account's uid
     pub account_id: String,
+    /// Account's uid who did the action.
     pub from_account_id: String,
-    pub notification_type: String,
+    /// Mastodon notification type. (reblog, favourite, follow...)
+    #[sqlx(rename = "type")]
+    pub notification_type: Option<String>,
+}
+
+impl Notification {
+    /// Insert new notification.
+    pub async fn new(
+        activity_id: String,
+        activity_type: String,
+        account_id: String,
+        from_account_id: String,
+        notification_type: String,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT INTO notification(uid, activity_id, activity_type, account_id, from_account_id, type) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(activity_id)
+        .bind(activity_type)
+        .bind(account_id)
+        .bind(from_account_id)
+        .bind(notification_type)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete notifications caused by given activity row.
+    pub async fn remove(
+        activity_id: String,
+        activity_type: String,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "DELETE FROM notification WHERE activity_id = ? AND activity_type = ?",
+        )
+        .bind(activity_id)
+        .bind(activity_type)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,127 @@
+//! poll table
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// poll table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct Poll {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// Poll author's account uid.
+    pub account_uid: String,
+    /// Status' uid the poll is attached to.
+    pub status_uid: String,
+    pub expires_at: Option<i64>,
+    /// Option titles. JSON array.
+    pub options: String,
+    /// Votes count of each option. JSON array.
+    pub cached_tallies: String,
+    pub multiple: bool,
+    pub hide_totals: bool,
+    pub votes_count: i64,
+    pub last_fetched_at: Option<i64>,
+    /// When the final result of the local poll was sent to followers.
+    pub closed_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub voters_count: Option<i64>,
+}
+
+impl Poll {
+    /// Record poll and return its uid.
+    pub async fn new(
+        account_uid: String,
+        status_uid: String,
+        expires_at: Option<i64>,
+        options: Vec<String>,
+        cached_tallies: Vec<i64>,
+        multiple: bool,
+        voters_count: Option<i64>,
+    ) -> Result<String> {
+        let sqlx_conn = dbcon::open_default()?;
+        let uid = Uuid::now_v7().to_string();
+        sqlx::query(
+            "INSERT INTO poll(uid, account_uid, status_uid, expires_at, options, cached_tallies, multiple, votes_count, voters_count) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(uid.to_owned())
+        .bind(account_uid)
+        .bind(status_uid)
+        .bind(expires_at)
+        .bind(serde_json::to_string(&options)?)
+        .bind(serde_json::to_string(&cached_tallies)?)
+        .bind(multiple)
+        .bind(cached_tallies.iter().sum::<i64>())
+        .bind(voters_count)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(uid)
+    }
+
+    /// Replace votes count of each option.
+    pub async fn update_tallies(
+        uid: String,
+        cached_tallies: Vec<i64>,
+        voters_count: Option<i64>,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE poll SET cached_tallies = ?, votes_count = ?, voters_count = ?, last_fetched_at = unixepoch(), updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(serde_json::to_string(&cached_tallies)?)
+        .bind(cached_tallies.iter().sum::<i64>())
+        .bind(voters_count)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Expired polls of the account whose final result is not sent yet.
+    pub async fn expired_unclosed(account_uid: String) -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let polls: Vec<Self> = sqlx::query_as(
+            "SELECT rowid, * FROM poll WHERE account_uid = ? AND closed_at IS NULL AND expires_at <= unixepoch()",
+        )
+        .bind(account_uid)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(polls)
+    }
+
+    /// Mark final result of the poll as sent.
+    pub async fn close(uid: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE poll SET closed_at = unixepoch(), updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<Poll>>;
+}
+
+#[async_trait]
+impl Get<(String, String)> for Poll {
+    async fn get((key, val): (String, String)) -> Result<Vec<Poll>> {
+        let query_template =
+            format!("SELECT rowid, * FROM poll WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let polls = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(polls)
+    }
+}
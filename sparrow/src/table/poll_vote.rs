@@ -0,0 +1,87 @@
+//! poll_vote table
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// poll_vote table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct PollVote {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// Voter's account uid.
+    pub account_uid: String,
+    pub poll_uid: String,
+    /// Index of the chosen option.
+    pub choice: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    /// Vote(Note) object's id.
+    pub uri: Option<String>,
+}
+
+impl PollVote {
+    /// Record vote. Same choice of the account is counted once.
+    pub async fn new(
+        account_uid: String,
+        poll_uid: String,
+        choice: i64,
+        uri: Option<String>,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO poll_vote(uid, account_uid, poll_uid, choice, uri) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(account_uid)
+        .bind(poll_uid)
+        .bind(choice)
+        .bind(uri)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Choices of the account on the poll.
+    pub async fn choices(
+        account_uid: String,
+        poll_uid: String,
+    ) -> Result<Vec<i64>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let choices: Vec<(i64,)> = sqlx::query_as(
+            "SELECT choice FROM poll_vote WHERE account_uid = ? AND poll_uid = ? ORDER BY choice",
+        )
+        .bind(account_uid)
+        .bind(poll_uid)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(choices.into_iter().map(|(x,)| x).collect())
+    }
+
+    /// Votes count of each choice. (choice, count)
+    pub async fn tallies(poll_uid: String) -> Result<Vec<(i64, i64)>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let tallies: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT choice, count(*) FROM poll_vote WHERE poll_uid = ? GROUP BY choice",
+        )
+        .bind(poll_uid)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(tallies)
+    }
+
+    /// Count accounts who voted on the poll.
+    pub async fn voters_count(poll_uid: String) -> Result<i64> {
+        let sqlx_conn = dbcon::open_default()?;
+        let (cnt,): (i64,) = sqlx::query_as(
+            "SELECT count(DISTINCT account_uid) FROM poll_vote WHERE poll_uid = ?",
+        )
+        .bind(poll_uid)
+        .fetch_one(&sqlx_conn)
+        .await?;
+        Ok(cnt)
+    }
+}
@@ -0,0 +1,106 @@
+//! report table
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// report table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct Report {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// Reported statuses' uid. JSON array.
+    pub status_uids: String,
+    pub comment: String,
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+    /// Reporter's account uid.
+    pub account_uid: String,
+    pub action_taken_by_account_uid: Option<String>,
+    /// Reported account's uid.
+    pub target_account_uid: String,
+    pub assigned_account_uid: Option<String>,
+    /// Flag activity's id.
+    pub uri: Option<String>,
+    pub forwarded: Option<bool>,
+    /// spam, violation, legal or other
+    pub category: String,
+    pub action_taken_at: Option<i64>,
+    pub rule_ids: Option<String>,
+}
+
+impl Report {
+    /// Record report and return its uid.
+    pub async fn new(
+        uri: String,
+        account_uid: String,
+        target_account_uid: String,
+        status_uids: Vec<String>,
+        comment: String,
+    ) -> Result<String> {
+        let sqlx_conn = dbcon::open_default()?;
+        let uid = Uuid::now_v7().to_string();
+        sqlx::query(
+            "INSERT INTO report(uid, status_uids, comment, account_uid, target_account_uid, uri) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(uid.to_owned())
+        .bind(serde_json::to_string(&status_uids)?)
+        .bind(comment)
+        .bind(account_uid)
+        .bind(target_account_uid)
+        .bind(uri)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(uid)
+    }
+
+    /// All reports. Newest first.
+    pub async fn all() -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let reports: Vec<Self> = sqlx::query_as(
+            "SELECT rowid, * FROM report ORDER BY created_at DESC, rowid DESC",
+        )
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(reports)
+    }
+
+    /// Mark report as resolved by the account.
+    pub async fn resolve(
+        uid: String,
+        action_taken_by_account_uid: String,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE report SET action_taken_at = unixepoch(), action_taken_by_account_uid = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(action_taken_by_account_uid)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<Report>>;
+}
+
+#[async_trait]
+impl Get<(String, String)> for Report {
+    async fn get((key, val): (String, String)) -> Result<Vec<Report>> {
+        let query_template =
+            format!("SELECT rowid, * FROM report WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let reports = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(reports)
+    }
+}
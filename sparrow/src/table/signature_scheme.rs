@@ -0,0 +1,58 @@
+//! signature_scheme table
+//!
+//! HTTP signature scheme accepted by each domain.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+
+/// signature_scheme table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct SignatureScheme {
+    pub rowid: Option<i64>,
+    pub domain: String,
+    /// `rfc9421` or `cavage`
+    pub scheme: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl SignatureScheme {
+    /// Remember the scheme the domain accepted.
+    pub async fn put(domain: String, scheme: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT INTO signature_scheme(domain, scheme) VALUES (?, ?) ON CONFLICT(domain) DO UPDATE SET scheme = excluded.scheme, updated_at = unixepoch()",
+        )
+        .bind(domain)
+        .bind(scheme)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<SignatureScheme>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for SignatureScheme {
+    async fn get((key, val): (String, String)) -> Result<Vec<SignatureScheme>> {
+        let query_template =
+            format!("SELECT rowid, * FROM signature_scheme WHERE {} = ?", key);
+        let sqlx_conn = dbcon::open_default()?;
+        let schemes = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(schemes)
+    }
+}
@@ -31,7 +31,7 @@ use crate::table::New;
     Iterable,
 )]
 pub struct Status {
-    pub rowid: Option<String>,
+    pub rowid: Option<i64>,
     pub uid: String,
     pub uri: Option<String>,
     pub text: String,
@@ -71,6 +71,103 @@ impl Status {
         .await?;
         Ok(cnt as u32)
     }
+
+    /// Statuses from given account and accounts it follows. Newest first.  
+    /// Statuses of blocked/blocking accounts are hidden.  
+    /// Private statuses need a follow and direct ones a mention of the
+    /// account, unless they are its own.  
+    pub async fn home_timeline(
+        account_uid: String,
+        limit: i64,
+    ) -> Result<Vec<Status>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let statuses: Vec<Status> = sqlx::query_as(
+            "SELECT rowid, * FROM status WHERE deleted_at IS NULL AND (account_id = ? OR account_id IN (SELECT target_account_uid FROM follow WHERE account_uid = ? AND pending = false)) AND account_id NOT IN (SELECT account_uid FROM block WHERE target_account_uid = ? UNION SELECT target_account_uid FROM block WHERE account_uid = ?) AND (visibility < 2 OR account_id = ? OR (visibility = 2 AND account_id IN (SELECT target_account_uid FROM follow WHERE account_uid = ? AND pending = false)) OR uid IN (SELECT activity_id FROM notification WHERE type = 'mention' AND activity_type = 'Status' AND account_id = ?)) ORDER BY created_at DESC LIMIT ?",
+        )
+        .bind(account_uid.to_owned())
+        .bind(account_uid.to_owned())
+        .bind(account_uid.to_owned())
+        .bind(account_uid.to_owned())
+        .bind(account_uid.to_owned())
+        .bind(account_uid.to_owned())
+        .bind(account_uid)
+        .bind(limit)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(statuses)
+    }
+
+    /// Apply edit to the status.
+    pub async fn edit(
+        uid: String,
+        text: String,
+        spoiler_text: String,
+        sensitive: bool,
+        edited_at: i64,
+    ) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE status SET text = ?, spoiler_text = ?, sensitive = ?, edited_at = ?, updated_at = ? WHERE uid = ?",
+        )
+        .bind(text)
+        .bind(spoiler_text)
+        .bind(sensitive)
+        .bind(edited_at)
+        .bind(edited_at)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Count boosts of the status.
+    pub async fn reblogs_count(uid: String) -> Result<i64> {
+        let sqlx_conn = dbcon::open_default()?;
+        let (cnt,): (i64,) = sqlx::query_as(
+            "SELECT count(rowid) AS CNT FROM status WHERE reblog_of_id = ? AND deleted_at IS NULL",
+        )
+        .bind(uid)
+        .fetch_one(&sqlx_conn)
+        .await?;
+        Ok(cnt)
+    }
+
+    /// Count replies of the status.
+    pub async fn replies_count(uid: String) -> Result<i64> {
+        let sqlx_conn = dbcon::open_default()?;
+        let (cnt,): (i64,) = sqlx::query_as(
+            "SELECT count(rowid) AS CNT FROM status WHERE in_reply_to_id = ? AND deleted_at IS NULL",
+        )
+        .bind(uid)
+        .fetch_one(&sqlx_conn)
+        .await?;
+        Ok(cnt)
+    }
+
+    /// Attach poll to the status.
+    pub async fn set_poll_id(uid: String, poll_id: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE status SET poll_id = ?, updated_at = unixepoch() WHERE uid = ?",
+        )
+        .bind(poll_id)
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark the status as deleted. Row is kept for the Tombstone.
+    pub async fn delete(uid: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "UPDATE status SET deleted_at = unixepoch(), updated_at = unixepoch() WHERE uid = ? AND deleted_at IS NULL",
+        )
+        .bind(uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -114,6 +211,8 @@ impl TryFrom<MStatus> for Status {
                 }
             })(mstatus.visibility.as_str()),
             spoiler_text: mstatus.spoiler_text,
+            local: Some(mstatus.account.local()),
+            account_id: mstatus.account.uid.to_string(),
             ..Default::default()
         };
         Ok(status)
@@ -0,0 +1,62 @@
+//! status_edit table
+//!
+//! Keeps every version of an edited status.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+use crate::table::status::Status as TStatus;
+
+/// status_edit table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct StatusEdit {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// Edited status's uid
+    pub status_uid: String,
+    pub account_uid: Option<String>,
+    pub text: String,
+    pub spoiler_text: String,
+    /// When this version was made.
+    pub created_at: i64,
+    pub updated_at: Option<i64>,
+    pub ordered_media_attachment_ids: Option<String>,
+    pub sensitive: Option<bool>,
+}
+
+impl StatusEdit {
+    /// Snapshot current version of the status.
+    pub async fn new(tstatus: &TStatus, created_at: i64) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT INTO status_edit(uid, status_uid, account_uid, text, spoiler_text, created_at, ordered_media_attachment_ids, sensitive) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(tstatus.uid.to_owned())
+        .bind(tstatus.account_id.to_owned())
+        .bind(tstatus.text.to_owned())
+        .bind(tstatus.spoiler_text.to_owned())
+        .bind(created_at)
+        .bind(tstatus.ordered_media_attachment_ids.to_owned())
+        .bind(tstatus.sensitive != 0)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// All versions of the status. Oldest first.
+    pub async fn history(status_uid: String) -> Result<Vec<StatusEdit>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let edits: Vec<StatusEdit> = sqlx::query_as(
+            "SELECT rowid, * FROM status_edit WHERE status_uid = ? ORDER BY created_at ASC, rowid ASC",
+        )
+        .bind(status_uid)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(edits)
+    }
+}
@@ -0,0 +1,78 @@
+//! status_pin table
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// status_pin table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct StatusPin {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    /// Account's uid who pinned.
+    pub account_uid: String,
+    /// Pinned status's uid.
+    pub status_uid: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl StatusPin {
+    /// Pin status to account's profile.
+    pub async fn new(account_uid: String, status_uid: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO status_pin(uid, account_uid, status_uid) VALUES (?, ?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(account_uid)
+        .bind(status_uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Unpin status.
+    pub async fn remove(account_uid: String, status_uid: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "DELETE FROM status_pin WHERE account_uid = ? AND status_uid = ?",
+        )
+        .bind(account_uid)
+        .bind(status_uid)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Is status pinned by the account?
+    pub async fn is_exist(
+        account_uid: String,
+        status_uid: String,
+    ) -> Result<bool> {
+        let sqlx_conn = dbcon::open_default()?;
+        let (cnt,): (i64,) = sqlx::query_as(
+            "SELECT count(*) FROM status_pin WHERE account_uid = ? AND status_uid = ?",
+        )
+        .bind(account_uid)
+        .bind(status_uid)
+        .fetch_one(&sqlx_conn)
+        .await?;
+        Ok(cnt > 0)
+    }
+
+    /// Pins of the account. Newest first.
+    pub async fn pins(account_uid: String) -> Result<Vec<Self>> {
+        let sqlx_conn = dbcon::open_default()?;
+        let pins: Vec<Self> = sqlx::query_as(
+            "SELECT rowid, * FROM status_pin WHERE account_uid = ? ORDER BY created_at DESC, rowid DESC",
+        )
+        .bind(account_uid)
+        .fetch_all(&sqlx_conn)
+        .await?;
+        Ok(pins)
+    }
+}
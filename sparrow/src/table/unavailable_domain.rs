@@ -0,0 +1,72 @@
+//! unavailable_domain table
+//!
+//! Domains nothing is delivered to.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use spin_sqlx::sqlite::Connection as dbcon;
+use uuid::Uuid;
+
+/// unavailable_domain table in Database
+#[derive(
+    Serialize, Deserialize, Default, Clone, Debug, PartialEq, sqlx::FromRow,
+)]
+pub struct UnavailableDomain {
+    pub rowid: Option<i64>,
+    pub uid: String,
+    pub domain: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl UnavailableDomain {
+    /// Mark the domain unavailable.
+    pub async fn new(domain: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO unavailable_domain(uid, domain) VALUES (?, ?)",
+        )
+        .bind(Uuid::now_v7().to_string())
+        .bind(domain)
+        .execute(&sqlx_conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark the domain available again.
+    pub async fn remove(domain: String) -> Result<()> {
+        let sqlx_conn = dbcon::open_default()?;
+        sqlx::query("DELETE FROM unavailable_domain WHERE domain = ?")
+            .bind(domain)
+            .execute(&sqlx_conn)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+pub trait Get<T> {
+    async fn get(arg: T) -> Result<Vec<UnavailableDomain>>;
+}
+
+/// Very generic table Get function
+/// Geting (key: String, val: String).
+/// This goes `SELECT * FROM some_table WHERER key = val`
+#[async_trait]
+impl Get<(String, String)> for UnavailableDomain {
+    async fn get(
+        (key, val): (String, String),
+    ) -> Result<Vec<UnavailableDomain>> {
+        let query_template = format!(
+            "SELECT rowid, * FROM unavailable_domain WHERE {} = ?",
+            key
+        );
+        let sqlx_conn = dbcon::open_default()?;
+        let domains = sqlx::query_as(query_template.as_str())
+            .bind(val)
+            .fetch_all(&sqlx_conn)
+            .await?;
+        Ok(domains)
+    }
+}
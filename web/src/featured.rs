@@ -0,0 +1,74 @@
+//! Featured collection. Statuses pinned by the owner.
+//! GET /collections/featured
+//!
+//! Mastodon doc: <https://docs.joinmastodon.org/spec/activitypub/#featured>
+
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::object::note::Note;
+use sparrow::activitystream::ordered_collection::OrderedCollection;
+use sparrow::mastodon::setting::Setting;
+use sparrow::mastodon::status_pin::StatusPin;
+
+pub async fn req(req: Request, params: Params) -> anyhow::Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, _params: Params) -> anyhow::Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let (account, _user) =
+        sparrow::mastodon::account::Account::default().await?;
+
+    let mut ordered_items = Vec::new();
+    for tstatus in StatusPin::pins(&account).await? {
+        if !public(tstatus.visibility) {
+            continue;
+        }
+        let note = Note::fr_tstatus(tstatus).await?;
+        ordered_items.push(serde_json::to_value(note).unwrap());
+    }
+
+    let id =
+        format!("https://{}/collections/featured", Setting::domain().await);
+    let mut featured = OrderedCollection::new(id, ordered_items.len() as i64);
+    featured.first = None;
+    featured.ordered_items = Some(ordered_items);
+
+    let s = serde_json::to_string(&featured).unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header(
+            "Content-Type",
+            "application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"",
+        )
+        .body(s)
+        .build())
+}
+
+/// Followers only and direct statuses are never featured.
+fn public(visibility: i64) -> bool {
+    visibility != 2 && visibility != 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_public_and_unlisted_are_featured() {
+        assert!(public(0));
+        assert!(public(1));
+        assert!(!public(2));
+        assert!(!public(3));
+    }
+}
@@ -5,6 +5,8 @@ use spin_sdk::{
 use tracing_subscriber::{filter::EnvFilter, FmtSubscriber};
 
 pub mod actor;
+pub mod featured;
+pub mod status;
 pub(crate) mod http_response;
 pub(crate) mod util;
 
@@ -58,6 +60,12 @@ async fn handle_route(req: Request) -> Response {
     router.any_async(format!("@{}", owner).as_str(), actor::req);
     router.any_async(format!("/users/{}", owner).as_str(), actor::req);
 
+    // Collections
+    router.any_async("/collections/featured", featured::req);
+
+    // Objects
+    router.any_async("/statuses/:id", status::req);
+
     router.handle_async(req).await
 }
 
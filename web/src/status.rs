@@ -0,0 +1,77 @@
+//! Status object of the owner.
+//! GET /statuses/:id
+//!
+//! Deleted statuses answer 410 with a Tombstone so remote caches purge them.
+
+use spin_sdk::http::{Method, Params, Request, Response};
+
+use crate::http_response::HttpResponse;
+use sparrow::activitystream::object::note::Note;
+use sparrow::activitystream::object::question::Question;
+use sparrow::activitystream::object::tombstone::Tombstone;
+use sparrow::table::status::Get as _;
+use sparrow::table::status::Status as TStatus;
+use sparrow::utils::convert_epoch_to_iso_8601;
+
+pub async fn req(req: Request, params: Params) -> anyhow::Result<Response> {
+    match req.method() {
+        Method::Get => get(req, params).await,
+        _ => HttpResponse::not_found(),
+    }
+}
+
+pub async fn get(req: Request, params: Params) -> anyhow::Result<Response> {
+    tracing::debug!(
+        "requested -> {} {}",
+        req.method().to_string(),
+        req.path_and_query().unwrap()
+    );
+
+    let uid = params.get("id").unwrap_or_default().to_string();
+
+    let (account, _user) =
+        sparrow::mastodon::account::Account::default().await?;
+
+    let tstatus = match TStatus::get(("uid".to_string(), uid))
+        .await?
+        .into_iter()
+        .find(|x| x.account_id == account.uid.to_string())
+    {
+        Some(t) => t,
+        None => return HttpResponse::not_found(),
+    };
+
+    if let Some(deleted_at) = tstatus.deleted_at {
+        let former_type = match tstatus.poll_id {
+            Some(_) => "Question",
+            None => "Note",
+        };
+        let tombstone = Tombstone::new(
+            tstatus.uri.unwrap_or_default(),
+            Some(former_type.to_string()),
+            Some(convert_epoch_to_iso_8601(deleted_at)),
+        );
+        return Ok(Response::builder()
+            .status(410)
+            .header("Content-Type", "application/activity+json")
+            .body(serde_json::to_string(&tombstone).unwrap())
+            .build());
+    }
+
+    // Followers only and direct statuses are not public.
+    if tstatus.visibility == 2 || tstatus.visibility == 3 {
+        return HttpResponse::not_found();
+    }
+
+    let object = match tstatus.poll_id {
+        Some(_) => serde_json::to_string(&Question::fr_tstatus(tstatus).await?),
+        None => serde_json::to_string(&Note::fr_tstatus(tstatus).await?),
+    }
+    .unwrap();
+
+    Ok(Response::builder()
+        .status(200)
+        .header("Content-Type", "application/activity+json")
+        .body(object)
+        .build())
+}
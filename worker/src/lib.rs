@@ -0,0 +1,52 @@
+//! Background worker triggered by cron.
+//!
+//! Drains queued inbound activities, delivers queued outbound activities
+//! and sends final results of expired polls.
+
+use spin_cron_sdk::{cron_component, Error, Metadata};
+use tracing_subscriber::{filter::EnvFilter, FmtSubscriber};
+use uuid::Uuid;
+
+use sparrow::mastodon::account::Account as MAccount;
+use sparrow::mastodon::delivery::Delivery;
+use sparrow::mastodon::job::{Job, INBOX_QUEUE};
+use sparrow::mastodon::poll::Poll as MPoll;
+use sparrow::mastodon::ME_ACCOUNT;
+use sparrow::REQUEST_UID;
+
+#[cron_component]
+async fn handle_cron_event(metadata: Metadata) -> Result<(), Error> {
+    let subscriber = FmtSubscriber::builder()
+        .with_env_filter(EnvFilter::from_env("APP_LOG_LEVEL"))
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("setting default subscriber failed");
+
+    tracing::trace!("<--------- (CRON) {} --------->", metadata.timestamp);
+
+    if let Err(e) = REQUEST_UID.set(Uuid::now_v7()) {
+        tracing::error!("REQUEST_UID sentting error {e:?}")
+    }
+
+    work().await.map_err(|e| {
+        tracing::error!("Worker error -> {e:?}");
+        Error::Other(e.to_string())
+    })
+}
+
+async fn work() -> anyhow::Result<()> {
+    let (me, _) = MAccount::default().await?;
+    if let Err(e) = ME_ACCOUNT.set(me.to_owned()) {
+        tracing::error!("ME_ACCOUNT sentting error {e:?}")
+    }
+
+    let finished = Job::drain(INBOX_QUEUE, &me).await?;
+    tracing::debug!("{} inbox jobs finished", finished);
+
+    MPoll::close_expired(&me).await?;
+
+    let delivered = Delivery::drain().await?;
+    tracing::debug!("{} deliveries sent", delivered);
+
+    Ok(())
+}